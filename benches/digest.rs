@@ -0,0 +1,51 @@
+//! Canonical encoding plus `digest32` over small, medium, and large messages,
+//! and VRF evaluation over an experience record digest.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use prost::Message;
+use ucf_protocol::testing::decode_fixture;
+use ucf_protocol::ucf::v1::{ExperienceRecord, ReasonCodes, ToolRegistryContainer};
+use ucf_protocol::{canonical_bytes, digest32, Domain};
+use ucf_vrf::VrfEngine;
+
+fn bench_message<M: Message>(c: &mut Criterion, size: &str, schema_id: &str, message: &M) {
+    let mut group = c.benchmark_group("canonical_digest");
+    group.throughput(Throughput::Bytes(message.encoded_len() as u64));
+    group.bench_function(BenchmarkId::new(size, schema_id), |b| {
+        b.iter(|| {
+            let bytes = canonical_bytes(black_box(message));
+            black_box(digest32(Domain::Core, schema_id, "1", &bytes))
+        })
+    });
+    group.finish();
+}
+
+fn canonical_digest(c: &mut Criterion) {
+    let small: ReasonCodes = decode_fixture("reason_codes_basic");
+    let medium: ExperienceRecord = decode_fixture("experience_rt_action_exec");
+    let large: ToolRegistryContainer = decode_fixture("tool_registry_container");
+    bench_message(c, "small", "ucf.v1.ReasonCodes", &small);
+    bench_message(c, "medium", "ucf.v1.ExperienceRecord", &medium);
+    bench_message(c, "large", "ucf.v1.ToolRegistryContainer", &large);
+}
+
+fn eval_record_vrf(c: &mut Criterion) {
+    let record: ExperienceRecord = decode_fixture("experience_rt_action_exec");
+    let record_digest =
+        digest32(Domain::Core, "ucf.v1.ExperienceRecord", "1", &canonical_bytes(&record));
+    let engine = VrfEngine::new_dev(1);
+    c.bench_function("eval_record_vrf", |b| {
+        b.iter(|| {
+            black_box(engine.eval_record_vrf(
+                [0u8; 32],
+                black_box(record_digest),
+                "charter:v3",
+                [2u8; 32],
+                engine.current_epoch(),
+            ))
+        })
+    });
+}
+
+criterion_group!(benches, canonical_digest, eval_record_vrf);
+criterion_main!(benches);
@@ -1,5 +1,9 @@
 use std::path::PathBuf;
 
+use prost::Message;
+use prost_types::field_descriptor_proto::Type;
+use prost_types::{DescriptorProto, FileDescriptorSet};
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let proto_dir = PathBuf::from("proto");
     let protos = [
@@ -22,12 +26,117 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         "proto/ucf/v1/proposal.proto",
         "proto/ucf/v1/activation.proto",
         "proto/ucf/v1/trace.proto",
+        "proto/ucf/v1/service.proto",
     ];
 
     println!("cargo:rerun-if-changed=proto");
 
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    let descriptor_path = out_dir.join("ucf_descriptor.bin");
     let mut config = prost_build::Config::new();
-    config.out_dir(PathBuf::from(std::env::var("OUT_DIR").unwrap()));
+    config.out_dir(&out_dir);
+    config.file_descriptor_set_path(&descriptor_path);
+    add_grpc_services(&mut config);
     config.compile_protos(&protos, &[proto_dir])?;
+
+    // With the `serde` feature (implied by `json`), regenerate from the same
+    // descriptors with serde derives, base64url `bytes` fields, and enum fields
+    // named by their proto variants.
+    if std::env::var_os("CARGO_FEATURE_SERDE").is_some() {
+        let descriptors = FileDescriptorSet::decode(std::fs::read(&descriptor_path)?.as_slice())?;
+        let mut config = prost_build::Config::new();
+        config.out_dir(&out_dir);
+        config.type_attribute(".ucf", "#[derive(serde::Serialize, serde::Deserialize)]");
+        config.message_attribute(".ucf", "#[serde(default)]");
+        add_grpc_services(&mut config);
+        let mut enums = Vec::new();
+        for (path, kind) in serde_field_paths(&descriptors) {
+            match kind {
+                FieldKind::Bytes => {
+                    config.field_attribute(path, "#[serde(with = \"crate::serde_fields::bytes\")]");
+                }
+                FieldKind::Enum(name) => {
+                    let rust = format!("crate::ucf::v1::{name}");
+                    let with = |helper| format!("crate::serde_fields::{helper}::<{rust}, _, _>");
+                    config.field_attribute(
+                        path,
+                        format!(
+                            "#[serde(serialize_with = \"{}\", deserialize_with = \"{}\")]",
+                            with("serialize_enum"),
+                            with("deserialize_enum")
+                        ),
+                    );
+                    enums.push(rust);
+                }
+            }
+        }
+        enums.sort();
+        enums.dedup();
+        std::fs::write(
+            out_dir.join("ucf_serde_enums.rs"),
+            format!("proto_enum!({});\n", enums.join(", ")),
+        )?;
+        config.compile_fds(descriptors)?;
+    }
     Ok(())
 }
+
+/// With the `grpc` feature, generate tonic client and server modules for the
+/// services alongside the messages.
+#[cfg(feature = "grpc")]
+fn add_grpc_services(config: &mut prost_build::Config) {
+    config.service_generator(tonic_build::configure().service_generator());
+}
+
+#[cfg(not(feature = "grpc"))]
+fn add_grpc_services(_config: &mut prost_build::Config) {}
+
+/// How a field is represented by the serde derives.
+enum FieldKind {
+    Bytes,
+    /// An enum field, with the Rust name of its enum type.
+    Enum(String),
+}
+
+/// prost-build attribute paths of every `bytes` and enum field, including
+/// oneof members and fields of nested messages.
+fn serde_field_paths(descriptors: &FileDescriptorSet) -> Vec<(String, FieldKind)> {
+    let mut paths = Vec::new();
+    for file in &descriptors.file {
+        for message in &file.message_type {
+            collect_serde_fields(&format!(".{}", file.package()), message, &mut paths);
+        }
+    }
+    paths
+}
+
+fn collect_serde_fields(
+    parent: &str,
+    message: &DescriptorProto,
+    paths: &mut Vec<(String, FieldKind)>,
+) {
+    let message_path = format!("{parent}.{}", message.name());
+    for field in &message.field {
+        let kind = match field.r#type() {
+            Type::Bytes => FieldKind::Bytes,
+            // Enums are top level in the ucf package and already upper camel
+            // case, so the last segment of the type name is the Rust name.
+            Type::Enum => FieldKind::Enum(
+                field.type_name().rsplit('.').next().unwrap_or_default().to_string(),
+            ),
+            _ => continue,
+        };
+        let path = match field.oneof_index {
+            Some(index) if !field.proto3_optional() => format!(
+                "{message_path}.{}.{}",
+                message.oneof_decl[index as usize].name(),
+                field.name()
+            ),
+            _ => format!("{message_path}.{}", field.name()),
+        };
+        paths.push((path, kind));
+    }
+    for nested in &message.nested_type {
+        collect_serde_fields(&message_path, nested, paths);
+    }
+}
@@ -1,9 +1,19 @@
 //! PVGS receipt issuance helpers.
 
+use std::collections::HashSet;
+use std::fmt;
+
 use blake3::Hasher;
+use subtle::ConstantTimeEq;
+use ucf_protocol::trust::TrustConfig;
 use ucf_protocol::ucf::v1::{Digest32, ProofReceipt, ReceiptStatus, Signature};
+use ucf_protocol::Domain;
 use ucf_vrf::VrfEngine;
 
+pub use ucf_protocol::experience::{
+    record_digest_from_components, RECORD_DIGEST_DOMAIN, RECORD_DIGEST_VERSION,
+};
+
 /// Internal announcement format for PVGS key epochs.
 #[derive(Clone, Debug)]
 pub struct PvgsKeyEpoch {
@@ -14,8 +24,15 @@ pub struct PvgsKeyEpoch {
     pub signature: Vec<u8>,
 }
 
+/// Issues and checks [`ProofReceipt`]s with one VRF engine.
+///
+/// An issuer built with [`ProofReceiptIssuer::with_domain`] binds its domain tag
+/// into both the record digest ([`tenant_record_digest`]) and the VRF message
+/// (under [`TENANT_VRF_DOMAIN`]), so issuers with different tags never produce
+/// the same `vrf_digest`, even for identical inputs and the same key.
 pub struct ProofReceiptIssuer {
     vrf_engine: VrfEngine,
+    domain_tag: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -29,30 +46,81 @@ pub struct ProofReceiptInputs {
     pub commit_id: Vec<u8>,
     pub epoch_id: u64,
     pub validator: Signature,
+    /// Why the receipt was rejected; bound into the record digest only when
+    /// `status` is [`ReceiptStatus::Rejected`].
+    pub reason_codes: Vec<String>,
+}
+
+impl ProofReceiptInputs {
+    /// The record digest the receipt's VRF digest is evaluated over.
+    ///
+    /// For rejected receipts this is [`rejection_record_digest`] of the
+    /// component digest and `reason_codes`, so the reasons cannot be stripped
+    /// without changing the VRF digest.
+    pub fn record_digest(&self) -> [u8; 32] {
+        let record_digest = record_digest_from_components(
+            self.verified_fields_digest,
+            self.prev_record_digest,
+            &self.commit_id,
+        );
+        match self.status {
+            ReceiptStatus::Rejected => rejection_record_digest(record_digest, &self.reason_codes),
+            _ => record_digest,
+        }
+    }
 }
 
 impl ProofReceiptIssuer {
     pub fn new(vrf_engine: VrfEngine) -> Self {
-        Self { vrf_engine }
+        Self {
+            vrf_engine,
+            domain_tag: None,
+        }
+    }
+
+    /// An issuer whose digests are isolated to `domain_tag`, e.g. a tenant id.
+    pub fn with_domain(vrf_engine: VrfEngine, domain_tag: &str) -> Self {
+        Self {
+            vrf_engine,
+            domain_tag: Some(domain_tag.to_string()),
+        }
     }
 
     pub fn vrf_public_key(&self) -> &[u8] {
         self.vrf_engine.vrf_public_key()
     }
 
+    pub fn domain_tag(&self) -> Option<&str> {
+        self.domain_tag.as_deref()
+    }
+
+    /// The VRF digest this issuer derives from `inputs`.
+    fn vrf_digest(&self, inputs: &ProofReceiptInputs) -> [u8; 32] {
+        let Some(domain_tag) = &self.domain_tag else {
+            return self.vrf_engine.eval_record_vrf(
+                inputs.prev_record_digest,
+                inputs.record_digest(),
+                &inputs.charter_digest,
+                inputs.profile_digest,
+                inputs.epoch_id,
+            );
+        };
+        let record_digest = tenant_record_digest(domain_tag, inputs.record_digest());
+        self.vrf_engine.eval_vrf_in_domain(
+            TENANT_VRF_DOMAIN,
+            &[
+                domain_tag.as_bytes(),
+                &inputs.prev_record_digest,
+                &record_digest,
+                inputs.charter_digest.as_bytes(),
+                &inputs.profile_digest,
+                &inputs.epoch_id.to_le_bytes(),
+            ],
+        )
+    }
+
     pub fn issue_proof_receipt(&self, inputs: ProofReceiptInputs) -> ProofReceipt {
-        let record_digest = record_digest_from_components(
-            inputs.verified_fields_digest,
-            inputs.prev_record_digest,
-            &inputs.commit_id,
-        );
-        let vrf_digest = self.vrf_engine.eval_record_vrf(
-            inputs.prev_record_digest,
-            record_digest,
-            &inputs.charter_digest,
-            inputs.profile_digest,
-            inputs.epoch_id,
-        );
+        let vrf_digest = self.vrf_digest(&inputs);
 
         ProofReceipt {
             status: inputs.status as i32,
@@ -65,17 +133,215 @@ impl ProofReceiptIssuer {
             }),
         }
     }
+
+    /// Check that `receipt` is the one this issuer would mint for `inputs`.
+    ///
+    /// The status must be a known, specified [`ReceiptStatus`] equal to
+    /// `inputs.status`, both digests must be 32 bytes, `receipt_digest` and
+    /// `validator` must equal the inputs', and `vrf_digest` must equal the VRF
+    /// digest re-derived from `inputs`.
+    pub fn verify_proof_receipt(
+        &self,
+        receipt: &ProofReceipt,
+        inputs: &ProofReceiptInputs,
+    ) -> Result<(), PvgsError> {
+        match ReceiptStatus::try_from(receipt.status) {
+            Ok(ReceiptStatus::Unspecified) | Err(_) => {
+                return Err(PvgsError::UnknownStatus(receipt.status));
+            }
+            Ok(_) => {}
+        }
+        if receipt.status != inputs.status as i32 {
+            return Err(PvgsError::StatusMismatch {
+                expected: inputs.status as i32,
+                found: receipt.status,
+            });
+        }
+        let receipt_digest = digest32_field("receipt_digest", receipt.receipt_digest.as_ref())?;
+        let vrf_digest = digest32_field("vrf_digest", receipt.vrf_digest.as_ref())?;
+        if !bool::from(inputs.receipt_digest.ct_eq(receipt_digest)) {
+            return Err(PvgsError::ReceiptDigestMismatch);
+        }
+        if receipt.validator.as_ref() != Some(&inputs.validator) {
+            return Err(PvgsError::ValidatorMismatch);
+        }
+
+        let expected = self.vrf_digest(inputs);
+        if bool::from(expected.ct_eq(vrf_digest)) {
+            Ok(())
+        } else {
+            Err(PvgsError::VrfMismatch)
+        }
+    }
+}
+
+/// Errors returned by [`ProofReceiptIssuer::verify_proof_receipt`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PvgsError {
+    /// `vrf_digest` does not match the digest re-derived from the inputs.
+    VrfMismatch,
+    /// A digest field is missing or not 32 bytes long.
+    BadDigestLength { field: &'static str, len: usize },
+    /// `status` is unspecified or not a known [`ReceiptStatus`].
+    UnknownStatus(i32),
+    /// `status` differs from the inputs' status.
+    StatusMismatch { expected: i32, found: i32 },
+    /// `receipt_digest` differs from the inputs' receipt digest.
+    ReceiptDigestMismatch,
+    /// `validator` is missing or differs from the inputs' validator signature.
+    ValidatorMismatch,
+}
+
+impl fmt::Display for PvgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PvgsError::VrfMismatch => write!(f, "receipt vrf_digest does not match its inputs"),
+            PvgsError::BadDigestLength { field, len } => {
+                write!(f, "receipt {field} must be 32 bytes, found {len}")
+            }
+            PvgsError::UnknownStatus(status) => write!(f, "unknown receipt status {status}"),
+            PvgsError::StatusMismatch { expected, found } => {
+                write!(
+                    f,
+                    "receipt status {found} does not match expected {expected}"
+                )
+            }
+            PvgsError::ReceiptDigestMismatch => {
+                write!(f, "receipt receipt_digest does not match its inputs")
+            }
+            PvgsError::ValidatorMismatch => {
+                write!(f, "receipt validator does not match its inputs")
+            }
+        }
+    }
 }
 
-pub fn record_digest_from_components(
-    verified_fields_digest: [u8; 32],
-    prev_record_digest: [u8; 32],
-    commit_id: &[u8],
-) -> [u8; 32] {
+impl std::error::Error for PvgsError {}
+
+fn digest32_field<'a>(
+    field: &'static str,
+    digest: Option<&'a Digest32>,
+) -> Result<&'a [u8], PvgsError> {
+    let value = digest.map_or(&[][..], |digest| digest.value.as_slice());
+    if value.len() == 32 {
+        Ok(value)
+    } else {
+        Err(PvgsError::BadDigestLength {
+            field,
+            len: value.len(),
+        })
+    }
+}
+
+/// Errors returned when checking a [`ProofReceipt`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReceiptError {
+    /// The receipt has no `validator` signature.
+    MissingValidator,
+    /// `validator.signer` is not a recognised PVGS validator key.
+    UntrustedValidator,
+}
+
+impl fmt::Display for ReceiptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReceiptError::MissingValidator => write!(f, "receipt has no validator signature"),
+            ReceiptError::UntrustedValidator => {
+                write!(f, "receipt validator is not a trusted PVGS key")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReceiptError {}
+
+/// Confirm the receipt's `validator.signer` is one of the `trusted` PVGS keys.
+///
+/// Every trusted key is compared in constant time, so the check does not reveal
+/// which key (if any) matched.
+pub fn verify_validator_identity(
+    receipt: &ProofReceipt,
+    trusted: &HashSet<Vec<u8>>,
+) -> Result<(), ReceiptError> {
+    check_validator_signer(receipt, trusted.iter().map(Vec::as_slice))
+}
+
+/// [`verify_validator_identity`] against the keys trusted by `trust`.
+pub fn verify_validator_identity_with(
+    receipt: &ProofReceipt,
+    trust: &TrustConfig,
+) -> Result<(), ReceiptError> {
+    check_validator_signer(
+        receipt,
+        trust
+            .trusted_keys()
+            .map(|(_, key)| key.as_bytes().as_slice()),
+    )
+}
+
+fn check_validator_signer<'a>(
+    receipt: &ProofReceipt,
+    trusted: impl Iterator<Item = &'a [u8]>,
+) -> Result<(), ReceiptError> {
+    let signer = &receipt
+        .validator
+        .as_ref()
+        .ok_or(ReceiptError::MissingValidator)?
+        .signer;
+    let matched = trusted.fold(subtle::Choice::from(0), |matched, key| {
+        matched | key.ct_eq(signer)
+    });
+    if bool::from(matched) {
+        Ok(())
+    } else {
+        Err(ReceiptError::UntrustedValidator)
+    }
+}
+
+/// Domain tag prepended to every [`rejection_record_digest`] hash.
+pub const REJECTION_DIGEST_DOMAIN: &[u8] = Domain::PvgsRejectionDigest.as_bytes();
+
+/// Bind sorted `reason_codes` to the `record_digest` of a rejected receipt.
+///
+/// BLAKE3 of `REJECTION_DIGEST_DOMAIN || record_digest || count (u32 LE)`
+/// followed by each sorted code as `len (u32 LE) || utf8`, so the result does
+/// not depend on the order the codes were supplied in.
+pub fn rejection_record_digest(record_digest: [u8; 32], reason_codes: &[String]) -> [u8; 32] {
+    let mut sorted: Vec<&str> = reason_codes.iter().map(String::as_str).collect();
+    sorted.sort_unstable();
+
     let mut hasher = Hasher::new();
-    hasher.update(&verified_fields_digest);
-    hasher.update(&prev_record_digest);
-    hasher.update(commit_id);
+    hasher.update(REJECTION_DIGEST_DOMAIN);
+    hasher.update(&record_digest);
+    hasher.update(&(sorted.len() as u32).to_le_bytes());
+    for code in sorted {
+        hasher.update(&(code.len() as u32).to_le_bytes());
+        hasher.update(code.as_bytes());
+    }
+    *hasher.finalize().as_bytes()
+}
+
+/// Domain of the VRF message for issuers built with [`ProofReceiptIssuer::with_domain`].
+///
+/// The message is framed by `VrfEngine::build_message` over this tag and the
+/// components: the issuer's UTF-8 `domain_tag`, `prev_record_digest`, the
+/// [`tenant_record_digest`], the UTF-8 `charter_digest`, `profile_digest`, and
+/// `epoch_id` (u64 LE).
+pub const TENANT_VRF_DOMAIN: &[u8] = Domain::VrfTenantRecord.as_bytes();
+
+/// Domain tag prepended to every [`tenant_record_digest`] hash.
+pub const TENANT_RECORD_DIGEST_DOMAIN: &[u8] = Domain::PvgsTenantRecordDigest.as_bytes();
+
+/// Bind `record_digest` to an issuer's `domain_tag`.
+///
+/// BLAKE3 of `TENANT_RECORD_DIGEST_DOMAIN || len (u32 LE) || domain_tag ||
+/// record_digest`.
+pub fn tenant_record_digest(domain_tag: &str, record_digest: [u8; 32]) -> [u8; 32] {
+    let mut hasher = Hasher::new();
+    hasher.update(TENANT_RECORD_DIGEST_DOMAIN);
+    hasher.update(&(domain_tag.len() as u32).to_le_bytes());
+    hasher.update(domain_tag.as_bytes());
+    hasher.update(&record_digest);
     *hasher.finalize().as_bytes()
 }
 
@@ -106,6 +372,7 @@ mod tests {
             commit_id: b"commit-abc123".to_vec(),
             epoch_id: vrf_engine.current_epoch(),
             validator: sample_signature(),
+            reason_codes: Vec::new(),
         });
 
         let vrf_digest = receipt
@@ -134,4 +401,285 @@ mod tests {
             "VRF digest should be deterministic"
         );
     }
+
+    #[test]
+    fn validator_identity_requires_trusted_signer() {
+        let vrf_engine = VrfEngine::new_dev(5);
+        let issuer = ProofReceiptIssuer::new(vrf_engine.clone());
+        let receipt = issuer.issue_proof_receipt(ProofReceiptInputs {
+            status: ReceiptStatus::Accepted,
+            receipt_digest: [9u8; 32],
+            verified_fields_digest: [3u8; 32],
+            prev_record_digest: [0u8; 32],
+            charter_digest: "charter-digest".to_string(),
+            profile_digest: [2u8; 32],
+            commit_id: b"commit-abc123".to_vec(),
+            epoch_id: vrf_engine.current_epoch(),
+            validator: sample_signature(),
+            reason_codes: Vec::new(),
+        });
+
+        let trusted: HashSet<Vec<u8>> = [vec![0xAA; 32], vec![0xCC; 32]].into_iter().collect();
+        assert_eq!(verify_validator_identity(&receipt, &trusted), Ok(()));
+        assert_eq!(
+            verify_validator_identity(&receipt, &HashSet::from([vec![0xCC; 32]])),
+            Err(ReceiptError::UntrustedValidator)
+        );
+
+        let unsigned = ProofReceipt {
+            validator: None,
+            ..receipt.clone()
+        };
+        assert_eq!(
+            verify_validator_identity(&unsigned, &trusted),
+            Err(ReceiptError::MissingValidator)
+        );
+
+        let key = ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]).verifying_key();
+        let trust = TrustConfig::new().trust_key("pvgs-1", key);
+        assert_eq!(
+            verify_validator_identity_with(&receipt, &trust),
+            Err(ReceiptError::UntrustedValidator)
+        );
+        let mut signed_by_key = receipt;
+        signed_by_key.validator.as_mut().unwrap().signer = key.as_bytes().to_vec();
+        assert_eq!(
+            verify_validator_identity_with(&signed_by_key, &trust),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn verify_proof_receipt_checks_vrf_digest_status_and_lengths() {
+        let vrf_engine = VrfEngine::new_dev(5);
+        let issuer = ProofReceiptIssuer::new(vrf_engine.clone());
+        let inputs = ProofReceiptInputs {
+            status: ReceiptStatus::Accepted,
+            receipt_digest: [9u8; 32],
+            verified_fields_digest: [3u8; 32],
+            prev_record_digest: [0u8; 32],
+            charter_digest: "charter-digest".to_string(),
+            profile_digest: [2u8; 32],
+            commit_id: b"commit-abc123".to_vec(),
+            epoch_id: vrf_engine.current_epoch(),
+            validator: sample_signature(),
+            reason_codes: Vec::new(),
+        };
+        let receipt = issuer.issue_proof_receipt(inputs.clone());
+        assert_eq!(issuer.verify_proof_receipt(&receipt, &inputs), Ok(()));
+
+        let other_commit = ProofReceiptInputs {
+            commit_id: b"commit-def456".to_vec(),
+            ..inputs.clone()
+        };
+        assert_eq!(
+            issuer.verify_proof_receipt(&receipt, &other_commit),
+            Err(PvgsError::VrfMismatch)
+        );
+        let other_issuer = ProofReceiptIssuer::new(VrfEngine::new_dev(6));
+        assert_eq!(
+            other_issuer.verify_proof_receipt(&receipt, &inputs),
+            Err(PvgsError::VrfMismatch)
+        );
+
+        for status in [0, 99] {
+            let bad_status = ProofReceipt {
+                status,
+                ..receipt.clone()
+            };
+            assert_eq!(
+                issuer.verify_proof_receipt(&bad_status, &inputs),
+                Err(PvgsError::UnknownStatus(status))
+            );
+        }
+
+        let short_receipt_digest = ProofReceipt {
+            receipt_digest: Some(Digest32 {
+                value: vec![9u8; 31],
+            }),
+            ..receipt.clone()
+        };
+        assert_eq!(
+            issuer.verify_proof_receipt(&short_receipt_digest, &inputs),
+            Err(PvgsError::BadDigestLength {
+                field: "receipt_digest",
+                len: 31
+            })
+        );
+        let missing_vrf_digest = ProofReceipt {
+            vrf_digest: None,
+            ..receipt
+        };
+        assert_eq!(
+            issuer.verify_proof_receipt(&missing_vrf_digest, &inputs),
+            Err(PvgsError::BadDigestLength {
+                field: "vrf_digest",
+                len: 0
+            })
+        );
+    }
+
+    #[test]
+    fn verify_proof_receipt_rejects_tampered_fields() {
+        let vrf_engine = VrfEngine::new_dev(5);
+        let issuer = ProofReceiptIssuer::new(vrf_engine.clone());
+        let inputs = ProofReceiptInputs {
+            status: ReceiptStatus::Rejected,
+            receipt_digest: [9u8; 32],
+            verified_fields_digest: [3u8; 32],
+            prev_record_digest: [0u8; 32],
+            charter_digest: "charter-digest".to_string(),
+            profile_digest: [2u8; 32],
+            commit_id: b"commit-abc123".to_vec(),
+            epoch_id: vrf_engine.current_epoch(),
+            validator: sample_signature(),
+            reason_codes: vec!["policy-denied".to_string()],
+        };
+        let receipt = issuer.issue_proof_receipt(inputs.clone());
+
+        let flipped = ProofReceipt {
+            status: ReceiptStatus::Accepted as i32,
+            ..receipt.clone()
+        };
+        assert_eq!(
+            issuer.verify_proof_receipt(&flipped, &inputs),
+            Err(PvgsError::StatusMismatch {
+                expected: ReceiptStatus::Rejected as i32,
+                found: ReceiptStatus::Accepted as i32,
+            })
+        );
+
+        let swapped_digest = ProofReceipt {
+            receipt_digest: Some(Digest32 {
+                value: vec![8u8; 32],
+            }),
+            ..receipt.clone()
+        };
+        assert_eq!(
+            issuer.verify_proof_receipt(&swapped_digest, &inputs),
+            Err(PvgsError::ReceiptDigestMismatch)
+        );
+
+        let mut other_validator = receipt.clone();
+        other_validator.validator.as_mut().unwrap().signer = vec![0xCC; 32];
+        assert_eq!(
+            issuer.verify_proof_receipt(&other_validator, &inputs),
+            Err(PvgsError::ValidatorMismatch)
+        );
+        let unsigned = ProofReceipt {
+            validator: None,
+            ..receipt
+        };
+        assert_eq!(
+            issuer.verify_proof_receipt(&unsigned, &inputs),
+            Err(PvgsError::ValidatorMismatch)
+        );
+    }
+
+    #[test]
+    fn record_digest_is_domain_separated() {
+        let verified_fields_digest = [3u8; 32];
+        let prev_record_digest = [0u8; 32];
+        let commit_id = b"commit-abc123";
+
+        let mut undomained = Hasher::new();
+        undomained.update(&verified_fields_digest);
+        undomained.update(&prev_record_digest);
+        undomained.update(commit_id);
+
+        let digest =
+            record_digest_from_components(verified_fields_digest, prev_record_digest, commit_id);
+        assert_ne!(digest, *undomained.finalize().as_bytes());
+        assert_eq!(
+            digest,
+            record_digest_from_components(verified_fields_digest, prev_record_digest, commit_id)
+        );
+    }
+
+    #[test]
+    fn rejected_receipts_bind_sorted_reason_codes() {
+        let vrf_engine = VrfEngine::new_dev(5);
+        let issuer = ProofReceiptIssuer::new(vrf_engine.clone());
+        let accepted_inputs = ProofReceiptInputs {
+            status: ReceiptStatus::Accepted,
+            receipt_digest: [9u8; 32],
+            verified_fields_digest: [3u8; 32],
+            prev_record_digest: [0u8; 32],
+            charter_digest: "charter-digest".to_string(),
+            profile_digest: [2u8; 32],
+            commit_id: b"commit-abc123".to_vec(),
+            epoch_id: vrf_engine.current_epoch(),
+            validator: sample_signature(),
+            reason_codes: vec!["policy-denied".to_string(), "budget".to_string()],
+        };
+        let rejected_inputs = ProofReceiptInputs {
+            status: ReceiptStatus::Rejected,
+            ..accepted_inputs.clone()
+        };
+
+        let accepted = issuer.issue_proof_receipt(accepted_inputs.clone());
+        let rejected = issuer.issue_proof_receipt(rejected_inputs.clone());
+        assert_ne!(accepted.vrf_digest, rejected.vrf_digest);
+        assert_eq!(
+            issuer.verify_proof_receipt(&rejected, &rejected_inputs),
+            Ok(())
+        );
+
+        let reordered = ProofReceiptInputs {
+            reason_codes: vec!["budget".to_string(), "policy-denied".to_string()],
+            ..rejected_inputs.clone()
+        };
+        assert_eq!(
+            issuer.issue_proof_receipt(reordered).vrf_digest,
+            rejected.vrf_digest
+        );
+
+        let stripped = ProofReceiptInputs {
+            reason_codes: Vec::new(),
+            ..rejected_inputs
+        };
+        assert_eq!(
+            issuer.verify_proof_receipt(&rejected, &stripped),
+            Err(PvgsError::VrfMismatch)
+        );
+    }
+
+    #[test]
+    fn domain_tags_isolate_vrf_digests() {
+        let vrf_engine = VrfEngine::new_dev(5);
+        let inputs = ProofReceiptInputs {
+            status: ReceiptStatus::Accepted,
+            receipt_digest: [9u8; 32],
+            verified_fields_digest: [3u8; 32],
+            prev_record_digest: [0u8; 32],
+            charter_digest: "charter-digest".to_string(),
+            profile_digest: [2u8; 32],
+            commit_id: b"commit-abc123".to_vec(),
+            epoch_id: vrf_engine.current_epoch(),
+            validator: sample_signature(),
+            reason_codes: Vec::new(),
+        };
+        let issuers = [
+            ProofReceiptIssuer::new(vrf_engine.clone()),
+            ProofReceiptIssuer::with_domain(vrf_engine.clone(), "tenant-a"),
+            ProofReceiptIssuer::with_domain(vrf_engine.clone(), "tenant-b"),
+        ];
+        assert_eq!(issuers[1].domain_tag(), Some("tenant-a"));
+
+        let receipts: Vec<ProofReceipt> = issuers
+            .iter()
+            .map(|issuer| issuer.issue_proof_receipt(inputs.clone()))
+            .collect();
+        for (i, issuer) in issuers.iter().enumerate() {
+            for (j, receipt) in receipts.iter().enumerate() {
+                let expected = if i == j {
+                    Ok(())
+                } else {
+                    assert_ne!(receipts[i].vrf_digest, receipt.vrf_digest);
+                    Err(PvgsError::VrfMismatch)
+                };
+                assert_eq!(issuer.verify_proof_receipt(receipt, &inputs), expected);
+            }
+        }
+    }
 }
@@ -0,0 +1,102 @@
+//! Compare per-call, scratch-buffer, domain, and batch VRF evaluation over many records.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ucf_vrf::{MessageBuffer, VrfEngine, VrfRecordInputs};
+
+const BATCH: u64 = 1_000;
+const LARGE_BATCH: u64 = 10_000;
+
+fn record_digest(i: u64) -> [u8; 32] {
+    *blake3::hash(&i.to_le_bytes()).as_bytes()
+}
+
+fn eval_record_vrf(c: &mut Criterion) {
+    let engine = VrfEngine::new_dev(1);
+    let mut group = c.benchmark_group("eval_record_vrf");
+    group.throughput(Throughput::Elements(BATCH));
+
+    group.bench_function(BenchmarkId::new("per_call", BATCH), |b| {
+        b.iter(|| {
+            for i in 0..BATCH {
+                black_box(engine.eval_record_vrf(
+                    [0u8; 32],
+                    record_digest(i),
+                    "charter-digest",
+                    [2u8; 32],
+                    engine.current_epoch(),
+                ));
+            }
+        })
+    });
+
+    group.bench_function(BenchmarkId::new("scratch_buffer", BATCH), |b| {
+        let mut buf = MessageBuffer::new();
+        b.iter(|| {
+            for i in 0..BATCH {
+                black_box(engine.eval_record_vrf_into(
+                    &mut buf,
+                    [0u8; 32],
+                    record_digest(i),
+                    "charter-digest",
+                    [2u8; 32],
+                    engine.current_epoch(),
+                ));
+            }
+        })
+    });
+
+    group.bench_function(BenchmarkId::new("in_domain", BATCH), |b| {
+        b.iter(|| {
+            for i in 0..BATCH {
+                let record_digest = record_digest(i);
+                black_box(engine.eval_vrf_in_domain(b"UCF:VRF:BENCH", &[&record_digest]));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn eval_record_vrf_batch(c: &mut Criterion) {
+    let engine = VrfEngine::new_dev(1);
+    let record_digests: Vec<[u8; 32]> = (0..LARGE_BATCH).map(record_digest).collect();
+    let inputs: Vec<VrfRecordInputs<'_>> = record_digests
+        .iter()
+        .map(|&record_digest| VrfRecordInputs {
+            prev_record_digest: [0u8; 32],
+            record_digest,
+            charter_digest: "charter-digest",
+            profile_digest: [2u8; 32],
+            epoch_id: engine.current_epoch(),
+        })
+        .collect();
+    let mut group = c.benchmark_group("eval_record_vrf_batch");
+    group.throughput(Throughput::Elements(LARGE_BATCH));
+    group.sample_size(10);
+
+    group.bench_function(BenchmarkId::new("loop", LARGE_BATCH), |b| {
+        b.iter(|| {
+            inputs
+                .iter()
+                .map(|input| {
+                    engine.eval_record_vrf(
+                        input.prev_record_digest,
+                        input.record_digest,
+                        input.charter_digest,
+                        input.profile_digest,
+                        input.epoch_id,
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.bench_function(BenchmarkId::new("batch", LARGE_BATCH), |b| {
+        b.iter(|| engine.eval_record_vrf_batch(black_box(&inputs)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, eval_record_vrf, eval_record_vrf_batch);
+criterion_main!(benches);
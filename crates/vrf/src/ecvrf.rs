@@ -0,0 +1,253 @@
+//! ECVRF-EDWARDS25519-SHA512-TAI (RFC 9381, suite string `0x03`).
+//!
+//! Keys are ordinary Ed25519 keys: the secret is the 32-byte seed and the
+//! public key is the compressed point `x*B`, where `x` is the clamped scalar
+//! derived from the seed as in RFC 8032.
+
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use sha2::{Digest, Sha512};
+
+const SUITE: u8 = 0x03;
+const ENCODE_TO_CURVE_FRONT: u8 = 0x01;
+const CHALLENGE_FRONT: u8 = 0x02;
+const PROOF_TO_HASH_FRONT: u8 = 0x03;
+const BACK: u8 = 0x00;
+
+/// Length in bytes of an encoded [`EcvrfProof`].
+pub const PROOF_LEN: usize = 80;
+
+/// An ECVRF proof `pi = (Gamma, c, s)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EcvrfProof {
+    /// Compressed point `Gamma = x*H`.
+    pub gamma: [u8; 32],
+    /// Challenge, 16 bytes little-endian.
+    pub c: [u8; 16],
+    /// Response scalar, 32 bytes little-endian.
+    pub s: [u8; 32],
+}
+
+impl EcvrfProof {
+    /// `Gamma || c || s`.
+    pub fn to_bytes(&self) -> [u8; PROOF_LEN] {
+        let mut bytes = [0u8; PROOF_LEN];
+        bytes[..32].copy_from_slice(&self.gamma);
+        bytes[32..48].copy_from_slice(&self.c);
+        bytes[48..].copy_from_slice(&self.s);
+        bytes
+    }
+
+    /// Split an encoded proof, returning `None` if it is not [`PROOF_LEN`] bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != PROOF_LEN {
+            return None;
+        }
+        Some(Self {
+            gamma: bytes[..32].try_into().ok()?,
+            c: bytes[32..48].try_into().ok()?,
+            s: bytes[48..].try_into().ok()?,
+        })
+    }
+
+    /// The RFC 9381 output `beta`, or `None` if `gamma` is not a curve point.
+    ///
+    /// This does not check the proof; use [`verify`] for that.
+    pub fn beta(&self) -> Option<[u8; 64]> {
+        let gamma = CompressedEdwardsY(self.gamma).decompress()?;
+        Some(proof_to_hash(&gamma))
+    }
+}
+
+/// Compress `beta` to the 32-byte digest used in receipts: BLAKE3-256 of `beta`.
+pub fn beta_digest(beta: &[u8; 64]) -> [u8; 32] {
+    *blake3::hash(beta).as_bytes()
+}
+
+/// The public key for the Ed25519 seed `secret`.
+pub fn public_key(secret: &[u8; 32]) -> [u8; 32] {
+    let (x, _) = expand_secret(secret);
+    EdwardsPoint::mul_base(&x).compress().to_bytes()
+}
+
+/// Prove `alpha` under the Ed25519 seed `secret`.
+pub fn prove(secret: &[u8; 32], alpha: &[u8]) -> EcvrfProof {
+    let (x, nonce_prefix) = expand_secret(secret);
+    let y = EdwardsPoint::mul_base(&x).compress();
+    let h = encode_to_curve(&y, alpha);
+    let h_string = h.compress();
+    let gamma = x * h;
+
+    let k = Scalar::from_hash(
+        Sha512::new()
+            .chain_update(nonce_prefix)
+            .chain_update(h_string.as_bytes()),
+    );
+    let c = challenge(&[
+        y,
+        h_string,
+        gamma.compress(),
+        EdwardsPoint::mul_base(&k).compress(),
+        (k * h).compress(),
+    ]);
+    let s = k + challenge_scalar(&c) * x;
+
+    EcvrfProof {
+        gamma: gamma.compress().to_bytes(),
+        c,
+        s: s.to_bytes(),
+    }
+}
+
+/// Verify `proof` for `alpha` under `public`, returning `beta` if it is valid.
+///
+/// Public keys of small order are rejected, as RFC 9381 `validate_key` requires.
+pub fn verify(public: &[u8; 32], alpha: &[u8], proof: &EcvrfProof) -> Option<[u8; 64]> {
+    let y_string = CompressedEdwardsY(*public);
+    let y = y_string.decompress()?;
+    if y.is_small_order() {
+        return None;
+    }
+    let gamma = CompressedEdwardsY(proof.gamma).decompress()?;
+    let s = Option::<Scalar>::from(Scalar::from_canonical_bytes(proof.s))?;
+    let c = challenge_scalar(&proof.c);
+
+    let h = encode_to_curve(&y_string, alpha);
+    let u = EdwardsPoint::mul_base(&s) - c * y;
+    let v = s * h - c * gamma;
+    let expected = challenge(&[
+        y_string,
+        h.compress(),
+        gamma.compress(),
+        u.compress(),
+        v.compress(),
+    ]);
+
+    (expected == proof.c).then(|| proof_to_hash(&gamma))
+}
+
+/// The secret scalar `x` and the nonce prefix from `SHA-512(secret)`.
+fn expand_secret(secret: &[u8; 32]) -> (Scalar, [u8; 32]) {
+    let hashed = Sha512::digest(secret);
+    let mut scalar_bytes: [u8; 32] = hashed[..32].try_into().expect("SHA-512 output is 64 bytes");
+    scalar_bytes[0] &= 248;
+    scalar_bytes[31] &= 127;
+    scalar_bytes[31] |= 64;
+    let nonce_prefix = hashed[32..].try_into().expect("SHA-512 output is 64 bytes");
+    (Scalar::from_bytes_mod_order(scalar_bytes), nonce_prefix)
+}
+
+/// `ECVRF_encode_to_curve_try_and_increment` with the public key as salt.
+fn encode_to_curve(public: &CompressedEdwardsY, alpha: &[u8]) -> EdwardsPoint {
+    (0u8..=255)
+        .find_map(|ctr| {
+            let hash = Sha512::new()
+                .chain_update([SUITE, ENCODE_TO_CURVE_FRONT])
+                .chain_update(public.as_bytes())
+                .chain_update(alpha)
+                .chain_update([ctr, BACK])
+                .finalize();
+            let candidate: [u8; 32] = hash[..32].try_into().expect("SHA-512 output is 64 bytes");
+            CompressedEdwardsY(candidate).decompress()
+        })
+        .expect("try-and-increment finds a point within 256 attempts")
+        .mul_by_cofactor()
+}
+
+fn challenge(points: &[CompressedEdwardsY; 5]) -> [u8; 16] {
+    let mut hasher = Sha512::new().chain_update([SUITE, CHALLENGE_FRONT]);
+    for point in points {
+        hasher.update(point.as_bytes());
+    }
+    let hash = hasher.chain_update([BACK]).finalize();
+    hash[..16].try_into().expect("SHA-512 output is 64 bytes")
+}
+
+fn challenge_scalar(c: &[u8; 16]) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(c);
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+fn proof_to_hash(gamma: &EdwardsPoint) -> [u8; 64] {
+    Sha512::new()
+        .chain_update([SUITE, PROOF_TO_HASH_FRONT])
+        .chain_update(gamma.mul_by_cofactor().compress().as_bytes())
+        .chain_update([BACK])
+        .finalize()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Vector {
+        sk: &'static str,
+        pk: &'static str,
+        alpha: &'static str,
+        pi: &'static str,
+        beta: &'static str,
+    }
+
+    // RFC 9381, Appendix B.3, examples 16 and 17.
+    const VECTORS: &[Vector] = &[
+        Vector {
+            sk: "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            pk: "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            alpha: "",
+            pi: "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f26f8a57ccaed74ee1b190bed1f479d9727d2d0f9b005a6e456a35d4fb0daab1268a1b0db10836d9826a528ca76567805",
+            beta: "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae",
+        },
+        Vector {
+            sk: "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+            pk: "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            alpha: "72",
+            pi: "f3141cd382dc42909d19ec5110469e4feae18300e94f304590abdced48aed5933bf0864a62558b3ed7f2fea45c92a465301b3bbf5e3e54ddf2d935be3b67926da3ef39226bbc355bdc9850112c8f4b02",
+            beta: "eb4440665d3891d668e7e0fcaf587f1b4bd7fbfe99d0eb2211ccec90496310eb5e33821bc613efb94db5e5b54c70a848a0bef4553a41befc57663b56373a5031",
+        },
+    ];
+
+    fn bytes32(value: &str) -> [u8; 32] {
+        hex::decode(value).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn rfc9381_vectors_prove_and_verify() {
+        for vector in VECTORS {
+            let sk = bytes32(vector.sk);
+            let pk = bytes32(vector.pk);
+            let alpha = hex::decode(vector.alpha).unwrap();
+
+            assert_eq!(public_key(&sk), pk);
+            let proof = prove(&sk, &alpha);
+            assert_eq!(hex::encode(proof.to_bytes()), vector.pi);
+            assert_eq!(EcvrfProof::from_bytes(&proof.to_bytes()), Some(proof));
+
+            let beta = verify(&pk, &alpha, &proof).expect("RFC proof verifies");
+            assert_eq!(hex::encode(beta), vector.beta);
+            assert_eq!(proof.beta(), Some(beta));
+        }
+    }
+
+    #[test]
+    fn verify_rejects_wrong_inputs() {
+        let vector = &VECTORS[1];
+        let pk = bytes32(vector.pk);
+        let proof = prove(&bytes32(vector.sk), &[0x72]);
+
+        assert!(verify(&pk, &[0x73], &proof).is_none());
+        assert!(verify(&bytes32(VECTORS[0].pk), &[0x72], &proof).is_none());
+
+        let mut tampered = proof;
+        tampered.s[0] ^= 0x01;
+        assert!(verify(&pk, &[0x72], &tampered).is_none());
+
+        let identity = EdwardsPoint::default().compress().to_bytes();
+        assert!(
+            verify(&identity, &[0x72], &proof).is_none(),
+            "small-order keys are rejected"
+        );
+        assert!(EcvrfProof::from_bytes(&proof.to_bytes()[..79]).is_none());
+    }
+}
@@ -4,15 +4,67 @@
 //! deterministic Ed25519 signature and then compressing it with BLAKE3. The
 //! design is intentionally marked as `TEMPORARY_VRF` so it can be replaced by a
 //! standards-compliant ECVRF-ED25519-SHA512-TAI implementation later.
+//!
+//! With the `production-vrf` feature, [`VrfEngine::new_dev_ecvrf`] creates an
+//! engine that evaluates RFC 9381 ECVRF instead; see [`ecvrf`].
+//!
+//! Keys held outside the process, such as in a KMS, plug in through
+//! [`VrfSigner`] and [`VrfEngine::from_signer`].
+
+#[cfg(feature = "production-vrf")]
+pub mod ecvrf;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
 
 use blake3::Hasher;
-use ed25519_dalek::{Signature, Signer, SigningKey};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
 use sha2::{Digest, Sha512};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 const VRF_DOMAIN: &[u8] = b"UCF:VRF:EXPERIENCE_RECORD";
+/// Longest domain tag [`VrfEngine::eval_vrf_in_domain`] accepts.
+pub const MAX_DOMAIN_LEN: usize = 255;
+const VRF_COMMIT_DOMAIN: &[u8] = b"UCF:VRF:COMMIT";
+const VRF_ROTATE_DOMAIN: &[u8] = b"UCF:VRF:ROTATE";
+const VRF_SIGNER_PROBE_DOMAIN: &[u8] = b"UCF:VRF:SIGNER_PROBE";
 const TEMPORARY_VRF_LABEL: &str = "TEMPORARY_VRF";
+#[cfg(feature = "production-vrf")]
+const ECVRF_LABEL: &str = "ECVRF";
+
+/// How a [`VrfEngine`] derives digests; also the prefix of its `key_id`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VrfMode {
+    /// Hash of a deterministic Ed25519 signature (`TEMPORARY_VRF:`).
+    Temporary,
+    /// ECVRF-EDWARDS25519-SHA512-TAI (`ECVRF:`).
+    #[cfg(feature = "production-vrf")]
+    Ecvrf,
+}
+
+impl VrfMode {
+    fn from_key_id(key_id: &str) -> Self {
+        match key_id.split_once(':') {
+            #[cfg(feature = "production-vrf")]
+            Some((ECVRF_LABEL, _)) => VrfMode::Ecvrf,
+            _ => VrfMode::Temporary,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            VrfMode::Temporary => TEMPORARY_VRF_LABEL,
+            #[cfg(feature = "production-vrf")]
+            VrfMode::Ecvrf => ECVRF_LABEL,
+        }
+    }
+}
 
 /// Key material for the VRF engine.
+///
+/// `vrf_sk` is zeroized when the keypair is dropped.
 #[derive(Clone, Debug)]
 pub struct VrfKeypair {
     pub key_id: String,
@@ -21,45 +73,411 @@ pub struct VrfKeypair {
     pub vrf_sk: Vec<u8>,
 }
 
+impl Zeroize for VrfKeypair {
+    fn zeroize(&mut self) {
+        self.vrf_sk.zeroize();
+    }
+}
+
+impl Drop for VrfKeypair {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for VrfKeypair {}
+
+impl VrfKeypair {
+    /// Encode as `epoch_id` (u64 LE) followed by `key_id`, `vrf_pk`, and
+    /// `vrf_sk`, each prefixed with its length as u32 LE.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let fields = [self.key_id.as_bytes(), &self.vrf_pk, &self.vrf_sk];
+        let mut bytes = Vec::with_capacity(
+            std::mem::size_of::<u64>()
+                + fields
+                    .iter()
+                    .map(|field| std::mem::size_of::<u32>() + field.len())
+                    .sum::<usize>(),
+        );
+        bytes.extend_from_slice(&self.epoch_id.to_le_bytes());
+        for field in fields {
+            let len = u32::try_from(field.len()).expect("keypair fields fit in u32 lengths");
+            bytes.extend_from_slice(&len.to_le_bytes());
+            bytes.extend_from_slice(field);
+        }
+        bytes
+    }
+
+    /// Decode the layout written by [`VrfKeypair::to_bytes`], rejecting
+    /// truncated input and trailing bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, VrfError> {
+        let mut rest = bytes;
+        let epoch_id = u64::from_le_bytes(take(&mut rest, 8)?.try_into().expect("took 8 bytes"));
+        let key_id = String::from_utf8(take_prefixed(&mut rest)?.to_vec())
+            .map_err(|_| VrfError::MalformedKeypair)?;
+        let vrf_pk = take_prefixed(&mut rest)?.to_vec();
+        let vrf_sk = take_prefixed(&mut rest)?.to_vec();
+        if !rest.is_empty() {
+            return Err(VrfError::MalformedKeypair);
+        }
+        Ok(Self {
+            key_id,
+            epoch_id,
+            vrf_pk,
+            vrf_sk,
+        })
+    }
+}
+
+fn take<'a>(rest: &mut &'a [u8], len: usize) -> Result<&'a [u8], VrfError> {
+    if rest.len() < len {
+        return Err(VrfError::MalformedKeypair);
+    }
+    let (head, tail) = rest.split_at(len);
+    *rest = tail;
+    Ok(head)
+}
+
+fn take_prefixed<'a>(rest: &mut &'a [u8]) -> Result<&'a [u8], VrfError> {
+    let len = u32::from_le_bytes(take(rest, 4)?.try_into().expect("took 4 bytes"));
+    take(rest, len as usize)
+}
+
+/// Opening for a commitment produced by [`VrfEngine::commit`].
+///
+/// `proof` holds the raw Ed25519 signature that the temporary VRF hashes into
+/// `digest`, or the encoded [`ecvrf::EcvrfProof`] for an ECVRF engine.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VrfReveal {
+    pub digest: [u8; 32],
+    pub proof: Vec<u8>,
+}
+
+/// Every intermediate of one record VRF evaluation, returned by
+/// [`VrfEngine::eval_record_vrf_debug`] for cross-checking another
+/// implementation against this one.
+#[cfg(feature = "debug-vrf")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VrfEvalDebug {
+    /// The preimage; see [`vrf_message`].
+    pub message: Vec<u8>,
+    /// The Ed25519 signature over `message`, or the encoded
+    /// [`ecvrf::EcvrfProof`] for an ECVRF engine.
+    pub signature: Vec<u8>,
+    /// SHA-512 of `signature`, or the ECVRF output `beta`.
+    pub sha512: [u8; 64],
+    /// BLAKE3-256 of `sha512`, as returned by [`VrfEngine::eval_record_vrf`].
+    pub digest: [u8; 32],
+}
+
+/// Reusable scratch space for VRF preimages.
+///
+/// [`VrfEngine::eval_record_vrf_into`] clears and refills the buffer on each
+/// call, so evaluating many records with one buffer allocates only once.
+#[derive(Clone, Debug, Default)]
+pub struct MessageBuffer {
+    buf: Vec<u8>,
+}
+
+impl MessageBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+thread_local! {
+    /// Preimage scratch space for evaluations that take no [`MessageBuffer`].
+    ///
+    /// Engines evaluate through `&self` and are shared across threads via
+    /// [`SharedVrfEngine`], so the buffer lives per thread rather than on the
+    /// engine; each thread allocates once instead of once per evaluation. It is
+    /// taken out of the cell while in use, so a [`VrfSigner`] that evaluates
+    /// again on the same thread gets a fresh buffer instead of a borrow panic.
+    static SCRATCH: RefCell<MessageBuffer> = RefCell::new(MessageBuffer::new());
+}
+
+/// The public inputs to a record VRF evaluation, as passed to
+/// [`VrfEngine::eval_record_vrf`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VrfRecordInputs<'a> {
+    pub prev_record_digest: [u8; 32],
+    pub record_digest: [u8; 32],
+    pub charter_digest: &'a str,
+    pub profile_digest: [u8; 32],
+    pub epoch_id: u64,
+}
+
+impl VrfRecordInputs<'_> {
+    /// The VRF preimage; see [`vrf_message`].
+    pub fn message(&self) -> Vec<u8> {
+        vrf_message(
+            self.prev_record_digest,
+            self.record_digest,
+            self.charter_digest,
+            self.profile_digest,
+            self.epoch_id,
+        )
+    }
+}
+
+/// Produces the Ed25519 signatures a temporary-mode [`VrfEngine`] derives
+/// digests from.
+///
+/// Implement this to keep the VRF key outside the process, for example in a
+/// KMS or HSM. `sign` must return the deterministic RFC 8032 signature of
+/// `msg` under `public_key`: the digest is a hash of the signature, so a
+/// randomized signer would produce a different digest on every call and
+/// [`VrfEngine::verify_record_vrf`] would reject its proofs.
+pub trait VrfSigner {
+    /// The deterministic Ed25519 signature of `msg` under [`Self::public_key`].
+    ///
+    /// The same `msg` must always produce the same 64 bytes.
+    fn sign(&self, msg: &[u8]) -> [u8; 64];
+
+    /// The 32-byte Ed25519 public key `sign` signs under, which becomes the
+    /// engine's VRF public key.
+    fn public_key(&self) -> [u8; 32];
+
+    /// The ECVRF proof for `msg`, for signers that hold the secret scalar.
+    ///
+    /// External signers only sign, so the default returns `None` and they back
+    /// [`VrfMode::Temporary`] engines only.
+    #[cfg(feature = "production-vrf")]
+    fn ecvrf_proof(&self, _msg: &[u8]) -> Option<ecvrf::EcvrfProof> {
+        None
+    }
+}
+
+impl VrfSigner for SigningKey {
+    fn sign(&self, msg: &[u8]) -> [u8; 64] {
+        Signer::sign(self, msg).to_bytes()
+    }
+
+    fn public_key(&self) -> [u8; 32] {
+        self.verifying_key().to_bytes()
+    }
+
+    #[cfg(feature = "production-vrf")]
+    fn ecvrf_proof(&self, msg: &[u8]) -> Option<ecvrf::EcvrfProof> {
+        Some(ecvrf::prove(self.as_bytes(), msg))
+    }
+}
+
 /// VRF engine that evaluates digests for experience records.
 ///
 /// This implementation is a temporary stand-in: it signs the preimage with
 /// Ed25519, hashes the signature with SHA-512, and then compresses it with
 /// BLAKE3-256 to produce a 32-byte digest. It should be replaced by a true
 /// ECVRF-ED25519-SHA512-TAI implementation when available.
+///
+/// Signing goes through `S`, a local [`SigningKey`] unless the engine was built
+/// with [`VrfEngine::from_signer`]. Both the signing key and
+/// [`VrfKeypair::vrf_sk`] are zeroized on drop.
 #[derive(Clone)]
-pub struct VrfEngine {
-    signing_key: SigningKey,
+pub struct VrfEngine<S = SigningKey> {
+    signer: S,
+    mode: VrfMode,
+    previous_pk: Option<Vec<u8>>,
     pub current: VrfKeypair,
 }
 
+// `SigningKey` zeroizes itself on drop and `VrfKeypair` scrubs `vrf_sk`.
+impl<S: ZeroizeOnDrop> ZeroizeOnDrop for VrfEngine<S> {}
+
 impl VrfEngine {
     /// Create a deterministic dev/test keypair for the provided epoch.
     pub fn new_dev(epoch_id: u64) -> Self {
+        Self::dev(epoch_id, VrfMode::Temporary)
+    }
+
+    /// Create a deterministic dev/test ECVRF keypair for the provided epoch.
+    ///
+    /// The key is the same as [`VrfEngine::new_dev`]; only the digest derivation
+    /// and the `ECVRF:` key id prefix differ.
+    #[cfg(feature = "production-vrf")]
+    pub fn new_dev_ecvrf(epoch_id: u64) -> Self {
+        Self::dev(epoch_id, VrfMode::Ecvrf)
+    }
+
+    /// Create an engine from a persisted 32-byte secret key.
+    pub fn from_secret_bytes(epoch_id: u64, sk: &[u8]) -> Result<Self, VrfError> {
+        let mut sk: [u8; 32] = sk
+            .try_into()
+            .map_err(|_| VrfError::InvalidSecretKeyLength(sk.len()))?;
+        let signing_key = SigningKey::from_bytes(&sk);
+        sk.zeroize();
+        Ok(Self::from_signing_key(
+            signing_key,
+            epoch_id,
+            VrfMode::Temporary,
+        ))
+    }
+
+    /// Create an engine from persisted key material expected to be for `epoch_id`.
+    ///
+    /// The stored public key must match the secret key. The engine keeps the
+    /// stored `key_id` and takes its mode from the `key_id` prefix.
+    pub fn from_keypair(keypair: VrfKeypair, epoch_id: u64) -> Result<Self, VrfError> {
+        if keypair.epoch_id != epoch_id {
+            return Err(VrfError::EpochMismatch {
+                expected: epoch_id,
+                found: keypair.epoch_id,
+            });
+        }
+        let mut sk: [u8; 32] = keypair
+            .vrf_sk
+            .as_slice()
+            .try_into()
+            .map_err(|_| VrfError::InvalidSecretKeyLength(keypair.vrf_sk.len()))?;
+        let signing_key = SigningKey::from_bytes(&sk);
+        sk.zeroize();
+        if signing_key.verifying_key().as_bytes().as_slice() != keypair.vrf_pk {
+            return Err(VrfError::InvalidPublicKey);
+        }
+        Ok(Self {
+            signer: signing_key,
+            mode: VrfMode::from_key_id(&keypair.key_id),
+            previous_pk: None,
+            current: keypair,
+        })
+    }
+
+    fn dev(epoch_id: u64, mode: VrfMode) -> Self {
         let mut seed_hasher = Hasher::new();
         seed_hasher.update(b"UCF:VRF:DEV");
         seed_hasher.update(&epoch_id.to_le_bytes());
         let seed = seed_hasher.finalize();
 
-        let signing_key = SigningKey::from_bytes(seed.as_bytes());
-        let verifying_key = signing_key.verifying_key();
-        let key_id = format!(
-            "{TEMPORARY_VRF_LABEL}:{}",
-            hex::encode(&verifying_key.to_bytes()[..8])
-        );
+        Self::from_signing_key(SigningKey::from_bytes(seed.as_bytes()), epoch_id, mode)
+    }
+
+    fn from_signing_key(signing_key: SigningKey, epoch_id: u64, mode: VrfMode) -> Self {
+        let vrf_pk = signing_key.verifying_key().to_bytes();
         let current = VrfKeypair {
-            key_id,
+            key_id: key_id(mode, &vrf_pk),
             epoch_id,
-            vrf_pk: verifying_key.to_bytes().to_vec(),
+            vrf_pk: vrf_pk.to_vec(),
             vrf_sk: signing_key.to_bytes().to_vec(),
         };
 
         Self {
-            signing_key,
+            signer: signing_key,
+            mode,
+            previous_pk: None,
             current,
         }
     }
 
+    /// Rotate to a fresh keypair for `next_epoch`.
+    ///
+    /// The new secret is derived deterministically from the current secret and
+    /// `next_epoch`, so replaying the same rotations reproduces the same keys.
+    /// The outgoing public key stays available via
+    /// [`VrfEngine::previous_public_key`].
+    pub fn advance_epoch(&mut self, next_epoch: u64) -> Result<(), VrfError> {
+        let current_epoch = self.current.epoch_id;
+        if next_epoch <= current_epoch {
+            return Err(VrfError::EpochRegression {
+                current: current_epoch,
+                next: next_epoch,
+            });
+        }
+
+        let mut hasher = Hasher::new();
+        hasher.update(VRF_ROTATE_DOMAIN);
+        hasher.update(self.signer.as_bytes());
+        hasher.update(&next_epoch.to_le_bytes());
+        let mut seed: [u8; 32] = hasher.finalize().into();
+        let signing_key = SigningKey::from_bytes(&seed);
+        seed.zeroize();
+
+        let next = Self::from_signing_key(signing_key, next_epoch, self.mode);
+        let previous = std::mem::replace(self, next);
+        self.previous_pk = Some(previous.current.vrf_pk.clone());
+        Ok(())
+    }
+
+    /// The VRF preimage for [`VrfEngine::eval_vrf_in_domain`]: `domain` and then
+    /// each of `components`, every one prefixed with its length as u32 LE.
+    ///
+    /// # Panics
+    ///
+    /// If `domain` is longer than [`MAX_DOMAIN_LEN`].
+    pub fn build_message(domain: &[u8], components: &[&[u8]]) -> Vec<u8> {
+        let mut msg = Vec::new();
+        write_message(&mut msg, domain, components);
+        msg
+    }
+
+    /// Check a claimed VRF `digest` for `inputs` without the secret key.
+    ///
+    /// The digest is derived from the proof, so the public key alone cannot
+    /// confirm it: `proof` is the [`VrfReveal::proof`] published with the digest
+    /// (an Ed25519 signature, or an encoded [`ecvrf::EcvrfProof`] with the
+    /// `production-vrf` feature). Returns `Ok(false)` if the proof does not
+    /// verify or derives a different digest, and an error if `vrf_pk` or
+    /// `proof` is malformed.
+    pub fn verify_record_vrf(
+        vrf_pk: &[u8],
+        inputs: &VrfRecordInputs<'_>,
+        digest: [u8; 32],
+        proof: &[u8],
+    ) -> Result<bool, VrfError> {
+        match verify_proof(vrf_pk, &inputs.message(), proof) {
+            Ok(derived) => Ok(derived == digest),
+            Err(VrfError::InvalidProof) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<S: VrfSigner> VrfEngine<S> {
+    /// Create a temporary-VRF engine that signs through `signer`.
+    ///
+    /// The secret never leaves the signer, so `current.vrf_sk` is empty and
+    /// the engine cannot [`VrfEngine::advance_epoch`]; build a new engine for
+    /// the next epoch's key instead.
+    ///
+    /// The signer is probed first: it signs a fixed `UCF:VRF:SIGNER_PROBE`
+    /// message twice, and both signatures must be identical and pass
+    /// `verify_strict` under its public key. A signer that fails returns
+    /// [`VrfError::SignerMismatch`] rather than an engine whose digests no
+    /// verifier would accept.
+    pub fn from_signer(signer: S, epoch_id: u64) -> Result<Self, VrfError> {
+        let vrf_pk = signer.public_key();
+        let signature = signer.sign(VRF_SIGNER_PROBE_DOMAIN);
+        if signature != signer.sign(VRF_SIGNER_PROBE_DOMAIN) {
+            return Err(VrfError::SignerMismatch);
+        }
+        match verify_proof(&vrf_pk, VRF_SIGNER_PROBE_DOMAIN, &signature) {
+            Ok(_) => {}
+            Err(VrfError::InvalidPublicKey) => return Err(VrfError::InvalidPublicKey),
+            Err(_) => return Err(VrfError::SignerMismatch),
+        }
+        let current = VrfKeypair {
+            key_id: key_id(VrfMode::Temporary, &vrf_pk),
+            epoch_id,
+            vrf_pk: vrf_pk.to_vec(),
+            vrf_sk: Vec::new(),
+        };
+
+        Ok(Self {
+            signer,
+            mode: VrfMode::Temporary,
+            previous_pk: None,
+            current,
+        })
+    }
+
+    /// The public key in use before the last [`VrfEngine::advance_epoch`].
+    pub fn previous_public_key(&self) -> Option<&[u8]> {
+        self.previous_pk.as_deref()
+    }
+
+    pub fn mode(&self) -> VrfMode {
+        self.mode
+    }
+
     pub fn current_epoch(&self) -> u64 {
         self.current.epoch_id
     }
@@ -77,126 +495,641 @@ impl VrfEngine {
         profile_digest: [u8; 32],
         epoch_id: u64,
     ) -> [u8; 32] {
-        let message = self.build_message(
+        self.eval_scratch(|buf| {
+            write_record_message(
+                buf,
+                prev_record_digest,
+                record_digest,
+                charter_digest,
+                profile_digest,
+                epoch_id,
+            )
+        })
+    }
+
+    /// Evaluate the VRF over `domain` and `components`, framed as by
+    /// [`VrfEngine::build_message`].
+    ///
+    /// Each caller must pick a domain tag no other caller uses, so digests for
+    /// different purposes can never coincide. The domain and every component
+    /// are length-prefixed, so neither boundaries nor a domain that extends
+    /// another can make two different calls share a preimage. The preimage
+    /// opens with the domain length rather than a tag, so it never equals an
+    /// [`VrfEngine::eval_record_vrf`] preimage either.
+    ///
+    /// The preimage is built in a per-thread scratch buffer, so repeated calls
+    /// do not allocate.
+    ///
+    /// # Panics
+    ///
+    /// If `domain` is longer than [`MAX_DOMAIN_LEN`].
+    pub fn eval_vrf_in_domain(&self, domain: &[u8], components: &[&[u8]]) -> [u8; 32] {
+        self.eval_scratch(|buf| write_message(buf, domain, components))
+    }
+
+    /// Evaluate the VRF over the preimage `write` builds in the thread's scratch buffer.
+    fn eval_scratch(&self, write: impl FnOnce(&mut Vec<u8>)) -> [u8; 32] {
+        let mut scratch = SCRATCH.take();
+        write(&mut scratch.buf);
+        let digest = self.evaluate(&scratch.buf);
+        SCRATCH.set(scratch);
+        digest
+    }
+
+    /// Like [`VrfEngine::eval_record_vrf`], but builds the preimage in `buf`
+    /// instead of allocating a fresh message per call.
+    pub fn eval_record_vrf_into(
+        &self,
+        buf: &mut MessageBuffer,
+        prev_record_digest: [u8; 32],
+        record_digest: [u8; 32],
+        charter_digest: &str,
+        profile_digest: [u8; 32],
+        epoch_id: u64,
+    ) -> [u8; 32] {
+        write_record_message(
+            &mut buf.buf,
             prev_record_digest,
             record_digest,
             charter_digest,
             profile_digest,
             epoch_id,
         );
-        let signature = self.signing_key.sign(&message);
-        digest_signature(&signature)
+        self.evaluate(&buf.buf)
+    }
+
+    /// Evaluate the VRF digest for each of `inputs`, in order, reusing one
+    /// scratch buffer. Each output equals the corresponding
+    /// [`VrfEngine::eval_record_vrf`] call.
+    pub fn eval_record_vrf_batch(&self, inputs: &[VrfRecordInputs<'_>]) -> Vec<[u8; 32]> {
+        let mut buf = MessageBuffer::new();
+        inputs
+            .iter()
+            .map(|input| {
+                self.eval_record_vrf_into(
+                    &mut buf,
+                    input.prev_record_digest,
+                    input.record_digest,
+                    input.charter_digest,
+                    input.profile_digest,
+                    input.epoch_id,
+                )
+            })
+            .collect()
     }
 
-    fn build_message(
+    /// Evaluate ECVRF for an experience record, returning the digest and its proof.
+    ///
+    /// The digest is [`ecvrf::beta_digest`] of the proof output and equals
+    /// [`VrfEngine::eval_record_vrf`] for engines in [`VrfMode::Ecvrf`].
+    #[cfg(feature = "production-vrf")]
+    pub fn eval_record_vrf_proof(
         &self,
         prev_record_digest: [u8; 32],
         record_digest: [u8; 32],
         charter_digest: &str,
         profile_digest: [u8; 32],
         epoch_id: u64,
-    ) -> Vec<u8> {
-        let mut msg = Vec::with_capacity(
-            VRF_DOMAIN.len()
-                + prev_record_digest.len()
-                + record_digest.len()
-                + charter_digest.len()
-                + profile_digest.len()
-                + std::mem::size_of::<u64>(),
-        );
-        msg.extend_from_slice(VRF_DOMAIN);
-        msg.extend_from_slice(&prev_record_digest);
-        msg.extend_from_slice(&record_digest);
-        msg.extend_from_slice(charter_digest.as_bytes());
-        msg.extend_from_slice(&profile_digest);
-        msg.extend_from_slice(&epoch_id.to_le_bytes());
-        msg
-    }
-}
-
-fn digest_signature(signature: &Signature) -> [u8; 32] {
-    let sig_hash = Sha512::digest(signature.to_bytes());
-    let mut hasher = Hasher::new();
-    hasher.update(&sig_hash);
-    *hasher.finalize().as_bytes()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn sample_inputs() -> ([u8; 32], [u8; 32], &'static str, [u8; 32], u64) {
-        ([0u8; 32], [1u8; 32], "charter-digest", [2u8; 32], 42)
-    }
-
-    #[test]
-    fn vrf_digest_is_deterministic() {
-        let engine = VrfEngine::new_dev(7);
-        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
-            sample_inputs();
-
-        let digest1 = engine.eval_record_vrf(
+    ) -> ([u8; 32], ecvrf::EcvrfProof) {
+        let message = vrf_message(
             prev_record_digest,
             record_digest,
             charter_digest,
             profile_digest,
             epoch_id,
         );
-        let digest2 = engine.eval_record_vrf(
+        self.ecvrf_prove(&message)
+    }
+
+    /// Like [`VrfEngine::eval_record_vrf`], but also returns the preimage, the
+    /// signature, and the SHA-512 intermediate the digest is derived from.
+    ///
+    /// Only for validating a replacement implementation during migration; the
+    /// intermediates are not part of any protocol message.
+    #[cfg(feature = "debug-vrf")]
+    pub fn eval_record_vrf_debug(
+        &self,
+        prev_record_digest: [u8; 32],
+        record_digest: [u8; 32],
+        charter_digest: &str,
+        profile_digest: [u8; 32],
+        epoch_id: u64,
+    ) -> VrfEvalDebug {
+        let message = vrf_message(
             prev_record_digest,
             record_digest,
             charter_digest,
             profile_digest,
             epoch_id,
         );
-
-        assert_eq!(digest1, digest2, "VRF digest should be deterministic");
+        let (signature, sha512): (Vec<u8>, [u8; 64]) = match self.mode {
+            VrfMode::Temporary => {
+                let signature = self.signer.sign(&message);
+                (signature.to_vec(), Sha512::digest(signature).into())
+            }
+            #[cfg(feature = "production-vrf")]
+            VrfMode::Ecvrf => {
+                let (_, proof) = self.ecvrf_prove(&message);
+                let beta = proof.beta().expect("prove returns a valid Gamma");
+                (proof.to_bytes().to_vec(), beta)
+            }
+        };
+        let digest = *blake3::hash(&sha512).as_bytes();
+        VrfEvalDebug {
+            message,
+            signature,
+            sha512,
+            digest,
+        }
     }
 
-    #[test]
-    fn vrf_digest_changes_with_record_digest() {
-        let engine = VrfEngine::new_dev(7);
-        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
-            sample_inputs();
-        let mut tweaked_record_digest = record_digest;
-        tweaked_record_digest[0] ^= 0xFF;
-
-        let digest1 = engine.eval_record_vrf(
+    /// Commit to the VRF digest for an experience record without revealing it.
+    ///
+    /// Returns `blake3(UCF:VRF:COMMIT || digest)` together with the reveal that
+    /// opens it later; see [`verify_commit`].
+    pub fn commit(
+        &self,
+        prev_record_digest: [u8; 32],
+        record_digest: [u8; 32],
+        charter_digest: &str,
+        profile_digest: [u8; 32],
+        epoch_id: u64,
+    ) -> ([u8; 32], VrfReveal) {
+        let message = vrf_message(
             prev_record_digest,
             record_digest,
             charter_digest,
             profile_digest,
             epoch_id,
         );
-        let digest2 = engine.eval_record_vrf(
+        let reveal = self.reveal(&message);
+        (commitment_for(&reveal.digest), reveal)
+    }
+
+    fn evaluate(&self, message: &[u8]) -> [u8; 32] {
+        match self.mode {
+            VrfMode::Temporary => digest_signature(&self.signer.sign(message)),
+            #[cfg(feature = "production-vrf")]
+            VrfMode::Ecvrf => self.ecvrf_prove(message).0,
+        }
+    }
+
+    fn reveal(&self, message: &[u8]) -> VrfReveal {
+        match self.mode {
+            VrfMode::Temporary => {
+                let signature = self.signer.sign(message);
+                VrfReveal {
+                    digest: digest_signature(&signature),
+                    proof: signature.to_vec(),
+                }
+            }
+            #[cfg(feature = "production-vrf")]
+            VrfMode::Ecvrf => {
+                let (digest, proof) = self.ecvrf_prove(message);
+                VrfReveal {
+                    digest,
+                    proof: proof.to_bytes().to_vec(),
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "production-vrf")]
+    fn ecvrf_prove(&self, message: &[u8]) -> ([u8; 32], ecvrf::EcvrfProof) {
+        // Only `SigningKey` engines are ever in `VrfMode::Ecvrf`.
+        let proof = self
+            .signer
+            .ecvrf_proof(message)
+            .expect("ECVRF engines hold a local signing key");
+        let beta = proof.beta().expect("prove returns a valid Gamma");
+        (ecvrf::beta_digest(&beta), proof)
+    }
+}
+
+/// `<label>:<hex of the first 8 public key bytes>`.
+fn key_id(mode: VrfMode, vrf_pk: &[u8; 32]) -> String {
+    format!("{}:{}", mode.label(), hex::encode(&vrf_pk[..8]))
+}
+
+/// The VRF preimage for an experience record commitment.
+///
+/// The layout is part of the API contract: `UCF:VRF:EXPERIENCE_RECORD`, then
+/// `prev_record_digest`, `record_digest`, the UTF-8 `charter_digest`,
+/// `profile_digest`, and `epoch_id` as little-endian `u64`, with no separators.
+pub fn vrf_message(
+    prev_record_digest: [u8; 32],
+    record_digest: [u8; 32],
+    charter_digest: &str,
+    profile_digest: [u8; 32],
+    epoch_id: u64,
+) -> Vec<u8> {
+    let mut msg = Vec::new();
+    write_record_message(
+        &mut msg,
+        prev_record_digest,
+        record_digest,
+        charter_digest,
+        profile_digest,
+        epoch_id,
+    );
+    msg
+}
+
+/// Clear `msg` and write `domain` and `components`, each prefixed with its
+/// length as u32 LE, reserving the exact length.
+fn write_message(msg: &mut Vec<u8>, domain: &[u8], components: &[&[u8]]) {
+    assert!(
+        domain.len() <= MAX_DOMAIN_LEN,
+        "VRF domain tags are at most {MAX_DOMAIN_LEN} bytes"
+    );
+    msg.clear();
+    msg.reserve(
+        std::iter::once(domain)
+            .chain(components.iter().copied())
+            .map(|part| std::mem::size_of::<u32>() + part.len())
+            .sum::<usize>(),
+    );
+    for part in std::iter::once(domain).chain(components.iter().copied()) {
+        let len = u32::try_from(part.len()).expect("VRF components fit in u32 lengths");
+        msg.extend_from_slice(&len.to_le_bytes());
+        msg.extend_from_slice(part);
+    }
+}
+
+/// Clear `msg` and write the experience record VRF preimage into it.
+///
+/// Unlike [`write_message`] the layout has no length prefixes; it is frozen by
+/// [`vrf_message`] and the test vectors.
+fn write_record_message(
+    msg: &mut Vec<u8>,
+    prev_record_digest: [u8; 32],
+    record_digest: [u8; 32],
+    charter_digest: &str,
+    profile_digest: [u8; 32],
+    epoch_id: u64,
+) {
+    let parts: [&[u8]; 6] = [
+        VRF_DOMAIN,
+        &prev_record_digest,
+        &record_digest,
+        charter_digest.as_bytes(),
+        &profile_digest,
+        &epoch_id.to_le_bytes(),
+    ];
+    msg.clear();
+    msg.reserve(parts.iter().map(|part| part.len()).sum::<usize>());
+    for part in parts {
+        msg.extend_from_slice(part);
+    }
+}
+
+/// Cheaply cloneable handle for sharing one [`VrfEngine`] across threads.
+///
+/// A `VrfEngine` with a local key only holds plain key bytes, so it is
+/// `Send + Sync`; wrapping it in an `Arc` lets concurrent request handlers
+/// evaluate against a single copy of the signing key instead of cloning it per
+/// request.
+pub struct SharedVrfEngine<S = SigningKey>(Arc<VrfEngine<S>>);
+
+impl<S> Clone for SharedVrfEngine<S> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<S: VrfSigner> SharedVrfEngine<S> {
+    pub fn new(engine: VrfEngine<S>) -> Self {
+        Self(Arc::new(engine))
+    }
+
+    pub fn engine(&self) -> &VrfEngine<S> {
+        &self.0
+    }
+
+    pub fn current_epoch(&self) -> u64 {
+        self.0.current_epoch()
+    }
+
+    pub fn vrf_public_key(&self) -> &[u8] {
+        self.0.vrf_public_key()
+    }
+
+    /// See [`VrfEngine::eval_record_vrf`].
+    pub fn eval_record_vrf(
+        &self,
+        prev_record_digest: [u8; 32],
+        record_digest: [u8; 32],
+        charter_digest: &str,
+        profile_digest: [u8; 32],
+        epoch_id: u64,
+    ) -> [u8; 32] {
+        self.0.eval_record_vrf(
             prev_record_digest,
-            tweaked_record_digest,
+            record_digest,
             charter_digest,
             profile_digest,
             epoch_id,
-        );
-
-        assert_ne!(
-            digest1, digest2,
-            "VRF digest should change when record digest changes"
-        );
+        )
     }
 
-    #[test]
-    fn temporary_verify_recomputes_digest_from_signature() {
-        let engine = VrfEngine::new_dev(9);
-        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
-            sample_inputs();
+    /// See [`VrfEngine::eval_record_vrf_batch`].
+    pub fn eval_record_vrf_batch(&self, inputs: &[VrfRecordInputs<'_>]) -> Vec<[u8; 32]> {
+        self.0.eval_record_vrf_batch(inputs)
+    }
 
-        let message = engine.build_message(
+    /// See [`VrfEngine::eval_record_vrf_proof`].
+    #[cfg(feature = "production-vrf")]
+    pub fn eval_record_vrf_proof(
+        &self,
+        prev_record_digest: [u8; 32],
+        record_digest: [u8; 32],
+        charter_digest: &str,
+        profile_digest: [u8; 32],
+        epoch_id: u64,
+    ) -> ([u8; 32], ecvrf::EcvrfProof) {
+        self.0.eval_record_vrf_proof(
             prev_record_digest,
             record_digest,
             charter_digest,
             profile_digest,
             epoch_id,
-        );
-        let signature = engine.signing_key.sign(&message);
-        let digest = engine.eval_record_vrf(
+        )
+    }
+
+    /// See [`VrfEngine::commit`].
+    pub fn commit(
+        &self,
+        prev_record_digest: [u8; 32],
+        record_digest: [u8; 32],
+        charter_digest: &str,
+        profile_digest: [u8; 32],
+        epoch_id: u64,
+    ) -> ([u8; 32], VrfReveal) {
+        self.0.commit(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        )
+    }
+}
+
+impl<S: VrfSigner> From<VrfEngine<S>> for SharedVrfEngine<S> {
+    fn from(engine: VrfEngine<S>) -> Self {
+        Self::new(engine)
+    }
+}
+
+/// Check that `reveal` opens `commitment` with a proof for `inputs` under `vrf_pk`.
+///
+/// The proof is checked with [`VrfEngine::verify_record_vrf`] before the
+/// commitment is compared, so a reveal whose proof merely hashes to its own
+/// digest does not open. Returns `Ok(false)` if the proof or the commitment
+/// does not match, and an error if `vrf_pk` or the proof is malformed.
+pub fn verify_commit(
+    vrf_pk: &[u8],
+    inputs: &VrfRecordInputs<'_>,
+    commitment: &[u8; 32],
+    reveal: &VrfReveal,
+) -> Result<bool, VrfError> {
+    if !VrfEngine::verify_record_vrf(vrf_pk, inputs, reveal.digest, &reveal.proof)? {
+        return Ok(false);
+    }
+    Ok(commitment_for(&reveal.digest) == *commitment)
+}
+
+/// VRF public keys by epoch, for verifying digests issued across key rotations.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VrfKeyring {
+    keys: BTreeMap<u64, Vec<u8>>,
+}
+
+impl VrfKeyring {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `pk` for `epoch_id`, returning the key it replaces, if any.
+    pub fn insert(&mut self, epoch_id: u64, pk: impl Into<Vec<u8>>) -> Option<Vec<u8>> {
+        self.keys.insert(epoch_id, pk.into())
+    }
+
+    pub fn public_key_for(&self, epoch_id: u64) -> Option<&[u8]> {
+        self.keys.get(&epoch_id).map(Vec::as_slice)
+    }
+
+    /// [`VrfEngine::verify_record_vrf`] with the key registered for `key_epoch_id`.
+    pub fn verify_for_epoch(
+        &self,
+        key_epoch_id: u64,
+        inputs: &VrfRecordInputs<'_>,
+        digest: [u8; 32],
+        proof: &[u8],
+    ) -> Result<bool, VrfError> {
+        let pk = self
+            .public_key_for(key_epoch_id)
+            .ok_or(VrfError::UnknownEpoch(key_epoch_id))?;
+        VrfEngine::verify_record_vrf(pk, inputs, digest, proof)
+    }
+}
+
+/// Errors returned when loading VRF keys or verifying VRF proofs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VrfError {
+    /// The secret key is not 32 bytes; holds the length found.
+    InvalidSecretKeyLength(usize),
+    /// The public key is not a valid 32-byte Ed25519 key, or does not belong to
+    /// the secret key it was stored with.
+    InvalidPublicKey,
+    /// Key material is for a different epoch than the caller expected.
+    EpochMismatch { expected: u64, found: u64 },
+    /// Encoded key material is truncated, has trailing bytes, or a non-UTF-8 `key_id`.
+    MalformedKeypair,
+    /// [`VrfEngine::advance_epoch`] was asked to move to an epoch that is not
+    /// after the current one.
+    EpochRegression { current: u64, next: u64 },
+    /// A [`VrfKeyring`] has no public key for the epoch.
+    UnknownEpoch(u64),
+    /// The proof is not a 64-byte Ed25519 signature (or 80-byte ECVRF proof).
+    MalformedProof,
+    /// The proof does not verify over the VRF message under the public key.
+    InvalidProof,
+    /// An external [`VrfSigner`]'s signatures do not verify under its public
+    /// key, or differ between calls for the same message.
+    SignerMismatch,
+}
+
+impl fmt::Display for VrfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VrfError::InvalidSecretKeyLength(len) => {
+                write!(f, "VRF secret key must be 32 bytes, found {len}")
+            }
+            VrfError::InvalidPublicKey => write!(f, "invalid VRF public key"),
+            VrfError::EpochMismatch { expected, found } => {
+                write!(f, "VRF key is for epoch {found}, expected {expected}")
+            }
+            VrfError::MalformedKeypair => write!(f, "malformed encoded VRF keypair"),
+            VrfError::EpochRegression { current, next } => {
+                write!(f, "cannot advance VRF epoch from {current} to {next}")
+            }
+            VrfError::UnknownEpoch(epoch_id) => write!(f, "no VRF public key for epoch {epoch_id}"),
+            VrfError::MalformedProof => write!(f, "malformed VRF proof"),
+            VrfError::InvalidProof => write!(f, "VRF proof verification failed"),
+            VrfError::SignerMismatch => {
+                write!(
+                    f,
+                    "VRF signer does not sign deterministically under its public key"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for VrfError {}
+
+/// Verify `proof` against the public inputs and return the derived VRF digest.
+///
+/// Only the VRF public key is needed, so third parties can check a digest
+/// without constructing a [`VrfEngine`]. The message is rebuilt with
+/// [`vrf_message`].
+pub fn verify_detached(
+    pk: &[u8],
+    prev_record_digest: [u8; 32],
+    record_digest: [u8; 32],
+    charter_digest: &str,
+    profile_digest: [u8; 32],
+    epoch_id: u64,
+    proof: &[u8],
+) -> Result<[u8; 32], VrfError> {
+    let message = vrf_message(
+        prev_record_digest,
+        record_digest,
+        charter_digest,
+        profile_digest,
+        epoch_id,
+    );
+    verify_proof(pk, &message, proof)
+}
+
+/// Verify a temporary (64-byte) or ECVRF (80-byte) proof over `message`.
+fn verify_proof(pk: &[u8], message: &[u8], proof: &[u8]) -> Result<[u8; 32], VrfError> {
+    let pk: [u8; 32] = pk.try_into().map_err(|_| VrfError::InvalidPublicKey)?;
+    #[cfg(feature = "production-vrf")]
+    if let Some(proof) = ecvrf::EcvrfProof::from_bytes(proof) {
+        return ecvrf::verify(&pk, message, &proof)
+            .map(|beta| ecvrf::beta_digest(&beta))
+            .ok_or(VrfError::InvalidProof);
+    }
+    let verifying_key = VerifyingKey::from_bytes(&pk).map_err(|_| VrfError::InvalidPublicKey)?;
+    let signature = Signature::from_slice(proof).map_err(|_| VrfError::MalformedProof)?;
+    verifying_key
+        .verify_strict(message, &signature)
+        .map_err(|_| VrfError::InvalidProof)?;
+    Ok(digest_signature(&signature.to_bytes()))
+}
+
+fn commitment_for(digest: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Hasher::new();
+    hasher.update(VRF_COMMIT_DOMAIN);
+    hasher.update(digest);
+    *hasher.finalize().as_bytes()
+}
+
+fn digest_signature(signature: &[u8; 64]) -> [u8; 32] {
+    let sig_hash = Sha512::digest(signature);
+    let mut hasher = Hasher::new();
+    hasher.update(&sig_hash);
+    *hasher.finalize().as_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_inputs() -> ([u8; 32], [u8; 32], &'static str, [u8; 32], u64) {
+        ([0u8; 32], [1u8; 32], "charter-digest", [2u8; 32], 42)
+    }
+
+    #[test]
+    fn domains_match_the_protocol_registry() {
+        use ucf_protocol::Domain;
+
+        assert_eq!(VRF_DOMAIN, Domain::VrfExperienceRecord.as_bytes());
+        assert_eq!(VRF_COMMIT_DOMAIN, Domain::VrfCommit.as_bytes());
+        assert_eq!(VRF_ROTATE_DOMAIN, Domain::VrfRotate.as_bytes());
+        assert_eq!(VRF_SIGNER_PROBE_DOMAIN, Domain::VrfSignerProbe.as_bytes());
+    }
+
+    #[test]
+    fn vrf_digest_is_deterministic() {
+        let engine = VrfEngine::new_dev(7);
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
+            sample_inputs();
+
+        let digest1 = engine.eval_record_vrf(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+        let digest2 = engine.eval_record_vrf(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+
+        assert_eq!(digest1, digest2, "VRF digest should be deterministic");
+    }
+
+    #[test]
+    fn vrf_digest_changes_with_record_digest() {
+        let engine = VrfEngine::new_dev(7);
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
+            sample_inputs();
+        let mut tweaked_record_digest = record_digest;
+        tweaked_record_digest[0] ^= 0xFF;
+
+        let digest1 = engine.eval_record_vrf(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+        let digest2 = engine.eval_record_vrf(
+            prev_record_digest,
+            tweaked_record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+
+        assert_ne!(
+            digest1, digest2,
+            "VRF digest should change when record digest changes"
+        );
+    }
+
+    #[test]
+    fn temporary_verify_recomputes_digest_from_signature() {
+        let engine = VrfEngine::new_dev(9);
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
+            sample_inputs();
+
+        let message = vrf_message(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+        let signature = VrfSigner::sign(&engine.signer, &message);
+        let digest = engine.eval_record_vrf(
             prev_record_digest,
             record_digest,
             charter_digest,
@@ -210,4 +1143,865 @@ mod tests {
             "TEMPORARY_VRF digest should match recomputed hash of signature"
         );
     }
+
+    #[test]
+    fn commitment_opens_only_with_matching_reveal() {
+        let engine = VrfEngine::new_dev(11);
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
+            sample_inputs();
+        let inputs = VrfRecordInputs {
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        };
+        let pk = engine.vrf_public_key();
+
+        let (commitment, reveal) = engine.commit(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+        assert_eq!(
+            verify_commit(pk, &inputs, &commitment, &reveal),
+            Ok(true),
+            "honest reveal should open commitment"
+        );
+        assert_eq!(
+            reveal.digest,
+            engine.eval_record_vrf(
+                prev_record_digest,
+                record_digest,
+                charter_digest,
+                profile_digest,
+                epoch_id,
+            ),
+            "committed digest should equal the plain VRF digest"
+        );
+
+        let mut tweaked_record_digest = record_digest;
+        tweaked_record_digest[0] ^= 0xFF;
+        let tweaked_inputs = VrfRecordInputs {
+            record_digest: tweaked_record_digest,
+            ..inputs
+        };
+        let (_, other_reveal) = engine.commit(
+            prev_record_digest,
+            tweaked_record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+        assert_eq!(
+            verify_commit(pk, &tweaked_inputs, &commitment, &other_reveal),
+            Ok(false),
+            "wrong reveal must not open"
+        );
+        assert_eq!(
+            verify_commit(pk, &tweaked_inputs, &commitment, &reveal),
+            Ok(false),
+            "reveal must be for the given inputs"
+        );
+
+        let mut forged = reveal.clone();
+        forged.proof[0] ^= 0x01;
+        assert_eq!(
+            verify_commit(pk, &inputs, &commitment, &forged),
+            Ok(false),
+            "proof must match revealed digest"
+        );
+
+        // A reveal whose proof hashes to its own digest but is not a signature
+        // over the inputs must not open its commitment.
+        let proof = vec![0x42; 64];
+        let digest = digest_signature(&proof.clone().try_into().unwrap());
+        let self_consistent = VrfReveal { digest, proof };
+        assert_ne!(
+            verify_commit(pk, &inputs, &commitment_for(&digest), &self_consistent),
+            Ok(true)
+        );
+        let other_pk = VrfEngine::new_dev(12).current.vrf_pk.clone();
+        assert_eq!(
+            verify_commit(&other_pk, &inputs, &commitment, &reveal),
+            Ok(false),
+            "reveal must verify under the given key"
+        );
+    }
+
+    #[test]
+    fn shared_engine_evaluates_across_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<VrfEngine>();
+        assert_send_sync::<SharedVrfEngine>();
+
+        let engine = VrfEngine::new_dev(13);
+        let shared = SharedVrfEngine::new(engine.clone());
+        let (prev_record_digest, _, charter_digest, profile_digest, epoch_id) = sample_inputs();
+
+        let handles: Vec<_> = (0u8..4)
+            .map(|i| {
+                let shared = shared.clone();
+                std::thread::spawn(move || {
+                    shared.eval_record_vrf(
+                        prev_record_digest,
+                        [i; 32],
+                        charter_digest,
+                        profile_digest,
+                        epoch_id,
+                    )
+                })
+            })
+            .collect();
+
+        for (i, handle) in (0u8..4).zip(handles) {
+            let digest = handle.join().expect("worker thread should not panic");
+            let expected = engine.eval_record_vrf(
+                prev_record_digest,
+                [i; 32],
+                charter_digest,
+                profile_digest,
+                epoch_id,
+            );
+            assert_eq!(
+                digest, expected,
+                "shared engine should match direct evaluation"
+            );
+        }
+    }
+
+    #[test]
+    fn digests_match_pinned_values() {
+        let engine = VrfEngine::new_dev(7);
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
+            sample_inputs();
+        let record = engine.eval_record_vrf(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+        assert_eq!(
+            hex::encode(record),
+            "b4cb86d0beb80b6359fa13f904ff4e9bbe13c16131745bffab2b2b901aeddf94"
+        );
+        let message = vrf_message(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+        assert_eq!(engine.evaluate(&message), record);
+
+        let components: [&[u8]; 2] = [b"a", b"bc"];
+        let domain = engine.eval_vrf_in_domain(b"UCF:VRF:TEST", &components);
+        assert_eq!(
+            hex::encode(domain),
+            "adcaabcc13888b5a0e8ac00c7c492a964434db2c3b3d0f92bca07d5805786ad5"
+        );
+        assert_eq!(
+            engine.evaluate(&VrfEngine::build_message(b"UCF:VRF:TEST", &components)),
+            domain
+        );
+    }
+
+    /// Every epoch passed to `new_dev` by the workspace's tests and benches.
+    const DEV_EPOCHS: [u64; 19] = [
+        1, 5, 6, 7, 9, 11, 13, 15, 16, 17, 19, 21, 22, 23, 25, 27, 29, 31, 33,
+    ];
+
+    fn hex32(value: &str) -> [u8; 32] {
+        hex::decode(value)
+            .expect("vector field is hex")
+            .try_into()
+            .expect("vector field is 32 bytes")
+    }
+
+    #[test]
+    fn stored_vectors_reproduce() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("testvectors");
+        let mut names: Vec<String> = std::fs::read_dir(&dir)
+            .expect("testvectors directory is readable")
+            .map(|entry| entry.expect("directory entry").path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "inputs"))
+            .map(|path| path.file_stem().unwrap().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+
+        let mut epochs = Vec::new();
+        for name in &names {
+            let inputs = std::fs::read_to_string(dir.join(format!("{name}.inputs")))
+                .expect("inputs file is readable");
+            let fields: BTreeMap<&str, &str> = inputs
+                .lines()
+                .filter_map(|line| line.split_once(" = "))
+                .collect();
+            let field = |key: &str| {
+                *fields
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{name}: missing {key}"))
+            };
+            let expected = std::fs::read_to_string(dir.join(format!("{name}.digest")))
+                .expect("digest file is readable");
+
+            let engine_epoch: u64 = field("engine_epoch").parse().expect("engine_epoch");
+            let engine = VrfEngine::new_dev(engine_epoch);
+            let digest = engine.eval_record_vrf(
+                hex32(field("prev_record_digest")),
+                hex32(field("record_digest")),
+                field("charter_digest"),
+                hex32(field("profile_digest")),
+                field("epoch_id").parse().expect("epoch_id"),
+            );
+            assert_eq!(hex::encode(digest), expected.trim_end(), "{name}");
+            epochs.push(engine_epoch);
+        }
+
+        epochs.sort_unstable();
+        assert_eq!(epochs, DEV_EPOCHS, "one vector per dev epoch");
+    }
+
+    #[test]
+    fn scratch_buffer_matches_allocating_eval() {
+        let engine = VrfEngine::new_dev(11);
+        let (prev_record_digest, _, charter_digest, profile_digest, epoch_id) = sample_inputs();
+        let mut buf = MessageBuffer::new();
+
+        for i in 0u8..8 {
+            // Alternate charter lengths so the buffer both grows and shrinks.
+            let charter = if i % 2 == 0 { charter_digest } else { "c" };
+            let expected = engine.eval_record_vrf(
+                prev_record_digest,
+                [i; 32],
+                charter,
+                profile_digest,
+                epoch_id,
+            );
+            let digest = engine.eval_record_vrf_into(
+                &mut buf,
+                prev_record_digest,
+                [i; 32],
+                charter,
+                profile_digest,
+                epoch_id,
+            );
+            assert_eq!(
+                digest, expected,
+                "scratch buffer should not change the digest"
+            );
+        }
+    }
+
+    #[test]
+    fn detached_proof_verifies_with_public_key_only() {
+        let engine = VrfEngine::new_dev(15);
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
+            sample_inputs();
+        let (_, reveal) = engine.commit(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+
+        let verify = |pk: &[u8], record_digest, proof: &[u8]| {
+            verify_detached(
+                pk,
+                prev_record_digest,
+                record_digest,
+                charter_digest,
+                profile_digest,
+                epoch_id,
+                proof,
+            )
+        };
+        let pk = engine.vrf_public_key();
+        assert_eq!(verify(pk, record_digest, &reveal.proof), Ok(reveal.digest));
+
+        let mut tweaked_record_digest = record_digest;
+        tweaked_record_digest[0] ^= 0xFF;
+        assert_eq!(
+            verify(pk, tweaked_record_digest, &reveal.proof),
+            Err(VrfError::InvalidProof)
+        );
+        assert_eq!(
+            verify(
+                VrfEngine::new_dev(16).vrf_public_key(),
+                record_digest,
+                &reveal.proof
+            ),
+            Err(VrfError::InvalidProof)
+        );
+        assert_eq!(
+            verify(&pk[..31], record_digest, &reveal.proof),
+            Err(VrfError::InvalidPublicKey)
+        );
+        assert_eq!(
+            verify(pk, record_digest, &reveal.proof[..63]),
+            Err(VrfError::MalformedProof)
+        );
+    }
+
+    #[cfg(feature = "production-vrf")]
+    #[test]
+    fn ecvrf_engine_proves_its_digest() {
+        let engine = VrfEngine::new_dev_ecvrf(17);
+        assert_eq!(engine.mode(), VrfMode::Ecvrf);
+        assert!(engine.current.key_id.starts_with("ECVRF:"));
+        assert!(VrfEngine::new_dev(17)
+            .current
+            .key_id
+            .starts_with("TEMPORARY_VRF:"));
+
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
+            sample_inputs();
+        let (digest, proof) = engine.eval_record_vrf_proof(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+        assert_eq!(
+            digest,
+            engine.eval_record_vrf(
+                prev_record_digest,
+                record_digest,
+                charter_digest,
+                profile_digest,
+                epoch_id,
+            )
+        );
+
+        let message = vrf_message(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+        let pk: [u8; 32] = engine.vrf_public_key().try_into().unwrap();
+        let beta = ecvrf::verify(&pk, &message, &proof).expect("engine proof verifies");
+        assert_eq!(ecvrf::beta_digest(&beta), digest);
+
+        let (commitment, reveal) = engine.commit(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+        assert_eq!(reveal.proof, proof.to_bytes());
+        let inputs = VrfRecordInputs {
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        };
+        assert_eq!(
+            verify_commit(engine.vrf_public_key(), &inputs, &commitment, &reveal),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn record_vrf_verifies_from_public_key_and_proof() {
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
+            sample_inputs();
+        let inputs = VrfRecordInputs {
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        };
+
+        let engines = [
+            VrfEngine::new_dev(19),
+            #[cfg(feature = "production-vrf")]
+            VrfEngine::new_dev_ecvrf(19),
+        ];
+        for engine in engines {
+            let (_, reveal) = engine.commit(
+                prev_record_digest,
+                record_digest,
+                charter_digest,
+                profile_digest,
+                epoch_id,
+            );
+            let pk = engine.vrf_public_key();
+            let verify = |inputs: &VrfRecordInputs<'_>, digest, proof: &[u8]| {
+                VrfEngine::verify_record_vrf(pk, inputs, digest, proof)
+            };
+
+            assert_eq!(verify(&inputs, reveal.digest, &reveal.proof), Ok(true));
+            assert_eq!(verify(&inputs, [0u8; 32], &reveal.proof), Ok(false));
+            let other = VrfRecordInputs {
+                epoch_id: epoch_id + 1,
+                ..inputs
+            };
+            assert_eq!(verify(&other, reveal.digest, &reveal.proof), Ok(false));
+            assert_eq!(
+                verify(&inputs, reveal.digest, &reveal.proof[1..]),
+                Err(VrfError::MalformedProof)
+            );
+            assert_eq!(
+                VrfEngine::verify_record_vrf(&pk[..16], &inputs, reveal.digest, &reveal.proof),
+                Err(VrfError::InvalidPublicKey)
+            );
+        }
+    }
+
+    #[test]
+    fn persisted_key_material_loads_with_typed_errors() {
+        let dev = VrfEngine::new_dev(21);
+        let sk = dev.current.vrf_sk.clone();
+
+        let loaded = VrfEngine::from_secret_bytes(21, &sk).expect("32-byte key loads");
+        assert_eq!(loaded.current.key_id, dev.current.key_id);
+        assert_eq!(loaded.vrf_public_key(), dev.vrf_public_key());
+        assert!(matches!(
+            VrfEngine::from_secret_bytes(21, &sk[..31]),
+            Err(VrfError::InvalidSecretKeyLength(31))
+        ));
+
+        let reloaded = VrfEngine::from_keypair(dev.current.clone(), 21).expect("keypair loads");
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
+            sample_inputs();
+        assert_eq!(
+            reloaded.eval_record_vrf(
+                prev_record_digest,
+                record_digest,
+                charter_digest,
+                profile_digest,
+                epoch_id,
+            ),
+            dev.eval_record_vrf(
+                prev_record_digest,
+                record_digest,
+                charter_digest,
+                profile_digest,
+                epoch_id,
+            )
+        );
+        assert!(matches!(
+            VrfEngine::from_keypair(dev.current.clone(), 22),
+            Err(VrfError::EpochMismatch {
+                expected: 22,
+                found: 21
+            })
+        ));
+        let mut mismatched = dev.current.clone();
+        mismatched.vrf_pk = VrfEngine::new_dev(22).current.vrf_pk.clone();
+        assert!(matches!(
+            VrfEngine::from_keypair(mismatched, 21),
+            Err(VrfError::InvalidPublicKey)
+        ));
+    }
+
+    #[test]
+    fn keypair_bytes_round_trip_exactly() {
+        let keypair = VrfEngine::new_dev(23).current.clone();
+        let bytes = keypair.to_bytes();
+        assert_eq!(&bytes[..8], &23u64.to_le_bytes());
+
+        let decoded = VrfKeypair::from_bytes(&bytes).expect("encoded keypair decodes");
+        assert_eq!(decoded.key_id, keypair.key_id);
+        assert_eq!(decoded.epoch_id, keypair.epoch_id);
+        assert_eq!(decoded.vrf_pk, keypair.vrf_pk);
+        assert_eq!(decoded.vrf_sk, keypair.vrf_sk);
+        assert_eq!(decoded.to_bytes(), bytes);
+
+        for len in 0..bytes.len() {
+            assert_eq!(
+                VrfKeypair::from_bytes(&bytes[..len]).err(),
+                Some(VrfError::MalformedKeypair),
+                "truncated to {len} bytes"
+            );
+        }
+        let mut trailing = bytes;
+        trailing.push(0);
+        assert_eq!(
+            VrfKeypair::from_bytes(&trailing).err(),
+            Some(VrfError::MalformedKeypair)
+        );
+    }
+
+    #[test]
+    fn keypair_secret_is_scrubbed() {
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<VrfKeypair>();
+        assert_zeroize_on_drop::<VrfEngine>();
+
+        // `Drop` delegates to `zeroize`; check the buffer while it is still owned.
+        let mut keypair = VrfEngine::new_dev(25).current.clone();
+        let len = keypair.vrf_sk.len();
+        assert!(keypair.vrf_sk.iter().any(|&byte| byte != 0));
+        keypair.zeroize();
+        assert!(keypair.vrf_sk.is_empty());
+        let scrubbed = &keypair.vrf_sk.spare_capacity_mut()[..len];
+        // SAFETY: `zeroize` wrote every byte of the former contents.
+        assert!(scrubbed
+            .iter()
+            .all(|byte| unsafe { byte.assume_init() } == 0));
+    }
+
+    #[test]
+    fn external_signer_matches_local_key() {
+        /// Stands in for a KMS client: it only exposes signing.
+        struct RemoteSigner(SigningKey);
+
+        impl VrfSigner for RemoteSigner {
+            fn sign(&self, msg: &[u8]) -> [u8; 64] {
+                VrfSigner::sign(&self.0, msg)
+            }
+
+            fn public_key(&self) -> [u8; 32] {
+                self.0.public_key()
+            }
+        }
+
+        let local = VrfEngine::new_dev(27);
+        let remote = VrfEngine::from_signer(RemoteSigner(local.signer.clone()), 27)
+            .expect("signer is consistent");
+        assert_eq!(remote.current.key_id, local.current.key_id);
+        assert_eq!(remote.vrf_public_key(), local.vrf_public_key());
+        assert!(remote.current.vrf_sk.is_empty());
+
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
+            sample_inputs();
+        let inputs = VrfRecordInputs {
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        };
+        assert_eq!(
+            remote.eval_record_vrf_batch(&[inputs]),
+            local.eval_record_vrf_batch(&[inputs])
+        );
+
+        let shared = SharedVrfEngine::new(remote);
+        let (commitment, reveal) = shared.commit(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+        assert_eq!(
+            verify_commit(shared.vrf_public_key(), &inputs, &commitment, &reveal),
+            Ok(true)
+        );
+        assert_eq!(
+            VrfEngine::verify_record_vrf(
+                shared.vrf_public_key(),
+                &inputs,
+                reveal.digest,
+                &reveal.proof
+            ),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn from_signer_rejects_inconsistent_signers() {
+        /// Signs with one key but reports another.
+        struct WrongKey(SigningKey, [u8; 32]);
+
+        impl VrfSigner for WrongKey {
+            fn sign(&self, msg: &[u8]) -> [u8; 64] {
+                VrfSigner::sign(&self.0, msg)
+            }
+
+            fn public_key(&self) -> [u8; 32] {
+                self.1
+            }
+        }
+
+        /// Returns a different signature on every call.
+        struct Randomized(SigningKey, std::cell::Cell<u8>);
+
+        impl VrfSigner for Randomized {
+            fn sign(&self, msg: &[u8]) -> [u8; 64] {
+                self.1.set(self.1.get() + 1);
+                VrfSigner::sign(&self.0, &[msg, &[self.1.get()]].concat())
+            }
+
+            fn public_key(&self) -> [u8; 32] {
+                self.0.public_key()
+            }
+        }
+
+        let key = VrfEngine::new_dev(33).signer;
+        let other = VrfEngine::new_dev(34).signer.public_key();
+        assert_eq!(
+            VrfEngine::from_signer(WrongKey(key.clone(), other), 1).err(),
+            Some(VrfError::SignerMismatch)
+        );
+        assert_eq!(
+            VrfEngine::from_signer(Randomized(key, std::cell::Cell::new(0)), 1).err(),
+            Some(VrfError::SignerMismatch)
+        );
+    }
+
+    #[test]
+    fn signer_may_evaluate_on_the_same_thread() {
+        /// Evaluates another engine before signing, as a caching signer might.
+        struct Reentrant(SigningKey, VrfEngine);
+
+        impl VrfSigner for Reentrant {
+            fn sign(&self, msg: &[u8]) -> [u8; 64] {
+                self.1.eval_vrf_in_domain(b"UCF:VRF:TEST", &[msg]);
+                VrfSigner::sign(&self.0, msg)
+            }
+
+            fn public_key(&self) -> [u8; 32] {
+                self.0.public_key()
+            }
+        }
+
+        let local = VrfEngine::new_dev(29);
+        let reentrant =
+            VrfEngine::from_signer(Reentrant(local.signer.clone(), VrfEngine::new_dev(31)), 29)
+                .expect("signer is consistent");
+        let components: [&[u8]; 2] = [b"a", b"bc"];
+        assert_eq!(
+            reentrant.eval_vrf_in_domain(b"UCF:VRF:TEST", &components),
+            local.eval_vrf_in_domain(b"UCF:VRF:TEST", &components)
+        );
+    }
+
+    #[test]
+    fn advance_epoch_rotates_keys_forward_only() {
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
+            sample_inputs();
+        let eval = |engine: &VrfEngine| {
+            engine.eval_record_vrf(
+                prev_record_digest,
+                record_digest,
+                charter_digest,
+                profile_digest,
+                epoch_id,
+            )
+        };
+
+        let mut engine = VrfEngine::new_dev(27);
+        let first_pk = engine.vrf_public_key().to_vec();
+        let first_digest = eval(&engine);
+        assert_eq!(engine.previous_public_key(), None);
+
+        engine.advance_epoch(28).expect("epoch moves forward");
+        assert_eq!(engine.current_epoch(), 28);
+        assert_ne!(engine.vrf_public_key(), first_pk.as_slice());
+        assert_eq!(engine.previous_public_key(), Some(first_pk.as_slice()));
+        assert_ne!(eval(&engine), first_digest);
+
+        let mut replayed = VrfEngine::new_dev(27);
+        replayed.advance_epoch(28).unwrap();
+        assert_eq!(replayed.vrf_public_key(), engine.vrf_public_key());
+
+        for next in [28, 27] {
+            assert_eq!(
+                engine.advance_epoch(next),
+                Err(VrfError::EpochRegression { current: 28, next })
+            );
+        }
+        assert_eq!(engine.current_epoch(), 28);
+    }
+
+    #[test]
+    fn keyring_verifies_with_the_key_for_each_epoch() {
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, _) =
+            sample_inputs();
+        let mut engine = VrfEngine::new_dev(29);
+        let mut keyring = VrfKeyring::new();
+        let mut issued = Vec::new();
+        for epoch_id in [29, 30] {
+            if epoch_id != engine.current_epoch() {
+                engine.advance_epoch(epoch_id).unwrap();
+            }
+            assert_eq!(keyring.insert(epoch_id, engine.vrf_public_key()), None);
+            let (_, reveal) = engine.commit(
+                prev_record_digest,
+                record_digest,
+                charter_digest,
+                profile_digest,
+                epoch_id,
+            );
+            issued.push((epoch_id, reveal));
+        }
+
+        assert_eq!(keyring.public_key_for(30), Some(engine.vrf_public_key()));
+        assert_eq!(keyring.public_key_for(31), None);
+        for (epoch_id, reveal) in &issued {
+            let inputs = VrfRecordInputs {
+                prev_record_digest,
+                record_digest,
+                charter_digest,
+                profile_digest,
+                epoch_id: *epoch_id,
+            };
+            let verify = |key_epoch_id| {
+                keyring.verify_for_epoch(key_epoch_id, &inputs, reveal.digest, &reveal.proof)
+            };
+            assert_eq!(verify(*epoch_id), Ok(true));
+            let other_epoch = if *epoch_id == 29 { 30 } else { 29 };
+            assert_eq!(verify(other_epoch), Ok(false));
+            assert_eq!(verify(31), Err(VrfError::UnknownEpoch(31)));
+        }
+    }
+
+    #[test]
+    fn domain_evaluation_separates_purposes() {
+        let engine = VrfEngine::new_dev(31);
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
+            sample_inputs();
+        let components: [&[u8]; 5] = [
+            &prev_record_digest,
+            &record_digest,
+            charter_digest.as_bytes(),
+            &profile_digest,
+            &epoch_id.to_le_bytes(),
+        ];
+
+        let record_message = vrf_message(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+        assert_ne!(
+            VrfEngine::build_message(b"UCF:VRF:EXPERIENCE_RECORD", &components),
+            record_message
+        );
+        let record = engine.eval_record_vrf(
+            prev_record_digest,
+            record_digest,
+            charter_digest,
+            profile_digest,
+            epoch_id,
+        );
+        assert_ne!(
+            engine.eval_vrf_in_domain(b"UCF:VRF:EXPERIENCE_RECORD", &components),
+            record
+        );
+        assert_ne!(
+            engine.eval_vrf_in_domain(b"UCF:VRF:MILESTONE", &components),
+            record
+        );
+
+        // Splitting the record domain across the domain and a component must
+        // not reproduce the record preimage or another framing.
+        let mut split: Vec<&[u8]> = vec![b"_RECORD"];
+        split.extend_from_slice(&components);
+        assert_ne!(
+            engine.eval_vrf_in_domain(b"UCF:VRF:EXPERIENCE", &split),
+            record
+        );
+        assert_ne!(
+            VrfEngine::build_message(b"UCF:VRF:EXPERIENCE", &split),
+            VrfEngine::build_message(b"UCF:VRF:EXPERIENCE_RECORD", &components)
+        );
+        assert_ne!(
+            VrfEngine::build_message(b"UCF:VRF:A", &[b"bc"]),
+            VrfEngine::build_message(b"UCF:VRF:A", &[b"b", b"c"])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "VRF domain tags are at most 255 bytes")]
+    fn overlong_domains_are_rejected() {
+        VrfEngine::new_dev(31).eval_vrf_in_domain(&[b'x'; MAX_DOMAIN_LEN + 1], &[]);
+    }
+
+    #[test]
+    fn batch_matches_standalone_evaluation() {
+        let engine = VrfEngine::new_dev(33);
+        let (prev_record_digest, _, charter_digest, profile_digest, epoch_id) = sample_inputs();
+        let inputs: Vec<VrfRecordInputs<'_>> = (0u8..6)
+            .map(|i| VrfRecordInputs {
+                prev_record_digest,
+                record_digest: [i; 32],
+                charter_digest: if i % 2 == 0 { charter_digest } else { "c" },
+                profile_digest,
+                epoch_id,
+            })
+            .collect();
+
+        let batch = engine.eval_record_vrf_batch(&inputs);
+        assert_eq!(batch.len(), inputs.len());
+        for (input, digest) in inputs.iter().zip(&batch) {
+            let expected = engine.eval_record_vrf(
+                input.prev_record_digest,
+                input.record_digest,
+                input.charter_digest,
+                input.profile_digest,
+                input.epoch_id,
+            );
+            assert_eq!(*digest, expected);
+        }
+        assert!(engine.eval_record_vrf_batch(&[]).is_empty());
+    }
+
+    #[cfg(feature = "debug-vrf")]
+    #[test]
+    fn debug_eval_exposes_intermediates() {
+        let (prev_record_digest, record_digest, charter_digest, profile_digest, epoch_id) =
+            sample_inputs();
+        let engines = [
+            VrfEngine::new_dev(5),
+            #[cfg(feature = "production-vrf")]
+            VrfEngine::new_dev_ecvrf(5),
+        ];
+        for engine in engines {
+            let debug = engine.eval_record_vrf_debug(
+                prev_record_digest,
+                record_digest,
+                charter_digest,
+                profile_digest,
+                epoch_id,
+            );
+            let inputs = VrfRecordInputs {
+                prev_record_digest,
+                record_digest,
+                charter_digest,
+                profile_digest,
+                epoch_id,
+            };
+            assert_eq!(debug.message, inputs.message());
+            assert_eq!(
+                debug.digest,
+                engine.eval_record_vrf(
+                    prev_record_digest,
+                    record_digest,
+                    charter_digest,
+                    profile_digest,
+                    epoch_id,
+                )
+            );
+            assert_eq!(debug.digest, *blake3::hash(&debug.sha512).as_bytes());
+            assert_eq!(
+                VrfEngine::verify_record_vrf(
+                    engine.vrf_public_key(),
+                    &inputs,
+                    debug.digest,
+                    &debug.signature
+                ),
+                Ok(true)
+            );
+            if engine.mode() == VrfMode::Temporary {
+                let sha512: [u8; 64] = Sha512::digest(&debug.signature).into();
+                assert_eq!(debug.sha512, sha512);
+            }
+        }
+    }
 }
@@ -1,135 +1,69 @@
-use std::fs;
+use std::path::Path;
 
+use ucf_protocol::experience::{ExperienceRecordBuilder, FinalizationHeaderBuilder};
+use ucf_protocol::fixtures::FixtureSet;
 use ucf_protocol::ucf::v1::*;
-use ucf_protocol::{canonical_bytes, digest32};
+use ucf_protocol::Domain;
 
-const DOMAIN: &str = "ucf-core";
+const DOMAIN: Domain = Domain::Core;
 const SCHEMA: &str = "ucf.v1.ExperienceRecord";
-const VERSION: &str = "1";
 
 fn main() {
-    let fixtures = vec![
-        ("experience_rt_perception", experience_rt_perception()),
-        ("experience_rt_action_exec", experience_rt_action_exec()),
-        ("experience_rt_output", experience_rt_output()),
-    ];
+    let mut fixtures = FixtureSet::new();
+    fixtures.push("experience_rt_perception", SCHEMA, DOMAIN, &experience_rt_perception());
+    fixtures.push("experience_rt_action_exec", SCHEMA, DOMAIN, &experience_rt_action_exec());
+    fixtures.push("experience_rt_output", SCHEMA, DOMAIN, &experience_rt_output());
+    fixtures.write_all(Path::new("testvectors")).expect("write fixtures");
+}
 
-    for (name, record) in fixtures {
-        let bytes = canonical_bytes(&record);
-        let digest = digest32(DOMAIN, SCHEMA, VERSION, &bytes);
-        fs::write(format!("testvectors/{name}.hex"), hex::encode(&bytes)).expect("write hex");
-        fs::write(format!("testvectors/{name}.digest"), hex::encode(digest)).expect("write digest");
-    }
+/// Header fields shared by every record in the fixture chain.
+fn header(
+    experience_id: u64,
+    timestamp_ms: u64,
+    prev: u8,
+    digest: u8,
+    receipt: &str,
+) -> FinalizationHeaderBuilder {
+    FinalizationHeaderBuilder::new(experience_id, timestamp_ms)
+        .prev_record_digest([prev; 32])
+        .record_digest([digest; 32])
+        .vrf_digest_ref("vrf://digest/seed", "vrf")
+        .proof_receipt_ref(receipt, "proof")
+        .charter_version_digest("charter:v3")
+        .policy_version_digest("policy:v5")
+        .key_epoch_id(17)
 }
 
 fn experience_rt_perception() -> ExperienceRecord {
-    ExperienceRecord {
-        record_type: RecordType::RtPerception as i32,
-        core_frame_ref: Some(Ref {
-            uri: "core://perception/001".to_string(),
-            label: "core-frame".to_string(),
-        }),
-        metabolic_frame_ref: Some(Ref {
-            uri: "metabolic://perception/001".to_string(),
-            label: "metabolic-frame".to_string(),
-        }),
-        governance_frame_ref: None,
-        finalization_header: Some(FinalizationHeader {
-            experience_id: 1_001,
-            timestamp_ms: 1_700_010_000,
-            prev_record_digest: Some(Digest32 { value: vec![0xAA; 32] }),
-            record_digest: Some(Digest32 { value: vec![0xBB; 32] }),
-            vrf_digest_ref: Some(Ref {
-                uri: "vrf://digest/seed".to_string(),
-                label: "vrf".to_string(),
-            }),
-            proof_receipt_ref: Some(Ref {
-                uri: "proof://receipt/a".to_string(),
-                label: "proof".to_string(),
-            }),
-            charter_version_digest: "charter:v3".to_string(),
-            policy_version_digest: "policy:v5".to_string(),
-            key_epoch_id: 17,
-        }),
-        related_refs: vec![],
-    }
+    ExperienceRecordBuilder::new(RecordType::RtPerception)
+        .core_frame("core://perception/001", "core-frame")
+        .metabolic_frame("metabolic://perception/001", "metabolic-frame")
+        .finalization_header(header(1_001, 1_700_010_000, 0xAA, 0xBB, "proof://receipt/a"))
+        .build()
+        .expect("valid perception record")
 }
 
 fn experience_rt_action_exec() -> ExperienceRecord {
-    ExperienceRecord {
-        record_type: RecordType::RtActionExec as i32,
-        core_frame_ref: Some(Ref {
-            uri: "core://action/002".to_string(),
-            label: "core-frame".to_string(),
-        }),
-        metabolic_frame_ref: Some(Ref {
-            uri: "metabolic://action/002".to_string(),
-            label: "metabolic-frame".to_string(),
-        }),
-        governance_frame_ref: Some(Ref {
-            uri: "governance://action/002".to_string(),
-            label: "governance-frame".to_string(),
-        }),
-        finalization_header: Some(FinalizationHeader {
-            experience_id: 1_002,
-            timestamp_ms: 1_700_010_250,
-            prev_record_digest: Some(Digest32 { value: vec![0xBB; 32] }),
-            record_digest: Some(Digest32 { value: vec![0xCC; 32] }),
-            vrf_digest_ref: Some(Ref {
-                uri: "vrf://digest/seed".to_string(),
-                label: "vrf".to_string(),
-            }),
-            proof_receipt_ref: Some(Ref {
-                uri: "proof://receipt/b".to_string(),
-                label: "proof".to_string(),
-            }),
-            charter_version_digest: "charter:v3".to_string(),
-            policy_version_digest: "policy:v5".to_string(),
-            key_epoch_id: 17,
-        }),
-        related_refs: vec![
-            Ref { uri: "policy://query/001".to_string(), label: "policy_query".to_string() },
-            Ref { uri: "policy://decision/001".to_string(), label: "policy_decision".to_string() },
-            Ref { uri: "policy://ruleset/alpha".to_string(), label: "ruleset".to_string() },
-        ],
-    }
+    ExperienceRecordBuilder::new(RecordType::RtActionExec)
+        .core_frame("core://action/002", "core-frame")
+        .metabolic_frame("metabolic://action/002", "metabolic-frame")
+        .governance_frame("governance://action/002", "governance-frame")
+        .related_ref("policy://query/001", "policy_query")
+        .related_ref("policy://decision/001", "policy_decision")
+        .related_ref("policy://ruleset/alpha", "ruleset")
+        .finalization_header(header(1_002, 1_700_010_250, 0xBB, 0xCC, "proof://receipt/b"))
+        .build()
+        .expect("valid action exec record")
 }
 
 fn experience_rt_output() -> ExperienceRecord {
-    ExperienceRecord {
-        record_type: RecordType::RtOutput as i32,
-        core_frame_ref: Some(Ref {
-            uri: "core://output/003".to_string(),
-            label: "core-frame".to_string(),
-        }),
-        metabolic_frame_ref: Some(Ref {
-            uri: "metabolic://output/003".to_string(),
-            label: "metabolic-frame".to_string(),
-        }),
-        governance_frame_ref: Some(Ref {
-            uri: "governance://output/003".to_string(),
-            label: "governance-frame".to_string(),
-        }),
-        finalization_header: Some(FinalizationHeader {
-            experience_id: 1_003,
-            timestamp_ms: 1_700_010_500,
-            prev_record_digest: Some(Digest32 { value: vec![0xCC; 32] }),
-            record_digest: Some(Digest32 { value: vec![0xDD; 32] }),
-            vrf_digest_ref: Some(Ref {
-                uri: "vrf://digest/seed".to_string(),
-                label: "vrf".to_string(),
-            }),
-            proof_receipt_ref: Some(Ref {
-                uri: "proof://receipt/c".to_string(),
-                label: "proof".to_string(),
-            }),
-            charter_version_digest: "charter:v3".to_string(),
-            policy_version_digest: "policy:v5".to_string(),
-            key_epoch_id: 17,
-        }),
-        related_refs: vec![
-            Ref { uri: "artifact://output/777".to_string(), label: "output_artifact".to_string() },
-            Ref { uri: "dlp://scan/final".to_string(), label: "dlp-scan".to_string() },
-        ],
-    }
+    ExperienceRecordBuilder::new(RecordType::RtOutput)
+        .core_frame("core://output/003", "core-frame")
+        .metabolic_frame("metabolic://output/003", "metabolic-frame")
+        .governance_frame("governance://output/003", "governance-frame")
+        .related_ref("artifact://output/777", "output_artifact")
+        .related_ref("dlp://scan/final", "dlp-scan")
+        .finalization_header(header(1_003, 1_700_010_500, 0xCC, 0xDD, "proof://receipt/c"))
+        .build()
+        .expect("valid output record")
 }
@@ -1,20 +1,8 @@
-use ucf_protocol::ucf::v1::*;
-use ucf_protocol::{canonical_bytes, digest32};
-
-fn emit_fixture<M: prost::Message>(name: &str, schema: &str, message: M) {
-    let bytes = canonical_bytes(&message);
-    let digest = digest32("ucf-core", schema, "1", &bytes);
-    let hex_bytes = hex::encode(&bytes);
-    let hex_digest = hex::encode(digest);
-
-    println!("{} hex: {}", name, hex_bytes);
-    println!("{} digest: {}", name, hex_digest);
+use std::path::Path;
 
-    std::fs::write(format!("testvectors/{name}.hex"), format!("{}\n", hex_bytes))
-        .expect("write hex fixture");
-    std::fs::write(format!("testvectors/{name}.digest"), format!("{}\n", hex_digest))
-        .expect("write digest fixture");
-}
+use ucf_protocol::codec::hex_fixture_body;
+use ucf_protocol::fixtures::FixtureSet;
+use ucf_protocol::ucf::v1::*;
 
 fn main() {
     let mut aggregate_reason_codes =
@@ -132,6 +120,18 @@ fn main() {
         prev_control_frame_digest: Some(Digest32 { value: vec![0x44; 32] }),
     };
 
-    emit_fixture("signal_frame_short_window", "ucf.v1.SignalFrame", signal_frame);
-    emit_fixture("control_frame_m1_overlays_on", "ucf.v1.ControlFrame", control_frame);
+    let mut fixtures = FixtureSet::new();
+    fixtures.push("signal_frame_short_window", "ucf.v1.SignalFrame", "ucf-core", &signal_frame);
+    fixtures.push(
+        "control_frame_m1_overlays_on",
+        "ucf.v1.ControlFrame",
+        "ucf-core",
+        &control_frame,
+    );
+
+    for entry in fixtures.entries() {
+        print!("{} hex: {}", entry.name, hex_fixture_body(&entry.bytes));
+        print!("{} digest: {}", entry.name, hex_fixture_body(&entry.digest()));
+    }
+    fixtures.write_all(Path::new("testvectors")).expect("write fixtures");
 }
@@ -1,8 +1,8 @@
-use std::fs;
 use std::path::Path;
 
+use ucf_protocol::fixtures::{FixtureFormat, FixtureSet};
 use ucf_protocol::ucf::v1::*;
-use ucf_protocol::{canonical_bytes, digest32};
+use ucf_protocol::Domain;
 
 fn main() -> anyhow::Result<()> {
     let message = MicrocircuitConfigEvidence {
@@ -16,17 +16,15 @@ fn main() -> anyhow::Result<()> {
         attestation_key_id: None,
     };
 
-    fs::create_dir_all("testvectors")?;
-
-    let bytes = canonical_bytes(&message);
-    let bin_path = Path::new("testvectors").join("mc_cfg_hpa.bin");
-    fs::write(&bin_path, &bytes)?;
-
-    let digest = digest32("UCF:HASH:MC_CONFIG", "ucf.v1.MicrocircuitConfigEvidence", "1", &bytes);
-    let digest_path = Path::new("testvectors").join("mc_cfg_hpa.digest");
-    let mut digest_body = hex::encode(digest);
-    digest_body.push('\n');
-    fs::write(&digest_path, digest_body)?;
+    let mut fixtures = FixtureSet::new();
+    fixtures.push_with_format(
+        "mc_cfg_hpa",
+        "ucf.v1.MicrocircuitConfigEvidence",
+        Domain::McConfig,
+        FixtureFormat::Bin,
+        &message,
+    );
+    fixtures.write_all(Path::new("testvectors"))?;
 
     Ok(())
 }
@@ -1,26 +1,24 @@
-use std::fs;
+use std::path::Path;
 
-use hex::encode as hex_encode;
+use ucf_protocol::fixtures::FixtureSet;
 use ucf_protocol::ucf::v1::*;
-use ucf_protocol::{canonical_bytes, digest32};
+use ucf_protocol::{DigestAlgo, Domain};
 
-const DOMAIN: &str = "ucf-core";
-const VERSION: &str = "1";
-
-fn write_fixture<M: prost::Message>(name: &str, schema: &str, message: &M) -> std::io::Result<()> {
-    let bytes = canonical_bytes(message);
-    let digest = digest32(DOMAIN, schema, VERSION, &bytes);
-
-    fs::write(format!("testvectors/{name}.hex"), format!("{}\n", hex_encode(bytes)))?;
-    fs::write(format!("testvectors/{name}.digest"), format!("{}\n", hex_encode(digest)))?;
-
-    Ok(())
-}
+const DOMAIN: Domain = Domain::Core;
 
 fn main() -> std::io::Result<()> {
     let reason_codes =
         ReasonCodes { codes: vec!["deterministic".to_string(), "coverage".to_string()] };
-    write_fixture("reason_codes_basic", "ucf.v1.ReasonCodes", &reason_codes)?;
+    let mut fixtures = FixtureSet::new();
+    // `reason_codes_basic` is the BLAKE3 digest of the same message.
+    fixtures.push("reason_codes_basic", "ucf.v1.ReasonCodes", DOMAIN, &reason_codes);
+    fixtures.push_with_algo(
+        "reason_codes_sha256",
+        "ucf.v1.ReasonCodes",
+        DOMAIN,
+        DigestAlgo::Sha256,
+        &reason_codes,
+    );
 
     let envelope = UcfEnvelope {
         epoch_id: "epoch-1".to_string(),
@@ -35,7 +33,7 @@ fn main() -> std::io::Result<()> {
         payload: vec![0xDE, 0xAD, 0xBE, 0xEF],
     };
 
-    write_fixture("ucf_envelope_policy_decision", "ucf.v1.UcfEnvelope", &envelope)?;
+    fixtures.push("ucf_envelope_policy_decision", "ucf.v1.UcfEnvelope", DOMAIN, &envelope);
 
-    Ok(())
+    fixtures.write_all(Path::new("testvectors"))
 }
@@ -1,50 +1,23 @@
 use std::fs;
 use std::path::Path;
 
-use prost::Message;
+use rayon::prelude::*;
+use ucf_protocol::fixtures::FixtureSet;
+use ucf_protocol::sep::compute_sep_event_digest;
 use ucf_protocol::ucf::v1::canonical_intent::Params as CanonicalIntentParams;
 use ucf_protocol::ucf::v1::replay_plan::StopConditions;
 use ucf_protocol::ucf::v1::*;
-use ucf_protocol::{canonical_bytes, digest32};
-
-fn sorted_strings(items: &[&str]) -> Vec<String> {
-    let mut values: Vec<String> = items.iter().map(|item| item.to_string()).collect();
-    values.sort();
-    values
-}
-
-fn write_fixture(name: &str, schema: &str, bytes: &[u8], domain: &str) -> anyhow::Result<()> {
-    let digest = digest32(domain, schema, "1", bytes);
-    let hex_path = Path::new("testvectors").join(format!("{name}.hex"));
-    let digest_path = Path::new("testvectors").join(format!("{name}.digest"));
-    let mut hex_body = hex::encode(bytes);
-    hex_body.push('\n');
-    let mut digest_body = hex::encode(digest);
-    digest_body.push('\n');
-    fs::write(&hex_path, hex_body)?;
-    fs::write(&digest_path, digest_body)?;
-    Ok(())
-}
-
-fn emit_fixture<M: Message>(
-    name: &str,
-    schema: &str,
-    message: &M,
-    domain: &str,
-) -> anyhow::Result<()> {
-    let bytes = canonical_bytes(message);
-    write_fixture(name, schema, &bytes, domain)
-}
+use ucf_protocol::{sorted_codes, Canonicalize, Domain};
 
 fn main() -> anyhow::Result<()> {
-    fs::create_dir_all("testvectors")?;
-    let domain = "ucf-core";
-    let microcircuit_domain = "UCF:HASH:MC_CONFIG";
-    let asset_morph_domain = "UCF:ASSET:MORPH";
-    let asset_channel_params_domain = "UCF:ASSET:CHANNEL_PARAMS";
-    let asset_syn_params_domain = "UCF:ASSET:SYN_PARAMS";
-    let asset_connectivity_domain = "UCF:ASSET:CONNECTIVITY";
-    let asset_manifest_domain = "UCF:ASSET:MANIFEST";
+    let mut fixtures = FixtureSet::new();
+    let domain = Domain::Core;
+    let microcircuit_domain = Domain::McConfig;
+    let asset_morph_domain = Domain::AssetMorphology;
+    let asset_channel_params_domain = Domain::AssetChannelParams;
+    let asset_syn_params_domain = Domain::AssetSynapseParams;
+    let asset_connectivity_domain = Domain::AssetConnectivity;
+    let asset_manifest_domain = Domain::AssetManifest;
 
     let canonical_intent = CanonicalIntent {
         intent_id: "intent-123".to_string(),
@@ -52,21 +25,21 @@ fn main() -> anyhow::Result<()> {
         risk_level: RiskLevel::Low as i32,
         data_class: DataClass::Public as i32,
         subject: Some(Ref { uri: "did:example:subject".to_string(), label: "primary".to_string() }),
-        reason_codes: Some(ReasonCodes { codes: sorted_strings(&["baseline", "query"]) }),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["baseline", "query"]) }),
         params: Some(CanonicalIntentParams::Query(QueryParams {
             query: "select * from controls".to_string(),
-            selectors: sorted_strings(&["foo", "bar"]),
+            selectors: sorted_codes(&["foo", "bar"]),
         })),
     };
 
     let policy_decision = PolicyDecision {
         decision: DecisionForm::RequireApproval as i32,
         reason_codes: Some(ReasonCodes {
-            codes: sorted_strings(&["missing-proof", "scope-limited"]),
+            codes: sorted_codes(&["missing-proof", "scope-limited"]),
         }),
         constraints: Some(ConstraintsDelta {
-            constraints_added: sorted_strings(&["mfa-required", "geo-fence"]),
-            constraints_removed: sorted_strings(&["legacy-exception"]),
+            constraints_added: sorted_codes(&["mfa-required", "geo-fence"]),
+            constraints_removed: sorted_codes(&["legacy-exception"]),
         }),
     };
 
@@ -276,7 +249,7 @@ fn main() -> anyhow::Result<()> {
             require_simulation_count: 1,
             top_reason_codes: Some(TopReasonCodes {
                 reason_codes: Some(ReasonCodes {
-                    codes: sorted_strings(&["deny", "require-approval"]),
+                    codes: sorted_codes(&["deny", "require-approval"]),
                 }),
             }),
         }),
@@ -286,7 +259,7 @@ fn main() -> anyhow::Result<()> {
             classify_upgrade_count: 1,
             top_reason_codes: Some(TopReasonCodes {
                 reason_codes: Some(ReasonCodes {
-                    codes: sorted_strings(&["dlp-block", "dlp-redact"]),
+                    codes: sorted_codes(&["dlp-block", "dlp-redact"]),
                 }),
             }),
         }),
@@ -296,7 +269,7 @@ fn main() -> anyhow::Result<()> {
             tool_unavailable_count: 2,
             top_reason_codes: Some(TopReasonCodes {
                 reason_codes: Some(ReasonCodes {
-                    codes: sorted_strings(&["executor-timeout", "tool-unavailable"]),
+                    codes: sorted_codes(&["executor-timeout", "tool-unavailable"]),
                 }),
             }),
         }),
@@ -306,7 +279,7 @@ fn main() -> anyhow::Result<()> {
             concurrency_limit_hits: 0,
             top_reason_codes: Some(TopReasonCodes {
                 reason_codes: Some(ReasonCodes {
-                    codes: sorted_strings(&["chain-limit", "near-exhaustion"]),
+                    codes: sorted_codes(&["chain-limit", "near-exhaustion"]),
                 }),
             }),
         }),
@@ -320,12 +293,12 @@ fn main() -> anyhow::Result<()> {
             receipt_invalid_count: 1,
             top_reason_codes: Some(TopReasonCodes {
                 reason_codes: Some(ReasonCodes {
-                    codes: sorted_strings(&["missing", "signature-invalid"]),
+                    codes: sorted_codes(&["missing", "signature-invalid"]),
                 }),
             }),
         }),
         reason_codes: Some(ReasonCodes {
-            codes: sorted_strings(&["budget-tight", "policy-deny", "receipt-missing"]),
+            codes: sorted_codes(&["budget-tight", "policy-deny", "receipt-missing"]),
         }),
     };
 
@@ -335,7 +308,7 @@ fn main() -> anyhow::Result<()> {
         epoch_id: 42,
         timestamp_ms: 1_700_000_750,
         active_profile: ProfileState::M1 as i32,
-        profile_reason_codes: Some(ReasonCodes { codes: sorted_strings(&["ml-ops", "safety"]) }),
+        profile_reason_codes: Some(ReasonCodes { codes: sorted_codes(&["ml-ops", "safety"]) }),
         overlays: Some(OverlaySet {
             ovl_simulate_first: true,
             ovl_export_lock: true,
@@ -490,8 +463,8 @@ fn main() -> anyhow::Result<()> {
             uri: "proof://micro/receipt".to_string(),
             label: "proof".to_string(),
         }),
-        theme_tags: sorted_strings(&["alignment", "staging"]),
-        reason_codes: Some(ReasonCodes { codes: sorted_strings(&["checkpoint", "sealed"]) }),
+        theme_tags: sorted_codes(&["alignment", "staging"]),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["checkpoint", "sealed"]) }),
     };
 
     let meso_milestone = MesoMilestone {
@@ -506,7 +479,7 @@ fn main() -> anyhow::Result<()> {
             refs.sort_by(|a, b| a.uri.cmp(&b.uri));
             refs
         },
-        theme_tags: sorted_strings(&["consolidation", "stability"]),
+        theme_tags: sorted_codes(&["consolidation", "stability"]),
         hormone_profile: Some(HormoneProfileSummary {
             arousal: LevelClass::Med as i32,
             threat: LevelClass::Low as i32,
@@ -572,7 +545,7 @@ fn main() -> anyhow::Result<()> {
         replay_id: "replay-stability-check".to_string(),
         replay_digest: Some(Digest32 { value: vec![0x44; 32] }),
         trigger_reason_codes: Some(ReasonCodes {
-            codes: sorted_strings(&["consistency-low", "operator-trigger"]),
+            codes: sorted_codes(&["consistency-low", "operator-trigger"]),
         }),
         target_refs: {
             let mut refs = vec![
@@ -600,7 +573,7 @@ fn main() -> anyhow::Result<()> {
     let replay_plan_asset_manifest = ReplayPlan {
         replay_id: "replay-asset-manifest".to_string(),
         replay_digest: Some(Digest32 { value: vec![0x45; 32] }),
-        trigger_reason_codes: Some(ReasonCodes { codes: sorted_strings(&["asset-refresh"]) }),
+        trigger_reason_codes: Some(ReasonCodes { codes: sorted_codes(&["asset-refresh"]) }),
         target_refs: {
             let mut refs = vec![Ref {
                 uri: "ucf://macro/asset-refresh".to_string(),
@@ -619,7 +592,7 @@ fn main() -> anyhow::Result<()> {
         vrf_digest_ref: None,
         proof_receipt_ref: None,
         asset_manifest_ref: Some(Ref {
-            uri: "asset_manifest".to_string(),
+            uri: "ucf://assets/manifest".to_string(),
             label: "manifest-digest".to_string(),
         }),
     };
@@ -706,7 +679,7 @@ fn main() -> anyhow::Result<()> {
         consolidation_eligibility: ConsolidationEligibility::Allow as i32,
         replay_trigger_hint: true,
         trigger_reason_codes: Some(ReasonCodes {
-            codes: sorted_strings(&["drift-detected", "replay-recommended"]),
+            codes: sorted_codes(&["drift-detected", "replay-recommended"]),
         }),
         proof_receipt_ref: Some(Ref {
             uri: "proof://consistency/receipt".to_string(),
@@ -783,14 +756,11 @@ fn main() -> anyhow::Result<()> {
         known_failure_modes: vec!["timeout".to_string(), "unreachable".to_string()],
     };
 
-    let mut tool_actions = vec![tool_action];
-    tool_actions.sort_by(|a, b| a.action_id.cmp(&b.action_id));
-
-    let registry_container = ToolRegistryContainer {
+    let mut registry_container = ToolRegistryContainer {
         registry_id: "registry-alpha".to_string(),
         registry_version: "2024-01".to_string(),
         registry_digest: Some(Digest32 { value: vec![0xCC; 32] }),
-        tool_actions,
+        tool_actions: vec![tool_action],
         created_at_ms: 1_700_003_000,
         proof_receipt_ref: Some(ProofReceipt {
             status: ReceiptStatus::Accepted as i32,
@@ -808,6 +778,7 @@ fn main() -> anyhow::Result<()> {
             signature: vec![0x21, 0x22, 0x23],
         }),
     };
+    registry_container.canonicalize();
 
     let onboarding_event = ToolOnboardingEvent {
         event_id: "onboard-evt-01".to_string(),
@@ -816,7 +787,7 @@ fn main() -> anyhow::Result<()> {
         action_id: "read-latest".to_string(),
         stage: OnboardingStage::To6Suspended as i32,
         stage_reason_codes: Some(ReasonCodes {
-            codes: sorted_strings(&["missing-attestation", "risk-review"]),
+            codes: sorted_codes(&["missing-attestation", "risk-review"]),
         }),
         required_artifact_digests: vec![Digest32 { value: vec![0x05; 32] }],
         test_evidence_refs: vec![Ref {
@@ -883,7 +854,7 @@ fn main() -> anyhow::Result<()> {
         risk_level: RiskLevel::Medium as i32,
         requested_data_class: DataClass::Restricted as i32,
         constraints_proposal: Some(ConstraintsDelta {
-            constraints_added: sorted_strings(&["approval-required", "scope-narrowing"]),
+            constraints_added: sorted_codes(&["approval-required", "scope-narrowing"]),
             constraints_removed: vec!["legacy-exception".to_string()],
         }),
         alternatives,
@@ -901,7 +872,7 @@ fn main() -> anyhow::Result<()> {
             constraints_added: vec!["cooldown-required".to_string()],
             constraints_removed: vec![],
         }),
-        reason_codes: Some(ReasonCodes { codes: sorted_strings(&["two-person", "risk-review"]) }),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["two-person", "risk-review"]) }),
         signatures: vec![Signature {
             algorithm: "ed25519".to_string(),
             signer: vec![0xAA],
@@ -920,7 +891,8 @@ fn main() -> anyhow::Result<()> {
         }),
     };
 
-    let sep_event_1 = SepEvent {
+    // Each event carries its real content digest, and the next event chains to it.
+    let sep_event_1 = with_event_digest(SepEvent {
         event_id: "evt-1".to_string(),
         session_id: "session-9000".to_string(),
         event_type: SepEventType::EvIntent as i32,
@@ -931,16 +903,16 @@ fn main() -> anyhow::Result<()> {
         reason_codes: Some(ReasonCodes { codes: vec!["init".to_string()] }),
         timestamp_ms: 1_700_002_000,
         prev_event_digest: Some(Digest32 { value: vec![0x00; 32] }),
-        event_digest: Some(Digest32 { value: vec![0x10; 32] }),
+        event_digest: None,
         attestation_sig: Some(Signature {
             algorithm: "ed25519".to_string(),
             signer: vec![0x01],
             signature: vec![0x02],
         }),
         epoch_id: 100,
-    };
+    });
 
-    let sep_event_2 = SepEvent {
+    let sep_event_2 = with_event_digest(SepEvent {
         event_id: "evt-2".to_string(),
         session_id: "session-9000".to_string(),
         event_type: SepEventType::EvDecision as i32,
@@ -950,38 +922,38 @@ fn main() -> anyhow::Result<()> {
         }),
         reason_codes: Some(ReasonCodes { codes: vec!["policy".to_string()] }),
         timestamp_ms: 1_700_002_500,
-        prev_event_digest: Some(Digest32 { value: vec![0x10; 32] }),
-        event_digest: Some(Digest32 { value: vec![0x20; 32] }),
+        prev_event_digest: sep_event_1.event_digest.clone(),
+        event_digest: None,
         attestation_sig: Some(Signature {
             algorithm: "ed25519".to_string(),
             signer: vec![0x03],
             signature: vec![0x04],
         }),
         epoch_id: 100,
-    };
+    });
 
-    let sep_event_3 = SepEvent {
+    let sep_event_3 = with_event_digest(SepEvent {
         event_id: "evt-3".to_string(),
         session_id: "session-9000".to_string(),
         event_type: SepEventType::EvOutcome as i32,
         object_ref: Some(Ref { uri: "outcome://result".to_string(), label: "outcome".to_string() }),
         reason_codes: Some(ReasonCodes { codes: vec!["success".to_string()] }),
         timestamp_ms: 1_700_003_000,
-        prev_event_digest: Some(Digest32 { value: vec![0x20; 32] }),
-        event_digest: Some(Digest32 { value: vec![0x30; 32] }),
+        prev_event_digest: sep_event_2.event_digest.clone(),
+        event_digest: None,
         attestation_sig: Some(Signature {
             algorithm: "ed25519".to_string(),
             signer: vec![0x05],
             signature: vec![0x06],
         }),
         epoch_id: 101,
-    };
+    });
 
     let session_seal = SessionSeal {
         seal_id: "seal-9000".to_string(),
         seal_digest: Some(Digest32 { value: vec![0xAB; 32] }),
         session_id: "session-9000".to_string(),
-        final_event_digest: Some(Digest32 { value: vec![0x30; 32] }),
+        final_event_digest: sep_event_3.event_digest.clone(),
         final_record_digest: Some(Digest32 { value: vec![0xCD; 32] }),
         proof_receipt_ref: Some(Ref {
             uri: "proof://session/receipt".to_string(),
@@ -1000,84 +972,84 @@ fn main() -> anyhow::Result<()> {
             label: "missing".to_string(),
         }],
         missing_edges: vec!["evt-2->evt-4".to_string(), "evt-1->evt-3".to_string()],
-        reason_codes: Some(ReasonCodes { codes: sorted_strings(&["edge-gap", "missing-node"]) }),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["edge-gap", "missing-node"]) }),
         proof_receipt_ref: Some(Ref {
             uri: "proof://completeness/receipt".to_string(),
             label: "proof".to_string(),
         }),
     };
 
-    emit_fixture("canonical_intent_query", "ucf.v1.CanonicalIntent", &canonical_intent, domain)?;
-    emit_fixture("policy_decision", "ucf.v1.PolicyDecision", &policy_decision, domain)?;
-    emit_fixture("pvgs_receipt", "ucf.v1.PVGSReceipt", &pvgs_receipt, domain)?;
-    emit_fixture(
+    fixtures.push("canonical_intent_query", "ucf.v1.CanonicalIntent", domain, &canonical_intent);
+    fixtures.push("policy_decision", "ucf.v1.PolicyDecision", domain, &policy_decision);
+    fixtures.push("pvgs_receipt", "ucf.v1.PVGSReceipt", domain, &pvgs_receipt);
+    fixtures.push(
         "asset_digest_morphology_v1",
         "ucf.v1.AssetDigest",
-        &asset_digest_morphology,
         asset_morph_domain,
-    )?;
-    emit_fixture(
+        &asset_digest_morphology,
+    );
+    fixtures.push(
         "asset_manifest_v1",
         "ucf.v1.AssetManifest",
-        &asset_manifest,
         asset_manifest_domain,
-    )?;
-    emit_fixture(
+        &asset_manifest,
+    );
+    fixtures.push(
         "biophys_morphology_set_v1",
         "ucf.v1.MorphologySetPayload",
-        &morphology_payload,
         asset_morph_domain,
-    )?;
-    emit_fixture(
+        &morphology_payload,
+    );
+    fixtures.push(
         "biophys_channel_params_set_v1",
         "ucf.v1.ChannelParamsSetPayload",
-        &channel_params_payload,
         asset_channel_params_domain,
-    )?;
-    emit_fixture(
+        &channel_params_payload,
+    );
+    fixtures.push(
         "biophys_synapse_params_set_v1",
         "ucf.v1.SynapseParamsSetPayload",
-        &synapse_params_payload,
         asset_syn_params_domain,
-    )?;
-    emit_fixture(
+        &synapse_params_payload,
+    );
+    fixtures.push(
         "biophys_connectivity_graph_v1",
         "ucf.v1.ConnectivityGraphPayload",
-        &connectivity_payload,
         asset_connectivity_domain,
-    )?;
-    emit_fixture("signal_frame_short_window", "ucf.v1.SignalFrame", &signal_frame, domain)?;
-    emit_fixture("control_frame_m1_overlays_on", "ucf.v1.ControlFrame", &control_frame, domain)?;
-    emit_fixture(
+        &connectivity_payload,
+    );
+    fixtures.push("signal_frame_short_window", "ucf.v1.SignalFrame", domain, &signal_frame);
+    fixtures.push("control_frame_m1_overlays_on", "ucf.v1.ControlFrame", domain, &control_frame);
+    fixtures.push(
         "experience_rt_perception",
         "ucf.v1.ExperienceRecord",
-        &experience_rt_perception,
         domain,
-    )?;
-    emit_fixture(
+        &experience_rt_perception,
+    );
+    fixtures.push(
         "experience_rt_action_exec",
         "ucf.v1.ExperienceRecord",
-        &experience_rt_action_exec,
         domain,
-    )?;
-    emit_fixture("experience_rt_output", "ucf.v1.ExperienceRecord", &experience_rt_output, domain)?;
-    emit_fixture("micro_milestone_sealed", "ucf.v1.MicroMilestone", &micro_milestone, domain)?;
-    emit_fixture("meso_milestone_stable", "ucf.v1.MesoMilestone", &meso_milestone, domain)?;
-    emit_fixture("macro_milestone_finalized", "ucf.v1.MacroMilestone", &macro_milestone, domain)?;
-    emit_fixture("replay_plan_high_fidelity", "ucf.v1.ReplayPlan", &replay_plan, domain)?;
-    emit_fixture(
+        &experience_rt_action_exec,
+    );
+    fixtures.push("experience_rt_output", "ucf.v1.ExperienceRecord", domain, &experience_rt_output);
+    fixtures.push("micro_milestone_sealed", "ucf.v1.MicroMilestone", domain, &micro_milestone);
+    fixtures.push("meso_milestone_stable", "ucf.v1.MesoMilestone", domain, &meso_milestone);
+    fixtures.push("macro_milestone_finalized", "ucf.v1.MacroMilestone", domain, &macro_milestone);
+    fixtures.push("replay_plan_high_fidelity", "ucf.v1.ReplayPlan", domain, &replay_plan);
+    fixtures.push(
         "replay_plan_asset_manifest_ref",
         "ucf.v1.ReplayPlan",
-        &replay_plan_asset_manifest,
         domain,
-    )?;
-    emit_fixture("replay_run_evidence", "ucf.v1.ReplayRunEvidence", &replay_run, domain)?;
-    emit_fixture(
+        &replay_plan_asset_manifest,
+    );
+    fixtures.push("replay_run_evidence", "ucf.v1.ReplayRunEvidence", domain, &replay_run);
+    fixtures.push(
         "consistency_feedback_low_flags",
         "ucf.v1.ConsistencyFeedback",
-        &consistency_feedback,
         domain,
-    )?;
+        &consistency_feedback,
+    );
 
     let microcircuit_config_lc = MicrocircuitConfigEvidence {
         module: MicroModule::Lc as i32,
@@ -1097,12 +1069,12 @@ fn main() -> anyhow::Result<()> {
         attestation_key_id: Some("attest-key-1".to_string()),
     };
 
-    emit_fixture(
+    fixtures.push(
         "microcircuit_config_lc_v1",
         "ucf.v1.MicrocircuitConfigEvidence",
-        &microcircuit_config_lc,
         microcircuit_domain,
-    )?;
+        &microcircuit_config_lc,
+    );
 
     let microcircuit_config_sn = MicrocircuitConfigEvidence {
         module: MicroModule::Sn as i32,
@@ -1115,12 +1087,12 @@ fn main() -> anyhow::Result<()> {
         attestation_key_id: None,
     };
 
-    emit_fixture(
+    fixtures.push(
         "microcircuit_config_sn_v1",
         "ucf.v1.MicrocircuitConfigEvidence",
-        &microcircuit_config_sn,
         microcircuit_domain,
-    )?;
+        &microcircuit_config_sn,
+    );
 
     let microcircuit_config_hpa = MicrocircuitConfigEvidence {
         module: MicroModule::Hpa as i32,
@@ -1136,31 +1108,41 @@ fn main() -> anyhow::Result<()> {
         attestation_key_id: Some("attest-key-hpa-1".to_string()),
     };
 
-    emit_fixture(
+    fixtures.push(
         "microcircuit_config_hpa_v1",
         "ucf.v1.MicrocircuitConfigEvidence",
-        &microcircuit_config_hpa,
         microcircuit_domain,
-    )?;
-    emit_fixture(
+        &microcircuit_config_hpa,
+    );
+    fixtures.push(
         "tool_registry_container",
         "ucf.v1.ToolRegistryContainer",
-        &registry_container,
         domain,
-    )?;
-    emit_fixture("tool_onboarding_event", "ucf.v1.ToolOnboardingEvent", &onboarding_event, domain)?;
-    emit_fixture(
+        &registry_container,
+    );
+    fixtures.push("tool_onboarding_event", "ucf.v1.ToolOnboardingEvent", domain, &onboarding_event);
+    fixtures.push(
         "approval_artifact_package",
         "ucf.v1.ApprovalArtifactPackage",
-        &approval_artifact_package,
         domain,
-    )?;
-    emit_fixture("approval_decision", "ucf.v1.ApprovalDecision", &approval_decision, domain)?;
-    emit_fixture("sep_event_chain_1", "ucf.v1.SepEvent", &sep_event_1, domain)?;
-    emit_fixture("sep_event_chain_2", "ucf.v1.SepEvent", &sep_event_2, domain)?;
-    emit_fixture("sep_event_chain_3", "ucf.v1.SepEvent", &sep_event_3, domain)?;
-    emit_fixture("session_seal", "ucf.v1.SessionSeal", &session_seal, domain)?;
-    emit_fixture("completeness_report", "ucf.v1.CompletenessReport", &completeness_report, domain)?;
+        &approval_artifact_package,
+    );
+    fixtures.push("approval_decision", "ucf.v1.ApprovalDecision", domain, &approval_decision);
+    fixtures.push("sep_event_chain_1", "ucf.v1.SepEvent", domain, &sep_event_1);
+    fixtures.push("sep_event_chain_2", "ucf.v1.SepEvent", domain, &sep_event_2);
+    fixtures.push("sep_event_chain_3", "ucf.v1.SepEvent", domain, &sep_event_3);
+    fixtures.push("session_seal", "ucf.v1.SessionSeal", domain, &session_seal);
+    fixtures.push("completeness_report", "ucf.v1.CompletenessReport", domain, &completeness_report);
 
+    // Each entry writes its own pair of files, so digesting and writing run in
+    // parallel without affecting the output.
+    let dir = Path::new("testvectors");
+    fs::create_dir_all(dir)?;
+    fixtures.entries().par_iter().try_for_each(|entry| entry.write(dir))?;
     Ok(())
 }
+
+fn with_event_digest(mut event: SepEvent) -> SepEvent {
+    event.event_digest = Some(Digest32 { value: compute_sep_event_digest(&event).to_vec() });
+    event
+}
@@ -0,0 +1,357 @@
+//! Checking that an [`ApprovalDecision`] satisfies its [`ApprovalArtifactPackage`].
+//!
+//! A package's `aap_digest` is [`compute_aap_digest`] of its content: the
+//! canonical encoding with `aap_digest` cleared, under `UCF:APPROVAL:PACKAGE`.
+//!
+//! A decision satisfies a package when the package's stored `aap_digest`
+//! matches its content, the decision approves (with or without modifications),
+//! names the package by that digest, arrives before the package's
+//! `expires_at_ms`, and carries valid signatures over the digest from as many
+//! distinct signers as the package's `two_person_requirement` demands. Since
+//! the digest covers `expires_at_ms` and `two_person_requirement`, weakening
+//! either invalidates every signature. Whether the signers are trusted is left
+//! to the caller.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use subtle::ConstantTimeEq;
+
+use crate::crypto::{AllowedAlgorithms, SignatureError};
+use crate::digest::{compute_self_digest, SelfDigested};
+use crate::ucf::v1::{
+    ApprovalArtifactPackage, ApprovalDecision, ApprovalDecisionType, Digest32, TwoPersonRequirement,
+};
+use crate::Domain;
+
+/// Domain tag for [`compute_aap_digest`].
+pub const APPROVAL_PACKAGE_DOMAIN: &str = Domain::ApprovalPackage.as_str();
+const APPROVAL_PACKAGE_SCHEMA: &str = "ucf.v1.ApprovalArtifactPackage";
+
+/// Errors returned by [`validate_approval`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ApprovalError {
+    /// `decision` is not `APPROVE` or `APPROVE_WITH_MODIFICATIONS`.
+    NotApproved { decision: i32 },
+    /// The package has no `aap_digest`.
+    PackageDigestMissing,
+    /// The package's `aap_digest` is not [`compute_aap_digest`] of its content.
+    PackageDigestInvalid { expected: [u8; 32], found: Vec<u8> },
+    /// The decision's `aap_digest` differs from the package's.
+    PackageDigestMismatch { expected: Vec<u8>, found: Vec<u8> },
+    /// The package has no `expires_at_ms`.
+    ExpiryMissing,
+    /// The decision was checked at or after the package's `expires_at_ms`.
+    Expired { expires_at_ms: u64, now_ms: u64 },
+    /// The signature at `index` in `signatures` does not verify over the package digest.
+    InvalidSignature { index: usize, error: SignatureError },
+    /// Fewer distinct signers than the package's `two_person_requirement`.
+    InsufficientSigners { required: usize, found: usize },
+}
+
+impl fmt::Display for ApprovalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApprovalError::NotApproved { decision } => {
+                write!(f, "approval decision {decision} does not approve")
+            }
+            ApprovalError::PackageDigestMissing => {
+                f.write_str("approval artifact package has no aap_digest")
+            }
+            ApprovalError::PackageDigestInvalid { expected, found } => write!(
+                f,
+                "approval artifact package aap_digest {} does not match its content digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+            ApprovalError::PackageDigestMismatch { expected, found } => write!(
+                f,
+                "approval decision aap_digest {} does not match package digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+            ApprovalError::ExpiryMissing => {
+                f.write_str("approval artifact package has no expires_at_ms")
+            }
+            ApprovalError::Expired { expires_at_ms, now_ms } => {
+                write!(f, "approval at {now_ms} is not before package expiry {expires_at_ms}")
+            }
+            ApprovalError::InvalidSignature { index, error } => {
+                write!(f, "approval signature {index}: {error}")
+            }
+            ApprovalError::InsufficientSigners { required, found } => {
+                write!(f, "approval has {found} distinct signers, {required} required")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ApprovalError {}
+
+/// Distinct signers a decision needs under `requirement`.
+///
+/// `UNSPECIFIED` and undefined values require two, the strictest setting.
+pub fn required_signers(requirement: TwoPersonRequirement) -> usize {
+    match requirement {
+        TwoPersonRequirement::None => 0,
+        TwoPersonRequirement::One => 1,
+        TwoPersonRequirement::Two | TwoPersonRequirement::Unspecified => 2,
+    }
+}
+
+impl SelfDigested for ApprovalArtifactPackage {
+    const SCHEMA_ID: &'static str = APPROVAL_PACKAGE_SCHEMA;
+
+    fn domain() -> &'static str {
+        APPROVAL_PACKAGE_DOMAIN
+    }
+
+    fn take_digest(&mut self) -> Option<Digest32> {
+        self.aap_digest.take()
+    }
+}
+
+/// Content digest of `aap`, ignoring `aap_digest`.
+pub fn compute_aap_digest(aap: &ApprovalArtifactPackage) -> [u8; 32] {
+    compute_self_digest(aap)
+}
+
+/// Confirm `decision` approves `aap` and satisfies its expiry and signer count at `now_ms`.
+///
+/// The package's `aap_digest` must equal [`compute_aap_digest`] of it, and
+/// every signature must verify over that digest under an `allowed` algorithm.
+/// A package with `expires_at_ms` unset (zero) is rejected rather than treated
+/// as never expiring.
+pub fn validate_approval(
+    aap: &ApprovalArtifactPackage,
+    decision: &ApprovalDecision,
+    now_ms: u64,
+    allowed: &AllowedAlgorithms,
+) -> Result<(), ApprovalError> {
+    match ApprovalDecisionType::try_from(decision.decision) {
+        Ok(ApprovalDecisionType::Approve | ApprovalDecisionType::ApproveWithModifications) => {}
+        _ => return Err(ApprovalError::NotApproved { decision: decision.decision }),
+    }
+    let stored = aap.aap_digest.as_ref().ok_or(ApprovalError::PackageDigestMissing)?;
+    let expected = compute_aap_digest(aap);
+    if !bool::from(stored.value.as_slice().ct_eq(&expected)) {
+        return Err(ApprovalError::PackageDigestInvalid { expected, found: stored.value.clone() });
+    }
+    let found =
+        decision.aap_digest.as_ref().map(|digest| digest.value.as_slice()).unwrap_or_default();
+    if !bool::from(found.ct_eq(&expected)) {
+        return Err(ApprovalError::PackageDigestMismatch {
+            expected: expected.to_vec(),
+            found: found.to_vec(),
+        });
+    }
+    if aap.expires_at_ms == 0 {
+        return Err(ApprovalError::ExpiryMissing);
+    }
+    if now_ms >= aap.expires_at_ms {
+        return Err(ApprovalError::Expired { expires_at_ms: aap.expires_at_ms, now_ms });
+    }
+    for (index, signature) in decision.signatures.iter().enumerate() {
+        signature
+            .verify(&expected, allowed)
+            .map_err(|error| ApprovalError::InvalidSignature { index, error })?;
+    }
+    let required = required_signers(aap.two_person_requirement());
+    let signers: BTreeSet<&[u8]> =
+        decision.signatures.iter().map(|signature| signature.signer.as_slice()).collect();
+    if signers.len() < required {
+        return Err(ApprovalError::InsufficientSigners { required, found: signers.len() });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ucf::v1::Signature;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const ALICE: [u8; 32] = [1; 32];
+    const BOB: [u8; 32] = [2; 32];
+
+    fn signature(signer: &[u8; 32], aap: &ApprovalArtifactPackage) -> Signature {
+        let key = SigningKey::from_bytes(signer);
+        Signature::ed25519(&key.verifying_key(), &key.sign(&compute_aap_digest(aap)))
+    }
+
+    fn sealed(mut aap: ApprovalArtifactPackage) -> ApprovalArtifactPackage {
+        aap.aap_digest = Some(Digest32 { value: compute_aap_digest(&aap).to_vec() });
+        aap
+    }
+
+    fn package(requirement: TwoPersonRequirement) -> ApprovalArtifactPackage {
+        sealed(ApprovalArtifactPackage {
+            aap_id: "aap-01".to_string(),
+            expires_at_ms: 1_000,
+            two_person_requirement: requirement as i32,
+            ..Default::default()
+        })
+    }
+
+    fn decision(aap: &ApprovalArtifactPackage, signers: &[&[u8; 32]]) -> ApprovalDecision {
+        ApprovalDecision {
+            approval_id: "approval-01".to_string(),
+            aap_digest: aap.aap_digest.clone(),
+            decision: ApprovalDecisionType::Approve as i32,
+            signatures: signers.iter().map(|signer| signature(signer, aap)).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn validate(
+        aap: &ApprovalArtifactPackage,
+        decision: &ApprovalDecision,
+        now_ms: u64,
+    ) -> Result<(), ApprovalError> {
+        validate_approval(aap, decision, now_ms, &AllowedAlgorithms::default())
+    }
+
+    #[test]
+    fn accepts_matching_unexpired_signed_approval() {
+        let aap = package(TwoPersonRequirement::Two);
+        assert_eq!(validate(&aap, &decision(&aap, &[&ALICE, &BOB]), 999), Ok(()));
+
+        let mut modified = decision(&aap, &[&ALICE, &BOB]);
+        modified.decision = ApprovalDecisionType::ApproveWithModifications as i32;
+        assert_eq!(validate(&aap, &modified, 0), Ok(()));
+
+        let unrestricted = package(TwoPersonRequirement::None);
+        assert_eq!(validate(&unrestricted, &decision(&unrestricted, &[]), 0), Ok(()));
+    }
+
+    #[test]
+    fn rejects_denials_wrong_packages_and_late_decisions() {
+        let aap = package(TwoPersonRequirement::One);
+        let mut denied = decision(&aap, &[&ALICE]);
+        denied.decision = ApprovalDecisionType::Deny as i32;
+        assert_eq!(
+            validate(&aap, &denied, 0),
+            Err(ApprovalError::NotApproved { decision: ApprovalDecisionType::Deny as i32 })
+        );
+
+        let mut other = decision(&aap, &[&ALICE]);
+        other.aap_digest = Some(Digest32 { value: vec![0xBB; 32] });
+        assert_eq!(
+            validate(&aap, &other, 0),
+            Err(ApprovalError::PackageDigestMismatch {
+                expected: compute_aap_digest(&aap).to_vec(),
+                found: vec![0xBB; 32],
+            })
+        );
+
+        let unsealed = ApprovalArtifactPackage { aap_digest: None, ..aap.clone() };
+        assert_eq!(
+            validate(&unsealed, &decision(&aap, &[&ALICE]), 0),
+            Err(ApprovalError::PackageDigestMissing)
+        );
+
+        assert_eq!(
+            validate(&aap, &decision(&aap, &[&ALICE]), 1_000),
+            Err(ApprovalError::Expired { expires_at_ms: 1_000, now_ms: 1_000 })
+        );
+
+        let unbounded = sealed(ApprovalArtifactPackage { expires_at_ms: 0, ..aap.clone() });
+        assert_eq!(
+            validate(&unbounded, &decision(&unbounded, &[&ALICE]), 0),
+            Err(ApprovalError::ExpiryMissing)
+        );
+    }
+
+    #[test]
+    fn rejects_packages_weakened_after_signing() {
+        let aap = package(TwoPersonRequirement::Two);
+        let approval = decision(&aap, &[&ALICE]);
+
+        for weakened in [
+            ApprovalArtifactPackage { expires_at_ms: u64::MAX, ..aap.clone() },
+            ApprovalArtifactPackage {
+                two_person_requirement: TwoPersonRequirement::One as i32,
+                ..aap.clone()
+            },
+        ] {
+            assert_eq!(
+                validate(&weakened, &approval, 0),
+                Err(ApprovalError::PackageDigestInvalid {
+                    expected: compute_aap_digest(&weakened),
+                    found: compute_aap_digest(&aap).to_vec(),
+                })
+            );
+
+            // Resealing the weakened package changes its digest, so the
+            // decision no longer names it and its signatures do not cover it.
+            let resealed = sealed(weakened);
+            assert!(matches!(
+                validate(&resealed, &approval, 0),
+                Err(ApprovalError::PackageDigestMismatch { .. })
+            ));
+            let mut renamed = approval.clone();
+            renamed.aap_digest = resealed.aap_digest.clone();
+            assert!(matches!(
+                validate(&resealed, &renamed, 0),
+                Err(ApprovalError::InvalidSignature { index: 0, error: SignatureError::Invalid })
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_unverified_signatures() {
+        let aap = package(TwoPersonRequirement::Two);
+
+        let mut forged = decision(&aap, &[&ALICE, &BOB]);
+        forged.signatures[1].signature = vec![0xEE; 64];
+        assert_eq!(
+            validate(&aap, &forged, 0),
+            Err(ApprovalError::InvalidSignature { index: 1, error: SignatureError::Invalid })
+        );
+
+        let mut borrowed = decision(&aap, &[&ALICE, &ALICE]);
+        borrowed.signatures[1].signer =
+            SigningKey::from_bytes(&BOB).verifying_key().to_bytes().to_vec();
+        assert_eq!(
+            validate(&aap, &borrowed, 0),
+            Err(ApprovalError::InvalidSignature { index: 1, error: SignatureError::Invalid })
+        );
+
+        let mut anonymous = decision(&aap, &[&ALICE, &BOB]);
+        anonymous.signatures[1].signer.clear();
+        assert_eq!(
+            validate(&aap, &anonymous, 0),
+            Err(ApprovalError::InvalidSignature {
+                index: 1,
+                error: SignatureError::MalformedSigner,
+            })
+        );
+
+        assert_eq!(
+            validate_approval(
+                &aap,
+                &decision(&aap, &[&ALICE, &BOB]),
+                0,
+                &AllowedAlgorithms::none()
+            ),
+            Err(ApprovalError::InvalidSignature {
+                index: 0,
+                error: SignatureError::UnsupportedAlgorithm("ed25519".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn counts_distinct_signers() {
+        let aap = package(TwoPersonRequirement::Two);
+        assert_eq!(
+            validate(&aap, &decision(&aap, &[&ALICE, &ALICE]), 0),
+            Err(ApprovalError::InsufficientSigners { required: 2, found: 1 })
+        );
+        let unspecified = package(TwoPersonRequirement::Unspecified);
+        assert_eq!(
+            validate(&unspecified, &decision(&unspecified, &[&ALICE]), 0),
+            Err(ApprovalError::InsufficientSigners { required: 2, found: 1 })
+        );
+    }
+}
@@ -0,0 +1,367 @@
+//! Linkage checks for asset manifests and asset version histories.
+//!
+//! An [`AssetManifest`]'s `manifest_digest` commits to its four assets:
+//! `digest32("UCF:ASSET:MANIFEST", "ucf.v1.AssetManifest", "1", preimage)`
+//! where `preimage` is the `digest` of the morphology, channel params, synapse
+//! params, and connectivity assets, in that order, followed by
+//! `created_at_ms` as a little-endian `u64`.
+//!
+//! Each biophysics payload is digested under its own domain and schema:
+//! [`asset_payload_digest`] picks them from the [`AssetKind`], and
+//! [`verify_asset_payload`] checks an [`AssetDigest`] describes a payload.
+
+use std::fmt;
+
+use prost::Message;
+use subtle::ConstantTimeEq;
+
+use crate::ucf::v1::{
+    AssetDigest, AssetKind, AssetManifest, ChannelParamsSetPayload, ConnectivityGraphPayload,
+    Digest32, MorphologySetPayload, SynapseParamsSetPayload,
+};
+use crate::{canonical_bytes, digest32, Domain};
+
+pub const ASSET_MANIFEST_DOMAIN: &str = Domain::AssetManifest.as_str();
+const MANIFEST_SCHEMA: &str = "ucf.v1.AssetManifest";
+const SCHEMA_VERSION: &str = "1";
+
+/// Errors returned by [`verify_asset_manifest`] and [`verify_asset_chain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssetError {
+    /// The manifest has no asset in `field`.
+    AssetMissing { field: &'static str },
+    /// The asset in `field` is not of the kind that slot holds.
+    WrongKind { field: &'static str, expected: AssetKind, found: i32 },
+    /// The asset in `field` has no 32-byte `digest`.
+    BadDigest { field: &'static str },
+    /// The manifest has no `manifest_digest`.
+    ManifestDigestMissing,
+    /// `manifest_digest` does not commit to the manifest's assets.
+    ManifestDigestMismatch { expected: [u8; 32], found: Vec<u8> },
+    /// The history entry at `index` has a different kind than the first entry.
+    KindChanged { index: usize },
+    /// The history entry at `index` does not point at the previous entry's digest.
+    PrevDigestMismatch { index: usize, expected: Vec<u8>, found: Vec<u8> },
+    /// `kind` does not name a biophysics payload.
+    NotAPayloadKind(AssetKind),
+    /// The asset's `kind` is not the kind of the payload it is checked against.
+    PayloadKindMismatch { expected: AssetKind, found: i32 },
+    /// The asset's `digest` is not the payload's digest.
+    PayloadDigestMismatch { expected: [u8; 32], found: Vec<u8> },
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetError::AssetMissing { field } => write!(f, "manifest has no {field} asset"),
+            AssetError::WrongKind { field, expected, found } => write!(
+                f,
+                "manifest {field} asset has kind {found}, expected {}",
+                expected.as_str_name()
+            ),
+            AssetError::BadDigest { field } => {
+                write!(f, "manifest {field} asset has no 32-byte digest")
+            }
+            AssetError::ManifestDigestMissing => write!(f, "manifest has no manifest_digest"),
+            AssetError::ManifestDigestMismatch { expected, found } => write!(
+                f,
+                "manifest_digest {} does not match asset digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+            AssetError::KindChanged { index } => {
+                write!(f, "asset history entry {index} changes asset kind")
+            }
+            AssetError::PrevDigestMismatch { index, expected, found } => write!(
+                f,
+                "asset history entry {index} prev_digest {} does not match previous digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+            AssetError::NotAPayloadKind(kind) => {
+                write!(f, "asset kind {} has no payload digest", kind.as_str_name())
+            }
+            AssetError::PayloadKindMismatch { expected, found } => {
+                write!(f, "asset has kind {found}, payload is {}", expected.as_str_name())
+            }
+            AssetError::PayloadDigestMismatch { expected, found } => write!(
+                f,
+                "asset digest {} does not match payload digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AssetError {}
+
+/// A biophysics payload message and the [`AssetKind`] it is stored as.
+pub trait AssetPayload: Message {
+    const KIND: AssetKind;
+}
+
+impl AssetPayload for MorphologySetPayload {
+    const KIND: AssetKind = AssetKind::MorphologySet;
+}
+
+impl AssetPayload for ChannelParamsSetPayload {
+    const KIND: AssetKind = AssetKind::ChannelParamsSet;
+}
+
+impl AssetPayload for SynapseParamsSetPayload {
+    const KIND: AssetKind = AssetKind::SynapseParamsSet;
+}
+
+impl AssetPayload for ConnectivityGraphPayload {
+    const KIND: AssetKind = AssetKind::ConnectivityGraph;
+}
+
+/// The domain and schema a payload of `kind` is digested under.
+fn payload_domain(kind: AssetKind) -> Option<(Domain, &'static str)> {
+    match kind {
+        AssetKind::MorphologySet => Some((Domain::AssetMorphology, "ucf.v1.MorphologySetPayload")),
+        AssetKind::ChannelParamsSet => {
+            Some((Domain::AssetChannelParams, "ucf.v1.ChannelParamsSetPayload"))
+        }
+        AssetKind::SynapseParamsSet => {
+            Some((Domain::AssetSynapseParams, "ucf.v1.SynapseParamsSetPayload"))
+        }
+        AssetKind::ConnectivityGraph => {
+            Some((Domain::AssetConnectivity, "ucf.v1.ConnectivityGraphPayload"))
+        }
+        AssetKind::Unspecified | AssetKind::AssetManifest => None,
+    }
+}
+
+/// Digest the canonical `bytes` of a payload of `kind` under that kind's domain.
+///
+/// Fails for kinds that have no payload: `Unspecified` and `AssetManifest`.
+pub fn asset_payload_digest(kind: AssetKind, bytes: &[u8]) -> Result<[u8; 32], AssetError> {
+    let (domain, schema) = payload_domain(kind).ok_or(AssetError::NotAPayloadKind(kind))?;
+    Ok(digest32(domain, schema, SCHEMA_VERSION, bytes))
+}
+
+/// Confirm `asset` has `payload`'s kind and commits to its digest.
+pub fn verify_asset_payload<P: AssetPayload>(
+    asset: &AssetDigest,
+    payload: &P,
+) -> Result<(), AssetError> {
+    if asset.kind != P::KIND as i32 {
+        return Err(AssetError::PayloadKindMismatch { expected: P::KIND, found: asset.kind });
+    }
+    let expected = asset_payload_digest(P::KIND, &canonical_bytes(payload))?;
+    let found = asset.digest.as_ref().map(|digest| digest.value.as_slice()).unwrap_or_default();
+    if !bool::from(found.ct_eq(&expected)) {
+        return Err(AssetError::PayloadDigestMismatch { expected, found: found.to_vec() });
+    }
+    Ok(())
+}
+
+/// The `manifest_digest` that `manifest`'s assets and `created_at_ms` commit to.
+pub fn compute_manifest_digest(manifest: &AssetManifest) -> Result<[u8; 32], AssetError> {
+    let slots = [
+        ("morphology", &manifest.morphology, AssetKind::MorphologySet),
+        ("channel_params", &manifest.channel_params, AssetKind::ChannelParamsSet),
+        ("synapse_params", &manifest.synapse_params, AssetKind::SynapseParamsSet),
+        ("connectivity", &manifest.connectivity, AssetKind::ConnectivityGraph),
+    ];
+    let mut preimage = Vec::with_capacity(slots.len() * 32 + 8);
+    for (field, asset, expected) in slots {
+        let asset = asset.as_ref().ok_or(AssetError::AssetMissing { field })?;
+        if asset.kind != expected as i32 {
+            return Err(AssetError::WrongKind { field, expected, found: asset.kind });
+        }
+        match asset.digest.as_ref() {
+            Some(digest) if digest.value.len() == 32 => preimage.extend_from_slice(&digest.value),
+            _ => return Err(AssetError::BadDigest { field }),
+        }
+    }
+    preimage.extend_from_slice(&manifest.created_at_ms.to_le_bytes());
+    Ok(digest32(ASSET_MANIFEST_DOMAIN, MANIFEST_SCHEMA, SCHEMA_VERSION, &preimage))
+}
+
+/// Confirm `manifest_digest` is [`compute_manifest_digest`] of `manifest`.
+pub fn verify_asset_manifest(manifest: &AssetManifest) -> Result<(), AssetError> {
+    let expected = compute_manifest_digest(manifest)?;
+    let found = manifest.manifest_digest.as_ref().ok_or(AssetError::ManifestDigestMissing)?;
+    if !bool::from(found.value.as_slice().ct_eq(&expected)) {
+        return Err(AssetError::ManifestDigestMismatch { expected, found: found.value.clone() });
+    }
+    Ok(())
+}
+
+/// Confirm `history`, oldest first, is one asset's version chain.
+///
+/// Every entry must have the first entry's `kind`, and each entry after the
+/// first must carry the previous entry's `digest` as its `prev_digest`. The
+/// first entry's `prev_digest` is not checked.
+pub fn verify_asset_chain(history: &[AssetDigest]) -> Result<(), AssetError> {
+    for (index, pair) in history.windows(2).enumerate() {
+        let (prev, next) = (&pair[0], &pair[1]);
+        let index = index + 1;
+        if next.kind != history[0].kind {
+            return Err(AssetError::KindChanged { index });
+        }
+        let expected = prev.digest.as_ref().map(|digest| &digest.value);
+        let found = next.prev_digest.as_ref().map(|digest| &digest.value);
+        if expected.is_none() || found != expected {
+            return Err(AssetError::PrevDigestMismatch {
+                index,
+                expected: expected.cloned().unwrap_or_default(),
+                found: found.cloned().unwrap_or_default(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Convenience for building a manifest: set `manifest_digest` from its assets.
+pub fn seal_asset_manifest(manifest: &mut AssetManifest) -> Result<(), AssetError> {
+    let digest = compute_manifest_digest(manifest)?;
+    manifest.manifest_digest = Some(Digest32 { value: digest.to_vec() });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{decode_fixture, decode_fixture_with_digest};
+
+    fn manifest_fixture() -> AssetManifest {
+        decode_fixture("asset_manifest_v1")
+    }
+
+    #[test]
+    fn manifest_digest_commits_to_its_assets() {
+        let mut manifest = manifest_fixture();
+        assert!(matches!(
+            verify_asset_manifest(&manifest),
+            Err(AssetError::ManifestDigestMismatch { .. })
+        ));
+
+        seal_asset_manifest(&mut manifest).expect("fixture assets are complete");
+        assert_eq!(verify_asset_manifest(&manifest), Ok(()));
+
+        let mut swapped = manifest.clone();
+        swapped.synapse_params.as_mut().unwrap().digest = Some(Digest32 { value: vec![0x33; 32] });
+        assert!(matches!(
+            verify_asset_manifest(&swapped),
+            Err(AssetError::ManifestDigestMismatch { .. })
+        ));
+
+        let mut restamped = manifest.clone();
+        restamped.created_at_ms += 1;
+        assert!(verify_asset_manifest(&restamped).is_err());
+
+        let mut misplaced = manifest.clone();
+        misplaced.connectivity = misplaced.morphology.clone();
+        assert_eq!(
+            verify_asset_manifest(&misplaced),
+            Err(AssetError::WrongKind {
+                field: "connectivity",
+                expected: AssetKind::ConnectivityGraph,
+                found: AssetKind::MorphologySet as i32,
+            })
+        );
+
+        manifest.channel_params = None;
+        assert_eq!(
+            verify_asset_manifest(&manifest),
+            Err(AssetError::AssetMissing { field: "channel_params" })
+        );
+    }
+
+    fn version(version: u32, digest: u8, prev: Option<u8>) -> AssetDigest {
+        AssetDigest {
+            kind: AssetKind::MorphologySet as i32,
+            version,
+            digest: Some(Digest32 { value: vec![digest; 32] }),
+            prev_digest: prev.map(|prev| Digest32 { value: vec![prev; 32] }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn asset_chain_follows_prev_digests() {
+        let history = vec![version(1, 0x01, None), version(2, 0x02, Some(0x01))];
+        assert_eq!(verify_asset_chain(&history), Ok(()));
+        assert_eq!(verify_asset_chain(&history[..1]), Ok(()));
+
+        let skipped = vec![history[0].clone(), version(3, 0x03, Some(0x02))];
+        assert_eq!(
+            verify_asset_chain(&skipped),
+            Err(AssetError::PrevDigestMismatch {
+                index: 1,
+                expected: vec![0x01; 32],
+                found: vec![0x02; 32],
+            })
+        );
+
+        let mut other_kind = history[1].clone();
+        other_kind.kind = AssetKind::ConnectivityGraph as i32;
+        assert_eq!(
+            verify_asset_chain(&[history[0].clone(), other_kind]),
+            Err(AssetError::KindChanged { index: 1 })
+        );
+    }
+
+    fn check_payload<P: AssetPayload + Default>(name: &str) {
+        let (payload, fixture_digest) = decode_fixture_with_digest::<P>(name);
+        let digest = asset_payload_digest(P::KIND, &canonical_bytes(&payload)).unwrap();
+        assert_eq!(digest, fixture_digest, "{name}");
+
+        let mut asset = AssetDigest {
+            kind: P::KIND as i32,
+            digest: Some(Digest32 { value: digest.to_vec() }),
+            ..Default::default()
+        };
+        assert_eq!(verify_asset_payload(&asset, &payload), Ok(()), "{name}");
+
+        let other = if P::KIND == AssetKind::MorphologySet {
+            AssetKind::SynapseParamsSet
+        } else {
+            AssetKind::MorphologySet
+        };
+        assert_ne!(asset_payload_digest(other, &canonical_bytes(&payload)).unwrap(), digest);
+
+        asset.kind = other as i32;
+        assert_eq!(
+            verify_asset_payload(&asset, &payload),
+            Err(AssetError::PayloadKindMismatch { expected: P::KIND, found: other as i32 })
+        );
+    }
+
+    #[test]
+    fn payload_digest_uses_the_kind_domain() {
+        check_payload::<MorphologySetPayload>("biophys_morphology_set_v1");
+        check_payload::<ChannelParamsSetPayload>("biophys_channel_params_set_v1");
+        check_payload::<SynapseParamsSetPayload>("biophys_synapse_params_set_v1");
+        check_payload::<ConnectivityGraphPayload>("biophys_connectivity_graph_v1");
+
+        for kind in [AssetKind::Unspecified, AssetKind::AssetManifest] {
+            assert_eq!(asset_payload_digest(kind, b""), Err(AssetError::NotAPayloadKind(kind)));
+        }
+    }
+
+    #[test]
+    fn asset_must_commit_to_payload_digest() {
+        let (payload, digest) =
+            decode_fixture_with_digest::<SynapseParamsSetPayload>("biophys_synapse_params_set_v1");
+        let mut asset = AssetDigest {
+            kind: AssetKind::SynapseParamsSet as i32,
+            digest: Some(Digest32 { value: vec![0x44; 32] }),
+            ..Default::default()
+        };
+        assert_eq!(
+            verify_asset_payload(&asset, &payload),
+            Err(AssetError::PayloadDigestMismatch { expected: digest, found: vec![0x44; 32] })
+        );
+
+        asset.digest = None;
+        assert_eq!(
+            verify_asset_payload(&asset, &payload),
+            Err(AssetError::PayloadDigestMismatch { expected: digest, found: Vec::new() })
+        );
+    }
+}
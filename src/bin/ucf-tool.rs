@@ -0,0 +1,184 @@
+#![forbid(unsafe_code)]
+
+//! Operator tool for triaging fixtures and digests without writing Rust.
+//!
+//! ```text
+//! ucf-tool verify [--dir <testvectors>] <name>
+//! ucf-tool decode --schema <schema_id> <file>
+//! ucf-tool digest --domain <domain> --schema <schema_id> [--version 1] [--algo blake3|sha256] <file>
+//! ```
+//!
+//! Files ending in `.hex` or `.b64` are read as hex or base64url fixtures; any
+//! other file is read as raw bytes. `decode` prints canonical JSON when built
+//! with the `json` feature and the message's debug form otherwise. With the
+//! `dynamic` feature, `decode` also accepts any other `ucf.v1` message, decoded
+//! from the embedded descriptors and printed in its debug form.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use prost::{DecodeError, Message};
+use ucf_protocol::fixtures::read_message_file;
+use ucf_protocol::testing::{fixture_dir, verify_fixture_in, DeterminismReport};
+use ucf_protocol::{digest32_with, DigestAlgo};
+
+const USAGE: &str = "\
+usage:
+  ucf-tool verify [--dir <testvectors>] <name>
+  ucf-tool decode --schema <schema_id> <file>
+  ucf-tool digest --domain <domain> --schema <schema_id> [--version 1] [--algo blake3|sha256] <file>";
+
+/// Outcome of a subcommand that ran to completion.
+enum Outcome {
+    Ok,
+    Mismatch,
+}
+
+/// Options given as `--key value` pairs plus the remaining positional arguments.
+struct Args {
+    options: Vec<(String, String)>,
+    positional: Vec<String>,
+}
+
+impl Args {
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Args { options: Vec::new(), positional: Vec::new() };
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(key) => {
+                    let value = args.next().ok_or_else(|| format!("--{key} needs a value"))?;
+                    parsed.options.push((key.to_string(), value));
+                }
+                None => parsed.positional.push(arg),
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn option(&self, key: &str) -> Option<&str> {
+        self.options.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    fn required(&self, key: &str) -> Result<&str, String> {
+        self.option(key).ok_or_else(|| format!("missing --{key}"))
+    }
+
+    fn only_positional(&self) -> Result<&str, String> {
+        match self.positional.as_slice() {
+            [one] => Ok(one),
+            _ => Err("expected exactly one positional argument".to_string()),
+        }
+    }
+
+    fn reject_unknown(&self, known: &[&str]) -> Result<(), String> {
+        match self.options.iter().find(|(key, _)| !known.contains(&key.as_str())) {
+            Some((key, _)) => Err(format!("unknown option --{key}")),
+            None => Ok(()),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let command = args.next();
+    let result = Args::parse(args).and_then(|args| match command.as_deref() {
+        Some("verify") => verify(&args),
+        Some("decode") => decode(&args),
+        Some("digest") => digest(&args),
+        Some(other) => Err(format!("unknown command {other}")),
+        None => Err("missing command".to_string()),
+    });
+    match result {
+        Ok(Outcome::Ok) => ExitCode::SUCCESS,
+        Ok(Outcome::Mismatch) => ExitCode::from(1),
+        Err(err) => {
+            eprintln!("ucf-tool: {err}\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn verify(args: &Args) -> Result<Outcome, String> {
+    args.reject_unknown(&["dir"])?;
+    let name = args.only_positional()?;
+    let dir = args.option("dir").map(PathBuf::from).unwrap_or_else(fixture_dir);
+    let result =
+        verify_fixture_in(&dir, name).ok_or_else(|| format!("no registered fixture {name}"))?;
+    let report = DeterminismReport { results: vec![result] };
+    print!("{report}");
+    Ok(if report.is_stable() { Outcome::Ok } else { Outcome::Mismatch })
+}
+
+fn decode(args: &Args) -> Result<Outcome, String> {
+    args.reject_unknown(&["schema"])?;
+    let schema = args.required("schema")?;
+    let bytes = read_message_file(Path::new(args.only_positional()?))?;
+    let rendered = match render_schema(schema, &bytes) {
+        Some(rendered) => {
+            rendered.map_err(|err| format!("bytes do not decode as {schema}: {err}"))?
+        }
+        None => render_dynamic(schema, &bytes)?,
+    };
+    println!("{rendered}");
+    Ok(Outcome::Ok)
+}
+
+fn digest(args: &Args) -> Result<Outcome, String> {
+    args.reject_unknown(&["domain", "schema", "version", "algo"])?;
+    let algo = match args.option("algo").unwrap_or("blake3") {
+        "blake3" => DigestAlgo::Blake3,
+        "sha256" => DigestAlgo::Sha256,
+        other => return Err(format!("unknown algorithm {other}")),
+    };
+    let bytes = read_message_file(Path::new(args.only_positional()?))?;
+    let digest = digest32_with(
+        algo,
+        args.required("domain")?,
+        args.required("schema")?,
+        args.option("version").unwrap_or("1"),
+        &bytes,
+    );
+    println!("{}", hex::encode(digest));
+    Ok(Outcome::Ok)
+}
+
+#[cfg(feature = "json")]
+fn render<M: Message + Default + serde::Serialize>(bytes: &[u8]) -> Result<String, DecodeError> {
+    Ok(ucf_protocol::to_canonical_json(&M::decode(bytes)?))
+}
+
+#[cfg(not(feature = "json"))]
+fn render<M: Message + Default>(bytes: &[u8]) -> Result<String, DecodeError> {
+    Ok(format!("{:#?}", M::decode(bytes)?))
+}
+
+#[cfg(feature = "dynamic")]
+fn render_dynamic(schema: &str, bytes: &[u8]) -> Result<String, String> {
+    use ucf_protocol::dynamic::{decode_dynamic, DynamicError};
+
+    match decode_dynamic(schema, bytes) {
+        Ok(message) => Ok(format!("{message:#?}")),
+        Err(DynamicError::UnknownSchema(_)) => Err(format!("unknown schema {schema}")),
+        Err(DynamicError::Decode(err)) => Err(format!("bytes do not decode as {schema}: {err}")),
+    }
+}
+
+#[cfg(not(feature = "dynamic"))]
+fn render_dynamic(schema: &str, _bytes: &[u8]) -> Result<String, String> {
+    Err(format!("unknown schema {schema}"))
+}
+
+macro_rules! schemas {
+    ($($schema:literal => $message:path,)*) => {
+        /// Decode and render `bytes` as `schema`, or `None` for an unknown schema id.
+        fn render_schema(schema: &str, bytes: &[u8]) -> Option<Result<String, DecodeError>> {
+            match schema {
+                $($schema => Some(render::<$message>(bytes)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+ucf_protocol::for_each_schema!(schemas);
@@ -0,0 +1,326 @@
+//! Structural checks for biophysics asset payloads.
+//!
+//! Decoding accepts any compartment graph. [`validate_morphology`] checks each
+//! neuron's compartments form a tree rooted at the soma, so a simulator never
+//! sees a dangling parent or a cycle. [`validate_connectivity`] checks a
+//! connectivity graph only references neurons, compartments, and synapse
+//! parameters that the morphology and synapse sets define.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::ucf::v1::{
+    compartment, CompartmentKind, ConnectivityGraphPayload, MorphNeuron, MorphologySetPayload,
+    SynapseParamsSetPayload,
+};
+
+/// Errors returned by [`validate_morphology`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MorphError {
+    /// Two compartments of the neuron share `comp_id`.
+    DuplicateCompartment { neuron_id: u32, comp_id: u32 },
+    /// The compartment's parent is not a compartment of the same neuron.
+    UnknownParent { neuron_id: u32, comp_id: u32, parent: u32 },
+    /// The soma compartment has a parent.
+    SomaHasParent { neuron_id: u32, comp_id: u32 },
+    /// The neuron does not have exactly one compartment without a parent.
+    RootCount { neuron_id: u32, roots: usize },
+    /// The neuron's only parentless compartment is not a soma.
+    RootNotSoma { neuron_id: u32, comp_id: u32 },
+    /// Following parents from the compartment leads back to it.
+    Cycle { neuron_id: u32, comp_id: u32 },
+}
+
+impl fmt::Display for MorphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MorphError::DuplicateCompartment { neuron_id, comp_id } => {
+                write!(f, "neuron {neuron_id} has compartment {comp_id} more than once")
+            }
+            MorphError::UnknownParent { neuron_id, comp_id, parent } => {
+                write!(f, "neuron {neuron_id} compartment {comp_id} has unknown parent {parent}")
+            }
+            MorphError::SomaHasParent { neuron_id, comp_id } => {
+                write!(f, "neuron {neuron_id} soma compartment {comp_id} has a parent")
+            }
+            MorphError::RootCount { neuron_id, roots } => {
+                write!(f, "neuron {neuron_id} has {roots} parentless compartments, expected 1")
+            }
+            MorphError::RootNotSoma { neuron_id, comp_id } => {
+                write!(f, "neuron {neuron_id} root compartment {comp_id} is not a soma")
+            }
+            MorphError::Cycle { neuron_id, comp_id } => {
+                write!(f, "neuron {neuron_id} compartment {comp_id} is its own ancestor")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MorphError {}
+
+/// Errors returned by [`validate_connectivity`], naming the edge's index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnError {
+    /// The edge's `pre` neuron is not in the morphology.
+    UnknownPre { edge: usize, pre: u32 },
+    /// The edge's `post` neuron is not in the morphology.
+    UnknownPost { edge: usize, post: u32 },
+    /// The `post` neuron has no compartment `post_compartment`.
+    UnknownPostCompartment { edge: usize, post: u32, post_compartment: u32 },
+    /// The synapse set does not define the edge's `syn_param_id`.
+    UnknownSynParam { edge: usize, syn_param_id: u32 },
+}
+
+impl fmt::Display for ConnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnError::UnknownPre { edge, pre } => {
+                write!(f, "edge {edge} pre neuron {pre} is not in the morphology")
+            }
+            ConnError::UnknownPost { edge, post } => {
+                write!(f, "edge {edge} post neuron {post} is not in the morphology")
+            }
+            ConnError::UnknownPostCompartment { edge, post, post_compartment } => {
+                write!(f, "edge {edge} post neuron {post} has no compartment {post_compartment}")
+            }
+            ConnError::UnknownSynParam { edge, syn_param_id } => {
+                write!(f, "edge {edge} syn_param_id {syn_param_id} is not defined")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConnError {}
+
+/// Confirm every neuron's compartments form a tree.
+///
+/// Compartment ids must be unique within a neuron, every parent must be a
+/// compartment of the same neuron, the soma must have no parent, and no
+/// compartment may be its own ancestor. Exactly one compartment may lack a
+/// parent, and it must be a soma.
+pub fn validate_morphology(payload: &MorphologySetPayload) -> Result<(), MorphError> {
+    payload.neurons.iter().try_for_each(validate_neuron)
+}
+
+fn validate_neuron(neuron: &MorphNeuron) -> Result<(), MorphError> {
+    let neuron_id = neuron.neuron_id;
+    let mut parents = BTreeMap::new();
+    let mut roots = Vec::new();
+    for comp in &neuron.compartments {
+        let parent = comp.parent.as_ref().map(|compartment::Parent::ParentCompId(parent)| *parent);
+        if parents.insert(comp.comp_id, parent).is_some() {
+            return Err(MorphError::DuplicateCompartment { neuron_id, comp_id: comp.comp_id });
+        }
+        if comp.kind == CompartmentKind::Soma as i32 && parent.is_some() {
+            return Err(MorphError::SomaHasParent { neuron_id, comp_id: comp.comp_id });
+        }
+        if parent.is_none() {
+            roots.push(comp);
+        }
+    }
+    for (&comp_id, parent) in &parents {
+        if let Some(parent) = *parent {
+            if !parents.contains_key(&parent) {
+                return Err(MorphError::UnknownParent { neuron_id, comp_id, parent });
+            }
+        }
+    }
+
+    // Walk up from each compartment, stopping at a root or at a compartment
+    // already known to reach one.
+    let mut rooted = BTreeSet::new();
+    for &start in parents.keys() {
+        let mut path = BTreeSet::new();
+        let mut current = Some(start);
+        while let Some(comp_id) = current {
+            if rooted.contains(&comp_id) {
+                break;
+            }
+            if !path.insert(comp_id) {
+                return Err(MorphError::Cycle { neuron_id, comp_id });
+            }
+            current = parents[&comp_id];
+        }
+        rooted.extend(path);
+    }
+
+    match roots.as_slice() {
+        [root] if root.kind == CompartmentKind::Soma as i32 => Ok(()),
+        [root] => Err(MorphError::RootNotSoma { neuron_id, comp_id: root.comp_id }),
+        _ => Err(MorphError::RootCount { neuron_id, roots: roots.len() }),
+    }
+}
+
+/// Confirm every edge of `graph` resolves against `morph` and `syn`.
+///
+/// Both endpoints must be neurons of `morph`, `post_compartment` must be a
+/// compartment of the post neuron, and `syn_param_id` must be defined in
+/// `syn`. The first edge that fails is reported.
+pub fn validate_connectivity(
+    graph: &ConnectivityGraphPayload,
+    morph: &MorphologySetPayload,
+    syn: &SynapseParamsSetPayload,
+) -> Result<(), ConnError> {
+    let neurons: BTreeMap<u32, &MorphNeuron> =
+        morph.neurons.iter().map(|neuron| (neuron.neuron_id, neuron)).collect();
+    let syn_params: BTreeSet<u32> = syn.params.iter().map(|params| params.syn_param_id).collect();
+    for (edge, conn) in graph.edges.iter().enumerate() {
+        if !neurons.contains_key(&conn.pre) {
+            return Err(ConnError::UnknownPre { edge, pre: conn.pre });
+        }
+        let post =
+            neurons.get(&conn.post).ok_or(ConnError::UnknownPost { edge, post: conn.post })?;
+        if !post.compartments.iter().any(|comp| comp.comp_id == conn.post_compartment) {
+            return Err(ConnError::UnknownPostCompartment {
+                edge,
+                post: conn.post,
+                post_compartment: conn.post_compartment,
+            });
+        }
+        if !syn_params.contains(&conn.syn_param_id) {
+            return Err(ConnError::UnknownSynParam { edge, syn_param_id: conn.syn_param_id });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::decode_fixture;
+    use crate::ucf::v1::{Compartment, ConnEdge};
+
+    fn morphology_fixture() -> MorphologySetPayload {
+        decode_fixture("biophys_morphology_set_v1")
+    }
+
+    fn compartment(comp_id: u32, parent: Option<u32>, kind: CompartmentKind) -> Compartment {
+        Compartment {
+            comp_id,
+            parent: parent.map(compartment::Parent::ParentCompId),
+            kind: kind as i32,
+            ..Default::default()
+        }
+    }
+
+    fn with_compartments(compartments: Vec<Compartment>) -> MorphologySetPayload {
+        let mut payload = morphology_fixture();
+        payload.neurons[1].compartments = compartments;
+        payload
+    }
+
+    #[test]
+    fn fixture_morphology_is_a_tree() {
+        assert_eq!(validate_morphology(&morphology_fixture()), Ok(()));
+    }
+
+    #[test]
+    fn rejects_broken_compartment_graphs() {
+        use CompartmentKind::{Dendrite, Soma};
+
+        let duplicate =
+            with_compartments(vec![compartment(1, None, Soma), compartment(1, Some(1), Dendrite)]);
+        assert_eq!(
+            validate_morphology(&duplicate),
+            Err(MorphError::DuplicateCompartment { neuron_id: 2, comp_id: 1 })
+        );
+
+        let dangling =
+            with_compartments(vec![compartment(1, None, Soma), compartment(2, Some(9), Dendrite)]);
+        assert_eq!(
+            validate_morphology(&dangling),
+            Err(MorphError::UnknownParent { neuron_id: 2, comp_id: 2, parent: 9 })
+        );
+
+        let rooted_soma =
+            with_compartments(vec![compartment(1, Some(2), Soma), compartment(2, None, Dendrite)]);
+        assert_eq!(
+            validate_morphology(&rooted_soma),
+            Err(MorphError::SomaHasParent { neuron_id: 2, comp_id: 1 })
+        );
+
+        let cycle = with_compartments(vec![
+            compartment(1, None, Soma),
+            compartment(2, Some(4), Dendrite),
+            compartment(3, Some(2), Dendrite),
+            compartment(4, Some(3), Dendrite),
+        ]);
+        assert_eq!(
+            validate_morphology(&cycle),
+            Err(MorphError::Cycle { neuron_id: 2, comp_id: 2 })
+        );
+
+        let self_parent = with_compartments(vec![compartment(5, Some(5), Dendrite)]);
+        assert_eq!(
+            validate_morphology(&self_parent),
+            Err(MorphError::Cycle { neuron_id: 2, comp_id: 5 })
+        );
+
+        let forest = with_compartments(vec![
+            compartment(1, None, Dendrite),
+            compartment(2, None, Dendrite),
+            compartment(3, Some(1), Dendrite),
+        ]);
+        assert_eq!(
+            validate_morphology(&forest),
+            Err(MorphError::RootCount { neuron_id: 2, roots: 2 })
+        );
+
+        let two_somas =
+            with_compartments(vec![compartment(1, None, Soma), compartment(2, None, Soma)]);
+        assert_eq!(
+            validate_morphology(&two_somas),
+            Err(MorphError::RootCount { neuron_id: 2, roots: 2 })
+        );
+
+        let empty = with_compartments(Vec::new());
+        assert_eq!(
+            validate_morphology(&empty),
+            Err(MorphError::RootCount { neuron_id: 2, roots: 0 })
+        );
+
+        let no_soma = with_compartments(vec![
+            compartment(1, None, Dendrite),
+            compartment(2, Some(1), Dendrite),
+        ]);
+        assert_eq!(
+            validate_morphology(&no_soma),
+            Err(MorphError::RootNotSoma { neuron_id: 2, comp_id: 1 })
+        );
+    }
+
+    #[test]
+    fn connectivity_edges_must_resolve() {
+        let morph = morphology_fixture();
+        let syn: SynapseParamsSetPayload = decode_fixture("biophys_synapse_params_set_v1");
+        let mut graph: ConnectivityGraphPayload = decode_fixture("biophys_connectivity_graph_v1");
+
+        // The fixture's second edge targets neuron 3, which the morphology
+        // fixture does not define.
+        assert_eq!(
+            validate_connectivity(&graph, &morph, &syn),
+            Err(ConnError::UnknownPost { edge: 1, post: 3 })
+        );
+        graph.edges[1].post = 2;
+        assert_eq!(validate_connectivity(&graph, &morph, &syn), Ok(()));
+
+        let edge = graph.edges[0].clone();
+        let check = |conn: ConnEdge| {
+            let mut graph = graph.clone();
+            graph.edges.push(conn);
+            validate_connectivity(&graph, &morph, &syn)
+        };
+        assert_eq!(
+            check(ConnEdge { pre: 7, ..edge.clone() }),
+            Err(ConnError::UnknownPre { edge: 2, pre: 7 })
+        );
+        assert_eq!(
+            check(ConnEdge { post_compartment: 2, ..edge.clone() }),
+            Err(ConnError::UnknownPostCompartment { edge: 2, post: 2, post_compartment: 2 })
+        );
+        assert_eq!(
+            check(ConnEdge { syn_param_id: 99, ..edge }),
+            Err(ConnError::UnknownSynParam { edge: 2, syn_param_id: 99 })
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Sorting of repeated fields that carry set semantics.
+//!
+//! Protobuf preserves the order of repeated fields, so two messages holding the
+//! same set of reason codes in different orders encode, and digest, differently.
+//! [`Canonicalize`] puts those fields into a fixed order before encoding; use
+//! [`crate::canonical_bytes_sorted`] to do both in one step.
+//!
+//! Fields treated as sets, per message:
+//!
+//! - `ReasonCodes`: `codes`.
+//! - `CanonicalIntent`: `reason_codes` and `query.selectors`.
+//! - `PolicyDecision`: `reason_codes` and both `constraints` lists.
+//! - `SignalFrame`: `reason_codes` and the `top_reason_codes` of every stats block.
+//! - `ControlFrame`: `profile_reason_codes`.
+//! - `MicroMilestone`: `theme_tags` and `reason_codes`.
+//! - `MesoMilestone`: `micro_refs` and `theme_tags`.
+//! - `MacroMilestone`: `meso_refs` and each trait update's `justification_refs`.
+//! - `ReplayPlan`: `trigger_reason_codes` and `target_refs`.
+//! - `ToolRegistryContainer`: `tool_actions`.
+//!
+//! Strings sort bytewise, `Ref`s by `(uri, label)`, and `ToolActionProfile`s by
+//! `(tool_id, action_id)`. Every other repeated field is ordered by the
+//! caller, e.g. `ExperienceRecord.related_refs` and
+//! `MacroMilestone.trait_updates`, whose order is significant.
+
+use crate::ucf::v1::canonical_intent::Params;
+use crate::ucf::v1::{
+    CanonicalIntent, ConstraintsDelta, ControlFrame, MacroMilestone, MesoMilestone, MicroMilestone,
+    PolicyDecision, ReasonCodes, Ref, ReplayPlan, SignalFrame, ToolRegistryContainer,
+    TopReasonCodes,
+};
+
+/// A message whose set-semantic repeated fields can be put in canonical order.
+pub trait Canonicalize {
+    /// Sort every set-semantic repeated field, including those of sub-messages.
+    fn canonicalize(&mut self);
+}
+
+impl<T: Canonicalize> Canonicalize for Option<T> {
+    fn canonicalize(&mut self) {
+        if let Some(inner) = self {
+            inner.canonicalize();
+        }
+    }
+}
+
+impl Canonicalize for ReasonCodes {
+    fn canonicalize(&mut self) {
+        self.codes.sort_unstable();
+    }
+}
+
+impl Canonicalize for TopReasonCodes {
+    fn canonicalize(&mut self) {
+        self.reason_codes.canonicalize();
+    }
+}
+
+impl Canonicalize for CanonicalIntent {
+    fn canonicalize(&mut self) {
+        self.reason_codes.canonicalize();
+        if let Some(Params::Query(query)) = &mut self.params {
+            query.selectors.sort_unstable();
+        }
+    }
+}
+
+impl Canonicalize for ConstraintsDelta {
+    fn canonicalize(&mut self) {
+        self.constraints_added.sort_unstable();
+        self.constraints_removed.sort_unstable();
+    }
+}
+
+impl Canonicalize for PolicyDecision {
+    fn canonicalize(&mut self) {
+        self.reason_codes.canonicalize();
+        self.constraints.canonicalize();
+    }
+}
+
+impl Canonicalize for SignalFrame {
+    fn canonicalize(&mut self) {
+        self.reason_codes.canonicalize();
+        if let Some(stats) = &mut self.policy_stats {
+            stats.top_reason_codes.canonicalize();
+        }
+        if let Some(stats) = &mut self.dlp_stats {
+            stats.top_reason_codes.canonicalize();
+        }
+        if let Some(stats) = &mut self.exec_stats {
+            stats.top_reason_codes.canonicalize();
+        }
+        if let Some(stats) = &mut self.budget_stats {
+            stats.top_reason_codes.canonicalize();
+        }
+        if let Some(stats) = &mut self.receipt_stats {
+            stats.top_reason_codes.canonicalize();
+        }
+    }
+}
+
+impl Canonicalize for ControlFrame {
+    fn canonicalize(&mut self) {
+        self.profile_reason_codes.canonicalize();
+    }
+}
+
+impl Canonicalize for MicroMilestone {
+    fn canonicalize(&mut self) {
+        self.theme_tags.sort_unstable();
+        self.reason_codes.canonicalize();
+    }
+}
+
+impl Canonicalize for MesoMilestone {
+    fn canonicalize(&mut self) {
+        sort_refs(&mut self.micro_refs);
+        self.theme_tags.sort_unstable();
+    }
+}
+
+impl Canonicalize for MacroMilestone {
+    fn canonicalize(&mut self) {
+        sort_refs(&mut self.meso_refs);
+        for update in &mut self.trait_updates {
+            sort_refs(&mut update.justification_refs);
+        }
+    }
+}
+
+impl Canonicalize for ReplayPlan {
+    fn canonicalize(&mut self) {
+        self.trigger_reason_codes.canonicalize();
+        sort_refs(&mut self.target_refs);
+    }
+}
+
+impl Canonicalize for ToolRegistryContainer {
+    fn canonicalize(&mut self) {
+        self.tool_actions
+            .sort_by(|a, b| (&a.tool_id, &a.action_id).cmp(&(&b.tool_id, &b.action_id)));
+    }
+}
+
+fn sort_refs(refs: &mut [Ref]) {
+    refs.sort_unstable_by(|a, b| (&a.uri, &a.label).cmp(&(&b.uri, &b.label)));
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+
+    use super::*;
+    use crate::canonical_bytes_sorted;
+    use crate::fixtures::{read_fixture, FixtureFormat};
+    use crate::testing::fixture_dir;
+
+    fn assert_fixture_sorted<M: Message + Default + Clone + Canonicalize>(
+        name: &str,
+        shuffle: impl Fn(&mut M),
+    ) {
+        let (bytes, _) =
+            read_fixture(&fixture_dir(), name, FixtureFormat::Hex).expect("fixture reads");
+        let mut message = M::decode(bytes.as_slice()).expect("fixture decodes");
+        assert_eq!(canonical_bytes_sorted(&message), bytes, "{name} is already canonical");
+
+        shuffle(&mut message);
+        assert_ne!(crate::canonical_bytes(&message), bytes, "{name} shuffle changes the bytes");
+        assert_eq!(canonical_bytes_sorted(&message), bytes, "{name} sorts back to the fixture");
+    }
+
+    #[test]
+    fn sorting_restores_the_golden_fixtures() {
+        assert_fixture_sorted("canonical_intent_query", |intent: &mut CanonicalIntent| {
+            intent.reason_codes.as_mut().unwrap().codes.reverse();
+            if let Some(Params::Query(query)) = &mut intent.params {
+                query.selectors.reverse();
+            }
+        });
+        assert_fixture_sorted("policy_decision", |decision: &mut PolicyDecision| {
+            decision.reason_codes.as_mut().unwrap().codes.reverse();
+            decision.constraints.as_mut().unwrap().constraints_added.reverse();
+        });
+        assert_fixture_sorted("signal_frame_short_window", |frame: &mut SignalFrame| {
+            frame.reason_codes.as_mut().unwrap().codes.rotate_left(1);
+            let top = frame.exec_stats.as_mut().unwrap().top_reason_codes.as_mut().unwrap();
+            top.reason_codes.as_mut().unwrap().codes.reverse();
+        });
+        assert_fixture_sorted("control_frame_m1_overlays_on", |frame: &mut ControlFrame| {
+            frame.profile_reason_codes.as_mut().unwrap().codes.reverse();
+        });
+        assert_fixture_sorted("micro_milestone_sealed", |micro: &mut MicroMilestone| {
+            micro.theme_tags.reverse();
+        });
+        assert_fixture_sorted("meso_milestone_stable", |meso: &mut MesoMilestone| {
+            meso.micro_refs.reverse();
+        });
+        assert_fixture_sorted("macro_milestone_finalized", |macro_: &mut MacroMilestone| {
+            macro_.trait_updates[1].justification_refs.reverse();
+        });
+        assert_fixture_sorted("replay_plan_high_fidelity", |plan: &mut ReplayPlan| {
+            plan.target_refs.reverse();
+        });
+    }
+}
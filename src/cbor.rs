@@ -0,0 +1,123 @@
+//! Deterministic CBOR encoding of UCF messages for consumers that cannot link
+//! protobuf.
+//!
+//! [`to_canonical_cbor`] encodes a message through its serde derives as
+//! deterministic CBOR (RFC 8949 §4.2.1): definite lengths, shortest integer
+//! and float forms, and map keys sorted bytewise by their encoding. Field
+//! names, enum names, and base64url `bytes` fields follow the same serde
+//! representation as [`crate::json`]. [`from_cbor`] decodes it back.
+//!
+//! **CBOR is a transport encoding only and is never digested.** Every digest
+//! and signature in UCF is computed over [`crate::canonical_bytes`], the
+//! canonical protobuf encoding. A message received as CBOR must be decoded
+//! with [`from_cbor`] and re-encoded as protobuf before its digest is checked;
+//! hashing the CBOR bytes would produce values no other UCF peer recognises.
+
+use std::fmt;
+
+use ciborium::value::Value;
+use prost::Message;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Errors returned by [`from_cbor`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CborError {
+    /// The bytes are not CBOR, or do not match the message's serde shape.
+    Decode(String),
+}
+
+impl fmt::Display for CborError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CborError::Decode(reason) => write!(f, "CBOR decode failed: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for CborError {}
+
+/// Encode `message` as deterministic CBOR.
+pub fn to_canonical_cbor<M: Message + Serialize>(message: &M) -> Vec<u8> {
+    let value = Value::serialized(message).expect("generated messages serialize to CBOR");
+    encode(&sort_maps(value))
+}
+
+/// Decode a message from CBOR produced by [`to_canonical_cbor`].
+///
+/// Map key order is not checked, so any CBOR encoding of the message decodes.
+pub fn from_cbor<M: Message + DeserializeOwned>(bytes: &[u8]) -> Result<M, CborError> {
+    ciborium::de::from_reader(bytes).map_err(|err| CborError::Decode(err.to_string()))
+}
+
+fn encode(value: &Value) -> Vec<u8> {
+    let mut out = Vec::new();
+    ciborium::ser::into_writer(value, &mut out).expect("writing CBOR to a Vec cannot fail");
+    out
+}
+
+/// Sort every map, at any depth, by the encoded bytes of its keys.
+fn sort_maps(value: Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_maps).collect()),
+        Value::Map(entries) => {
+            let mut entries: Vec<(Vec<u8>, Value, Value)> = entries
+                .into_iter()
+                .map(|(key, item)| {
+                    let key = sort_maps(key);
+                    (encode(&key), key, sort_maps(item))
+                })
+                .collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Map(entries.into_iter().map(|(_, key, item)| (key, item)).collect())
+        }
+        Value::Tag(tag, inner) => Value::Tag(tag, Box::new(sort_maps(*inner))),
+        scalar => scalar,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canonical_bytes;
+    use crate::fixtures::{read_fixture, FixtureFormat};
+    use crate::testing::fixture_dir;
+    use crate::ucf::v1::{ControlFrame, ReasonCodes};
+
+    #[test]
+    fn control_frame_round_trips_without_changing_its_digest() {
+        let (bytes, _) =
+            read_fixture(&fixture_dir(), "control_frame_m1_overlays_on", FixtureFormat::Hex)
+                .expect("fixture reads");
+        let frame = ControlFrame::decode(bytes.as_slice()).expect("fixture decodes");
+
+        let cbor = to_canonical_cbor(&frame);
+        assert_eq!(to_canonical_cbor(&frame), cbor, "encoding is deterministic");
+        let decoded: ControlFrame = from_cbor(&cbor).expect("CBOR decodes");
+        assert_eq!(decoded, frame);
+        assert_eq!(canonical_bytes(&decoded), bytes, "protobuf bytes are unchanged");
+    }
+
+    #[test]
+    fn map_keys_are_sorted_by_encoding() {
+        let frame = ControlFrame { control_frame_id: "cf-1".to_string(), ..Default::default() };
+        let value: Value =
+            ciborium::de::from_reader(to_canonical_cbor(&frame).as_slice()).expect("CBOR decodes");
+        let keys: Vec<Vec<u8>> = value
+            .as_map()
+            .expect("messages encode as maps")
+            .iter()
+            .map(|(key, _)| encode(key))
+            .collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert!(matches!(from_cbor::<ReasonCodes>(&[0xff]), Err(CborError::Decode(_))));
+        let codes = ReasonCodes { codes: vec!["b".to_string(), "a".to_string()] };
+        assert_eq!(from_cbor::<ReasonCodes>(&to_canonical_cbor(&codes)), Ok(codes));
+    }
+}
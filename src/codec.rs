@@ -0,0 +1,153 @@
+//! On-disk text formats for hex- and base64url-encoded fixtures.
+//!
+//! Fixtures under `testvectors/` store lowercase hex followed by a single `\n`.
+//! Older generators omitted the trailing newline and files checked out on
+//! Windows may carry CRLF line endings, so readers normalise surrounding
+//! whitespace before decoding. `.b64` fixtures store the same bytes as
+//! unpadded base64url (RFC 4648 §5) followed by `\n`, and are read the same
+//! way.
+
+use std::fmt;
+
+/// Errors returned by [`normalize_hex_fixture`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum HexError {
+    /// The trimmed fixture body is not valid hex.
+    Invalid(hex::FromHexError),
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HexError::Invalid(err) => write!(f, "invalid hex fixture: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for HexError {}
+
+impl From<hex::FromHexError> for HexError {
+    fn from(err: hex::FromHexError) -> Self {
+        HexError::Invalid(err)
+    }
+}
+
+/// Errors returned by [`normalize_b64_fixture`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Base64Error {
+    /// The trimmed fixture body is not unpadded base64url.
+    Invalid,
+}
+
+impl fmt::Display for Base64Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Base64Error::Invalid => write!(f, "invalid base64url fixture"),
+        }
+    }
+}
+
+impl std::error::Error for Base64Error {}
+
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Unpadded base64url (RFC 4648 §5) encoding of `bytes`.
+pub fn base64url(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, b)| n | u32::from(*b) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            out.push(BASE64URL[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+/// Decode unpadded base64url, or `None` if `text` is not in that form.
+pub fn decode_base64url(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 4 == 1 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    for chunk in text.as_bytes().chunks(4) {
+        let mut n = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            let sextet = BASE64URL.iter().position(|b| b == c)? as u32;
+            n |= sextet << (18 - 6 * i);
+        }
+        let bytes = n.to_be_bytes();
+        out.extend_from_slice(&bytes[1..chunk.len()]);
+        // Bits beyond the last whole byte must be zero for a canonical encoding.
+        if chunk.len() < 4 && n & (0xff_ffff >> (8 * (chunk.len() - 1))) != 0 {
+            return None;
+        }
+    }
+    Some(out)
+}
+
+/// Decode a hex fixture body, ignoring surrounding whitespace and CRLF endings.
+pub fn normalize_hex_fixture(raw: &str) -> Result<Vec<u8>, HexError> {
+    Ok(hex::decode(raw.trim())?)
+}
+
+/// Render `bytes` in the canonical fixture format: lowercase hex and a trailing `\n`.
+pub fn hex_fixture_body(bytes: &[u8]) -> String {
+    let mut body = hex::encode(bytes);
+    body.push('\n');
+    body
+}
+
+/// Decode a base64url fixture body, ignoring surrounding whitespace and CRLF endings.
+pub fn normalize_b64_fixture(raw: &str) -> Result<Vec<u8>, Base64Error> {
+    decode_base64url(raw.trim()).ok_or(Base64Error::Invalid)
+}
+
+/// Render `bytes` in the base64url fixture format: unpadded base64url and a trailing `\n`.
+pub fn b64_fixture_body(bytes: &[u8]) -> String {
+    let mut body = base64url(bytes);
+    body.push('\n');
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_and_current_fixture_formats_decode_identically() {
+        let bytes = vec![0x0A, 0x03, 0xDE, 0xAD, 0xBE];
+        let current = hex_fixture_body(&bytes);
+        assert_eq!(current, "0a03deadbe\n");
+
+        let legacy = "0a03deadbe";
+        let crlf = "0a03deadbe\r\n";
+        let padded = "  0a03deadbe \n\n";
+        for raw in [current.as_str(), legacy, crlf, padded] {
+            assert_eq!(normalize_hex_fixture(raw), Ok(bytes.clone()), "{raw:?}");
+        }
+
+        assert!(matches!(normalize_hex_fixture("0a0"), Err(HexError::Invalid(_))));
+    }
+
+    #[test]
+    fn base64url_round_trips() {
+        for len in 0..8 {
+            let bytes: Vec<u8> = (0..len).map(|i| 0xF0 ^ i).collect();
+            assert_eq!(decode_base64url(&base64url(&bytes)), Some(bytes));
+        }
+        assert_eq!(decode_base64url("Zg=="), None, "padding is not accepted");
+        assert_eq!(decode_base64url("Zh"), None, "trailing bits must be zero");
+        assert_eq!(decode_base64url("Z"), None);
+    }
+
+    #[test]
+    fn b64_fixture_body_round_trips() {
+        let bytes = vec![0x0A, 0x03, 0xDE, 0xAD, 0xBE];
+        let body = b64_fixture_body(&bytes);
+        assert_eq!(body, "CgPerb4\n");
+        for raw in [body.as_str(), "CgPerb4", "CgPerb4\r\n"] {
+            assert_eq!(normalize_b64_fixture(raw), Ok(bytes.clone()), "{raw:?}");
+        }
+        assert_eq!(normalize_b64_fixture("0a03deadbe"), Err(Base64Error::Invalid));
+    }
+}
@@ -0,0 +1,99 @@
+//! Applying and composing [`ConstraintsDelta`] changes.
+//!
+//! A delta is applied as `(base - constraints_removed) + constraints_added`.
+//! When a constraint is both added and removed by the same delta, the addition
+//! wins: constraints only ever narrow what is allowed, so an ambiguous delta
+//! resolves to the stricter set. Results are sorted bytewise and free of
+//! duplicates, the canonical form from `docs/determinism_rules.md`.
+
+use std::collections::BTreeSet;
+
+use crate::ucf::v1::ConstraintsDelta;
+
+/// `base` with `delta` applied, sorted and de-duplicated.
+pub fn apply_constraints(base: &[String], delta: &ConstraintsDelta) -> Vec<String> {
+    let removed: BTreeSet<&String> = delta.constraints_removed.iter().collect();
+    let kept = base.iter().filter(|constraint| !removed.contains(constraint));
+    let constraints: BTreeSet<&String> = kept.chain(&delta.constraints_added).collect();
+    constraints.into_iter().cloned().collect()
+}
+
+/// The delta equivalent to applying `a` and then `b`.
+///
+/// For every `base`, `apply_constraints(&apply_constraints(base, a), b)`
+/// equals `apply_constraints(base, &compose_deltas(a, b))`. The result is
+/// canonical: both lists are sorted and de-duplicated, and no constraint
+/// appears in both.
+pub fn compose_deltas(a: &ConstraintsDelta, b: &ConstraintsDelta) -> ConstraintsDelta {
+    let b_removed: BTreeSet<&String> = b.constraints_removed.iter().collect();
+    let added: BTreeSet<&String> = a
+        .constraints_added
+        .iter()
+        .filter(|constraint| !b_removed.contains(constraint))
+        .chain(&b.constraints_added)
+        .collect();
+    let removed: BTreeSet<&String> = a
+        .constraints_removed
+        .iter()
+        .chain(&b.constraints_removed)
+        .filter(|constraint| !added.contains(constraint))
+        .collect();
+    ConstraintsDelta {
+        constraints_added: added.into_iter().cloned().collect(),
+        constraints_removed: removed.into_iter().cloned().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reason_codes::sorted_codes;
+
+    fn delta(added: &[&str], removed: &[&str]) -> ConstraintsDelta {
+        ConstraintsDelta {
+            constraints_added: added.iter().map(|c| c.to_string()).collect(),
+            constraints_removed: removed.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn apply_removes_then_adds() {
+        let base = sorted_codes(&["no-network", "read-only", "scope:tmp"]);
+        let applied = apply_constraints(
+            &base,
+            &delta(&["max-tokens:100", "read-only"], &["scope:tmp", "unknown"]),
+        );
+        assert_eq!(applied, vec!["max-tokens:100", "no-network", "read-only"]);
+
+        let unsorted = vec!["b".to_string(), "a".to_string(), "b".to_string()];
+        assert_eq!(apply_constraints(&unsorted, &ConstraintsDelta::default()), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn addition_wins_within_a_delta() {
+        let base = sorted_codes(&["read-only"]);
+        let both = delta(&["read-only", "no-network"], &["read-only", "no-network"]);
+        assert_eq!(apply_constraints(&base, &both), vec!["no-network", "read-only"]);
+    }
+
+    #[test]
+    fn compose_matches_sequential_application() {
+        let a = delta(&["x", "y"], &["p", "q"]);
+        let b = delta(&["p", "z"], &["y", "r"]);
+        let composed = compose_deltas(&a, &b);
+        assert_eq!(composed, delta(&["p", "x", "z"], &["q", "r", "y"]));
+
+        for base in [&[][..], &["p", "q", "r"], &["x", "y", "z"], &["a", "q", "y"]] {
+            let base = sorted_codes(base);
+            assert_eq!(
+                apply_constraints(&apply_constraints(&base, &a), &b),
+                apply_constraints(&base, &composed),
+                "{base:?}"
+            );
+        }
+        assert_eq!(
+            compose_deltas(&ConstraintsDelta::default(), &b),
+            delta(&["p", "z"], &["r", "y"])
+        );
+    }
+}
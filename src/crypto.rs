@@ -0,0 +1,236 @@
+//! Signature verification for the wire-level [`Signature`] message.
+//!
+//! `Signature.algorithm` is a free-form string, so verification first checks it
+//! against an [`AllowedAlgorithms`] set. Anything outside the set is rejected
+//! with [`SignatureError::UnsupportedAlgorithm`] rather than being skipped.
+//! [`verify_signature`] instead pins the expected signer key, for fields such
+//! as `MicrocircuitConfigEvidence.attestation_sig` whose signer is known.
+//!
+//! [`Signature::ed25519`] and the `TryFrom<&Signature>` conversion move between
+//! the wire message and `ed25519-dalek` types, checking key and signature
+//! lengths on the way in.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use ed25519_dalek::{Verifier, VerifyingKey};
+
+use crate::ucf::v1::Signature;
+
+/// Algorithm name for Ed25519 signatures.
+pub const ED25519: &str = "ed25519";
+
+/// Signature algorithms a verifier is willing to check.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct AllowedAlgorithms(BTreeSet<String>);
+
+impl AllowedAlgorithms {
+    /// An empty set that rejects every algorithm.
+    pub fn none() -> Self {
+        Self(BTreeSet::new())
+    }
+
+    pub fn allow(mut self, algorithm: &str) -> Self {
+        self.0.insert(algorithm.to_string());
+        self
+    }
+
+    pub fn contains(&self, algorithm: &str) -> bool {
+        self.0.contains(algorithm)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl Default for AllowedAlgorithms {
+    fn default() -> Self {
+        Self::none().allow(ED25519)
+    }
+}
+
+/// Errors returned by [`Signature::verify`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignatureError {
+    /// `algorithm` is not in the allowed set.
+    UnsupportedAlgorithm(String),
+    /// `signer` is not a valid public key for the algorithm.
+    MalformedSigner,
+    /// `signer` is not the public key the caller expected.
+    SignerMismatch,
+    /// `signature` has the wrong length for the algorithm.
+    MalformedSignature,
+    /// The signature does not verify over the message.
+    Invalid,
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureError::UnsupportedAlgorithm(algorithm) => {
+                write!(f, "unsupported signature algorithm {algorithm:?}")
+            }
+            SignatureError::MalformedSigner => write!(f, "malformed signer public key"),
+            SignatureError::SignerMismatch => write!(f, "signer is not the expected public key"),
+            SignatureError::MalformedSignature => write!(f, "malformed signature bytes"),
+            SignatureError::Invalid => write!(f, "signature verification failed"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+impl Signature {
+    /// An Ed25519 `Signature` message carrying `sig` made by `signer`.
+    pub fn ed25519(signer: &VerifyingKey, sig: &ed25519_dalek::Signature) -> Signature {
+        Signature {
+            algorithm: ED25519.to_string(),
+            signer: signer.to_bytes().to_vec(),
+            signature: sig.to_bytes().to_vec(),
+        }
+    }
+
+    /// Verify this signature over `message`, accepting only `allowed` algorithms.
+    pub fn verify(
+        &self,
+        message: &[u8],
+        allowed: &AllowedAlgorithms,
+    ) -> Result<(), SignatureError> {
+        if !allowed.contains(&self.algorithm) {
+            return Err(SignatureError::UnsupportedAlgorithm(self.algorithm.clone()));
+        }
+
+        self.verify_algorithm(message)
+    }
+
+    /// Dispatch on `algorithm`; each supported algorithm gets an arm here.
+    fn verify_algorithm(&self, message: &[u8]) -> Result<(), SignatureError> {
+        match self.algorithm.as_str() {
+            ED25519 => self.verify_ed25519(message),
+            other => Err(SignatureError::UnsupportedAlgorithm(other.to_string())),
+        }
+    }
+
+    fn verify_ed25519(&self, message: &[u8]) -> Result<(), SignatureError> {
+        let (key, signature) = <(VerifyingKey, ed25519_dalek::Signature)>::try_from(self)?;
+        key.verify(message, &signature).map_err(|_| SignatureError::Invalid)
+    }
+}
+
+/// The Ed25519 key and signature in `sig`.
+///
+/// Fails unless `algorithm` is `ed25519`, `signer` is a valid 32-byte public
+/// key, and `signature` is 64 bytes. The signature itself is not verified.
+impl TryFrom<&Signature> for (VerifyingKey, ed25519_dalek::Signature) {
+    type Error = SignatureError;
+
+    fn try_from(sig: &Signature) -> Result<Self, Self::Error> {
+        if sig.algorithm != ED25519 {
+            return Err(SignatureError::UnsupportedAlgorithm(sig.algorithm.clone()));
+        }
+        let signer: [u8; 32] =
+            sig.signer.as_slice().try_into().map_err(|_| SignatureError::MalformedSigner)?;
+        let key = VerifyingKey::from_bytes(&signer).map_err(|_| SignatureError::MalformedSigner)?;
+        let signature = ed25519_dalek::Signature::from_slice(&sig.signature)
+            .map_err(|_| SignatureError::MalformedSignature)?;
+        Ok((key, signature))
+    }
+}
+
+/// Verify `sig` over `message` as a signature by the public key `pk`.
+///
+/// `sig.signer` must equal `pk`, so a signature made by some other key is
+/// rejected with [`SignatureError::SignerMismatch`] even if it is valid. Every
+/// algorithm this crate implements is accepted; use [`Signature::verify`] to
+/// restrict them to an [`AllowedAlgorithms`] set.
+pub fn verify_signature(sig: &Signature, message: &[u8], pk: &[u8]) -> Result<(), SignatureError> {
+    if sig.signer != pk {
+        return Err(SignatureError::SignerMismatch);
+    }
+    sig.verify_algorithm(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn signed(message: &[u8]) -> Signature {
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        Signature::ed25519(&key.verifying_key(), &key.sign(message))
+    }
+
+    #[test]
+    fn verify_checks_algorithm_then_signature() {
+        let allowed = AllowedAlgorithms::default();
+        let signature = signed(b"payload");
+
+        assert_eq!(signature.verify(b"payload", &allowed), Ok(()));
+        assert_eq!(signature.verify(b"tampered", &allowed), Err(SignatureError::Invalid));
+        assert_eq!(
+            signature.verify(b"payload", &AllowedAlgorithms::none()),
+            Err(SignatureError::UnsupportedAlgorithm(ED25519.to_string()))
+        );
+
+        let rsa = Signature { algorithm: "rsa".to_string(), ..signature.clone() };
+        assert_eq!(
+            rsa.verify(b"payload", &allowed.clone().allow("rsa")),
+            Err(SignatureError::UnsupportedAlgorithm("rsa".to_string()))
+        );
+
+        let truncated = Signature { signature: signature.signature[..63].to_vec(), ..signature };
+        assert_eq!(truncated.verify(b"payload", &allowed), Err(SignatureError::MalformedSignature));
+    }
+
+    #[test]
+    fn verify_signature_pins_the_signer() {
+        let signature = signed(b"payload");
+        let pk = SigningKey::from_bytes(&[9u8; 32]).verifying_key().to_bytes();
+        assert_eq!(verify_signature(&signature, b"payload", &pk), Ok(()));
+        assert_eq!(verify_signature(&signature, b"tampered", &pk), Err(SignatureError::Invalid));
+
+        let other = SigningKey::from_bytes(&[8u8; 32]).verifying_key().to_bytes();
+        assert_eq!(
+            verify_signature(&signature, b"payload", &other),
+            Err(SignatureError::SignerMismatch)
+        );
+
+        let unknown = Signature { algorithm: "ml-dsa-65".to_string(), ..signature };
+        assert_eq!(
+            verify_signature(&unknown, b"payload", &pk),
+            Err(SignatureError::UnsupportedAlgorithm("ml-dsa-65".to_string()))
+        );
+    }
+
+    #[test]
+    fn converts_to_and_from_dalek_types() {
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let sig = key.sign(b"payload");
+        let signature = Signature::ed25519(&key.verifying_key(), &sig);
+        assert_eq!(signature.signer.len(), 32);
+        assert_eq!(signature.signature.len(), 64);
+        assert_eq!(
+            <(VerifyingKey, ed25519_dalek::Signature)>::try_from(&signature),
+            Ok((key.verifying_key(), sig))
+        );
+
+        let short_signer = Signature { signer: vec![0xAA; 31], ..signature.clone() };
+        assert_eq!(
+            <(VerifyingKey, ed25519_dalek::Signature)>::try_from(&short_signer),
+            Err(SignatureError::MalformedSigner)
+        );
+        let long_signature = Signature { signature: vec![0xEE; 65], ..signature.clone() };
+        assert_eq!(
+            <(VerifyingKey, ed25519_dalek::Signature)>::try_from(&long_signature),
+            Err(SignatureError::MalformedSignature)
+        );
+        let rsa = Signature { algorithm: "rsa".to_string(), ..signature };
+        assert_eq!(
+            <(VerifyingKey, ed25519_dalek::Signature)>::try_from(&rsa),
+            Err(SignatureError::UnsupportedAlgorithm("rsa".to_string()))
+        );
+    }
+}
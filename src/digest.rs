@@ -0,0 +1,254 @@
+//! Per-message canonical digests and short, human-readable fingerprints.
+//!
+//! [`CanonicalDigest`] binds each top-level message type to the domain and
+//! schema id its digest is taken under, so `message.canonical_digest()` cannot
+//! pair a message with the wrong tag. Prefer it to calling [`digest32`] by hand.
+//!
+//! A fingerprint is the first 10 bytes (80 bits) of a digest rendered as
+//! lowercase hex in dash-separated groups of four, e.g. `1a2b-3c4d-5e6f-7a8b-9cad`.
+//! Fingerprints are for display and human cross-reference only; they are **not**
+//! authoritative. Always compare full 32-byte digests when verifying.
+//!
+//! Messages that carry a digest of their own content, such as
+//! `SepEvent.event_digest`, implement the internal [`SelfDigested`] trait, and
+//! [`compute_self_digest`] recomputes that digest with the self-referential
+//! field excluded. [`ContentDigest::content_digest`] exposes it. It is a
+//! different value from [`CanonicalDigest::canonical_digest`], which covers the
+//! whole message as encoded, stored digest included.
+
+use prost::Message;
+
+use crate::ucf::v1::{
+    ApprovalArtifactPackage, ApprovalDecision, AssetManifest, CanonicalIntent,
+    ChannelParamsSetPayload, CompletenessReport, ConnectivityGraphPayload, ConsistencyFeedback,
+    ControlFrame, Digest32, ExperienceRecord, MacroMilestone, MesoMilestone, MicroMilestone,
+    MicrocircuitConfigEvidence, MorphologySetPayload, PolicyDecision, PolicyQuery, PvgsReceipt,
+    ReasonCodes, ReplayPlan, ReplayRunEvidence, SepEvent, SessionSeal, SignalFrame,
+    SynapseParamsSetPayload, ToolOnboardingEvent, ToolRegistryContainer, UcfEnvelope,
+};
+use crate::{canonical_bytes, digest32, Domain};
+
+const FINGERPRINT_BYTES: usize = 10;
+const SCHEMA_VERSION: &str = "1";
+
+/// A top-level message digested under a fixed domain and schema id.
+///
+/// The digest covers the whole message as encoded, matching the golden
+/// fixtures. For messages that also store a digest of their own content this is
+/// not that stored value; use [`ContentDigest::content_digest`] to check it.
+/// `AssetDigest` has no impl: its domain depends on the asset kind it describes.
+pub trait CanonicalDigest: Message + Sized {
+    /// Domain tag the digest is taken under.
+    const DOMAIN: Domain;
+    /// Schema id the digest is taken under, e.g. `ucf.v1.ControlFrame`.
+    const SCHEMA: &'static str;
+
+    /// [`digest32`] of the canonical bytes under [`Self::DOMAIN`] and [`Self::SCHEMA`].
+    fn canonical_digest(&self) -> [u8; 32] {
+        digest32(Self::DOMAIN, Self::SCHEMA, SCHEMA_VERSION, &canonical_bytes(self))
+    }
+}
+
+macro_rules! canonical_digest {
+    ($($message:ident => $domain:expr),* $(,)?) => {
+        $(
+            impl CanonicalDigest for $message {
+                const DOMAIN: Domain = $domain;
+                const SCHEMA: &'static str = concat!("ucf.v1.", stringify!($message));
+            }
+        )*
+    };
+}
+
+canonical_digest! {
+    ApprovalArtifactPackage => Domain::Core,
+    ApprovalDecision => Domain::Core,
+    AssetManifest => Domain::AssetManifest,
+    CanonicalIntent => Domain::Core,
+    ChannelParamsSetPayload => Domain::AssetChannelParams,
+    CompletenessReport => Domain::Core,
+    ConnectivityGraphPayload => Domain::AssetConnectivity,
+    ConsistencyFeedback => Domain::Core,
+    ControlFrame => Domain::Core,
+    ExperienceRecord => Domain::Core,
+    MacroMilestone => Domain::Core,
+    MesoMilestone => Domain::Core,
+    MicroMilestone => Domain::Core,
+    MicrocircuitConfigEvidence => Domain::McConfig,
+    MorphologySetPayload => Domain::AssetMorphology,
+    PolicyDecision => Domain::Core,
+    PolicyQuery => Domain::Core,
+    ReasonCodes => Domain::Core,
+    ReplayPlan => Domain::Core,
+    ReplayRunEvidence => Domain::Core,
+    SepEvent => Domain::Core,
+    SessionSeal => Domain::Core,
+    SignalFrame => Domain::Core,
+    SynapseParamsSetPayload => Domain::AssetSynapseParams,
+    ToolOnboardingEvent => Domain::Core,
+    ToolRegistryContainer => Domain::Core,
+    UcfEnvelope => Domain::Core,
+}
+
+// The receipt schema id keeps the proto spelling rather than prost's `PvgsReceipt`.
+impl CanonicalDigest for PvgsReceipt {
+    const DOMAIN: Domain = Domain::Core;
+    const SCHEMA: &'static str = "ucf.v1.PVGSReceipt";
+}
+
+/// A message that stores a digest of its own content, e.g.
+/// `SepEvent.event_digest` or `SessionSeal.seal_digest`.
+pub trait ContentDigest {
+    /// The digest the message's own digest field should hold: taken with that
+    /// field and any attestation over it cleared, and set-semantic fields sorted.
+    fn content_digest(&self) -> [u8; 32];
+}
+
+impl<M: SelfDigested + Message + Clone> ContentDigest for M {
+    fn content_digest(&self) -> [u8; 32] {
+        compute_self_digest(self)
+    }
+}
+
+/// A message whose content digest is stored in one of its own fields.
+pub(crate) trait SelfDigested {
+    /// Schema id the digest is taken under, e.g. `ucf.v1.SepEvent`.
+    const SCHEMA_ID: &'static str;
+
+    /// Domain tag the digest is taken under.
+    fn domain() -> &'static str;
+
+    /// Clear the stored digest, returning it.
+    fn take_digest(&mut self) -> Option<Digest32>;
+
+    /// Clear fields that attest to the digest and so cannot be inputs to it.
+    fn clear_attestation(&mut self) {}
+
+    /// Sort set-semantic repeated fields before encoding.
+    fn sort_sets(&mut self) {}
+}
+
+/// Content digest of `message`: [`digest32`] over the canonical bytes of a
+/// copy with its self-digest and attestation cleared and its sets sorted.
+pub(crate) fn compute_self_digest<M: SelfDigested + Message + Clone>(message: &M) -> [u8; 32] {
+    let mut content = message.clone();
+    content.take_digest();
+    content.clear_attestation();
+    content.sort_sets();
+    digest32(M::domain(), M::SCHEMA_ID, SCHEMA_VERSION, &canonical_bytes(&content))
+}
+
+/// Render the display fingerprint of `digest`.
+pub fn fingerprint(digest: &[u8; 32]) -> String {
+    digest[..FINGERPRINT_BYTES].chunks(2).map(hex::encode).collect::<Vec<_>>().join("-")
+}
+
+/// Fingerprint the canonical digest of `message`.
+pub fn fingerprint_of<M: Message>(
+    domain: &str,
+    schema_id: &str,
+    schema_version: &str,
+    message: &M,
+) -> String {
+    fingerprint(&digest32(domain, schema_id, schema_version, &canonical_bytes(message)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::read_fixture;
+    use crate::testing::{fixture_dir, FIXTURES};
+    use crate::ucf::v1::Signature;
+    use crate::DigestAlgo;
+
+    /// Assert `M`'s constants match fixture `name` and reproduce its digest.
+    fn assert_matches_fixture<M: CanonicalDigest + Default>(name: &str) {
+        let spec = FIXTURES.iter().find(|spec| spec.name == name).expect("fixture is registered");
+        assert_eq!(M::DOMAIN, spec.domain, "{name}");
+        assert_eq!(M::SCHEMA, spec.schema, "{name}");
+        assert_eq!(spec.algo, DigestAlgo::Blake3, "{name}");
+
+        let (bytes, digest) =
+            read_fixture(&fixture_dir(), name, spec.format).expect("fixture reads");
+        let message = M::decode(bytes.as_slice()).expect("fixture decodes");
+        assert_eq!(message.canonical_digest(), digest, "{name}");
+    }
+
+    #[test]
+    fn canonical_digest_matches_fixture_table() {
+        assert_matches_fixture::<ApprovalArtifactPackage>("approval_artifact_package");
+        assert_matches_fixture::<ApprovalDecision>("approval_decision");
+        assert_matches_fixture::<AssetManifest>("asset_manifest_v1");
+        assert_matches_fixture::<CanonicalIntent>("canonical_intent_query");
+        assert_matches_fixture::<ChannelParamsSetPayload>("biophys_channel_params_set_v1");
+        assert_matches_fixture::<CompletenessReport>("completeness_report");
+        assert_matches_fixture::<ConnectivityGraphPayload>("biophys_connectivity_graph_v1");
+        assert_matches_fixture::<ConsistencyFeedback>("consistency_feedback_low_flags");
+        assert_matches_fixture::<ControlFrame>("control_frame_m1_overlays_on");
+        assert_matches_fixture::<ExperienceRecord>("experience_rt_output");
+        assert_matches_fixture::<MacroMilestone>("macro_milestone_finalized");
+        assert_matches_fixture::<MesoMilestone>("meso_milestone_stable");
+        assert_matches_fixture::<MicroMilestone>("micro_milestone_sealed");
+        assert_matches_fixture::<MicrocircuitConfigEvidence>("mc_cfg_hpa");
+        assert_matches_fixture::<MorphologySetPayload>("biophys_morphology_set_v1");
+        assert_matches_fixture::<PolicyDecision>("policy_decision");
+        assert_matches_fixture::<PvgsReceipt>("pvgs_receipt");
+        assert_matches_fixture::<ReasonCodes>("reason_codes_basic");
+        assert_matches_fixture::<ReplayPlan>("replay_plan_high_fidelity");
+        assert_matches_fixture::<ReplayRunEvidence>("replay_run_evidence");
+        assert_matches_fixture::<SepEvent>("sep_event_chain_1");
+        assert_matches_fixture::<SessionSeal>("session_seal");
+        assert_matches_fixture::<SignalFrame>("signal_frame_short_window");
+        assert_matches_fixture::<SynapseParamsSetPayload>("biophys_synapse_params_set_v1");
+        assert_matches_fixture::<ToolOnboardingEvent>("tool_onboarding_event");
+        assert_matches_fixture::<ToolRegistryContainer>("tool_registry_container");
+        assert_matches_fixture::<UcfEnvelope>("ucf_envelope_policy_decision");
+
+        assert_eq!(PolicyQuery::SCHEMA, "ucf.v1.PolicyQuery");
+        assert_eq!(PolicyQuery::DOMAIN, Domain::Core);
+    }
+
+    #[test]
+    fn content_digest_is_the_stored_self_digest() {
+        let event: SepEvent = crate::testing::decode_fixture("sep_event_chain_1");
+        let stored = event.event_digest.clone().expect("fixture is digested").value;
+        assert_eq!(event.content_digest().to_vec(), stored);
+        assert_ne!(event.canonical_digest(), event.content_digest());
+    }
+
+    #[test]
+    fn fingerprint_groups_leading_bytes() {
+        let mut digest = [0xFF; 32];
+        digest[..10].copy_from_slice(&[0x1A, 0x2B, 0x3C, 0x4D, 0x5E, 0x6F, 0x7A, 0x8B, 0x9C, 0xAD]);
+        assert_eq!(fingerprint(&digest), "1a2b-3c4d-5e6f-7a8b-9cad");
+
+        let codes =
+            ReasonCodes { codes: vec!["coverage".to_string(), "deterministic".to_string()] };
+        let full = digest32("ucf-core", "ucf.v1.ReasonCodes", "1", &canonical_bytes(&codes));
+        assert_eq!(
+            fingerprint_of("ucf-core", "ucf.v1.ReasonCodes", "1", &codes),
+            fingerprint(&full)
+        );
+    }
+
+    #[test]
+    fn self_digest_excludes_digest_and_attestation() {
+        let event = SepEvent { event_id: "evt-1".to_string(), ..Default::default() };
+        let sealed = SepEvent {
+            event_digest: Some(Digest32 { value: vec![0xAB; 32] }),
+            attestation_sig: Some(Signature::default()),
+            ..event.clone()
+        };
+        assert_eq!(compute_self_digest(&sealed), compute_self_digest(&event));
+
+        let mut micro = MicroMilestone {
+            theme_tags: vec!["b".to_string(), "a".to_string()],
+            micro_digest: Some(Digest32 { value: vec![0xCD; 32] }),
+            ..Default::default()
+        };
+        let digest = compute_self_digest(&micro);
+        micro.theme_tags.reverse();
+        assert_eq!(micro.take_digest(), Some(Digest32 { value: vec![0xCD; 32] }));
+        assert_eq!(compute_self_digest(&micro), digest);
+    }
+}
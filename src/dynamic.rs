@@ -0,0 +1,49 @@
+//! Decoding messages by schema id when the concrete type is not known at
+//! compile time.
+//!
+//! [`decode_dynamic`] looks a schema id such as `ucf.v1.PolicyDecision` up in
+//! [`crate::FILE_DESCRIPTOR_SET`], the descriptors embedded at build time. The
+//! resulting [`DynamicMessage`] exposes fields by name; prefer the generated
+//! types whenever the schema is known.
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use prost::DecodeError;
+pub use prost_reflect::{DescriptorPool, DynamicMessage};
+
+/// Errors returned by [`decode_dynamic`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DynamicError {
+    /// No message in the embedded descriptors has this full name.
+    UnknownSchema(String),
+    /// The bytes are not a valid encoding of the message.
+    Decode(DecodeError),
+}
+
+impl fmt::Display for DynamicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DynamicError::UnknownSchema(schema_id) => write!(f, "unknown schema {schema_id}"),
+            DynamicError::Decode(err) => write!(f, "decode failed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for DynamicError {}
+
+/// Descriptors of every message and enum generated by this crate.
+pub fn descriptor_pool() -> &'static DescriptorPool {
+    static POOL: OnceLock<DescriptorPool> = OnceLock::new();
+    POOL.get_or_init(|| {
+        DescriptorPool::decode(crate::FILE_DESCRIPTOR_SET).expect("embedded descriptor set decodes")
+    })
+}
+
+/// Decode `bytes` as the message whose full name is `schema_id`.
+pub fn decode_dynamic(schema_id: &str, bytes: &[u8]) -> Result<DynamicMessage, DynamicError> {
+    let descriptor = descriptor_pool()
+        .get_message_by_name(schema_id)
+        .ok_or_else(|| DynamicError::UnknownSchema(schema_id.to_string()))?;
+    DynamicMessage::decode(descriptor, bytes).map_err(DynamicError::Decode)
+}
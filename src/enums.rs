@@ -0,0 +1,213 @@
+//! Checks that enum-typed fields hold a defined variant.
+//!
+//! prost decodes proto enums as plain `i32`, so a decoded message can carry a
+//! value no variant corresponds to. [`ValidateEnums`] walks a message's enum
+//! fields, including those of sub-messages, and reports the first undefined
+//! value. `*_UNSPECIFIED` is a defined variant and passes; whether it is
+//! acceptable is up to the caller.
+//!
+//! Fields checked, per message:
+//!
+//! - `PolicyDecision`: `decision`.
+//! - `ControlFrame`: `active_profile` and every `threshold_modifiers` field.
+//! - `ExperienceRecord`: `record_type`.
+//!
+//! prost's generated getters (e.g. `CanonicalIntent::channel`) quietly map an
+//! undefined value to the default variant. The `*_enum` accessors defined here
+//! return an [`EnumError`] instead, and the `with_*` setters take the enum so
+//! callers never cast with `as i32`. They cover `CanonicalIntent`,
+//! `PolicyDecision`, `ControlFrame`, and `ThresholdModifiers`.
+
+use std::fmt;
+
+use crate::ucf::v1::{
+    ApprovalMode, CanonicalIntent, Channel, ControlFrame, CooldownClass, DataClass, DecisionForm,
+    ExperienceRecord, LevelClass, PolicyDecision, ProfileState, RecordType, RiskLevel,
+    ThresholdModifiers,
+};
+
+/// An enum field holds a value that is not a defined variant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnumError {
+    /// Path of the field within the validated message.
+    pub field: &'static str,
+    pub value: i32,
+}
+
+impl fmt::Display for EnumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} holds undefined enum value {}", self.field, self.value)
+    }
+}
+
+impl std::error::Error for EnumError {}
+
+/// A message whose enum fields can be checked against their defined variants.
+pub trait ValidateEnums {
+    /// Confirm every enum field, including those of sub-messages, is defined.
+    fn validate_enums(&self) -> Result<(), EnumError>;
+}
+
+/// Confirm every enum field of `message` holds a defined variant.
+pub fn validate_enums<M: ValidateEnums>(message: &M) -> Result<(), EnumError> {
+    message.validate_enums()
+}
+
+fn typed<E: TryFrom<i32>>(field: &'static str, value: i32) -> Result<E, EnumError> {
+    E::try_from(value).map_err(|_| EnumError { field, value })
+}
+
+fn check<E: TryFrom<i32>>(field: &'static str, value: i32) -> Result<(), EnumError> {
+    typed::<E>(field, value).map(|_| ())
+}
+
+/// Defines a checked `*_enum` accessor and a chaining `with_*` setter for
+/// each listed enum field of a generated message.
+macro_rules! typed_enum_fields {
+    ($message:ty { $($field:ident: $enum:ty => $getter:ident, $with:ident;)* }) => {
+        impl $message {
+            $(
+                #[doc = concat!("`", stringify!($field), "` as a [`", stringify!($enum), "`].")]
+                pub fn $getter(&self) -> Result<$enum, EnumError> {
+                    typed(stringify!($field), self.$field)
+                }
+
+                #[doc = concat!("Set `", stringify!($field), "` to `value`.")]
+                pub fn $with(mut self, value: $enum) -> Self {
+                    self.$field = value as i32;
+                    self
+                }
+            )*
+        }
+    };
+}
+
+typed_enum_fields!(CanonicalIntent {
+    channel: Channel => channel_enum, with_channel;
+    risk_level: RiskLevel => risk_level_enum, with_risk_level;
+    data_class: DataClass => data_class_enum, with_data_class;
+});
+
+typed_enum_fields!(PolicyDecision {
+    decision: DecisionForm => decision_enum, with_decision;
+});
+
+typed_enum_fields!(ControlFrame {
+    active_profile: ProfileState => active_profile_enum, with_active_profile;
+});
+
+typed_enum_fields!(ThresholdModifiers {
+    approval_mode: ApprovalMode => approval_mode_enum, with_approval_mode;
+    novelty_tightening: LevelClass => novelty_tightening_enum, with_novelty_tightening;
+    chain_tightening: LevelClass => chain_tightening_enum, with_chain_tightening;
+    export_strictness_tightening: LevelClass =>
+        export_strictness_tightening_enum, with_export_strictness_tightening;
+    cooldown_class: CooldownClass => cooldown_class_enum, with_cooldown_class;
+});
+
+impl<T: ValidateEnums> ValidateEnums for Option<T> {
+    fn validate_enums(&self) -> Result<(), EnumError> {
+        self.as_ref().map_or(Ok(()), ValidateEnums::validate_enums)
+    }
+}
+
+impl ValidateEnums for PolicyDecision {
+    fn validate_enums(&self) -> Result<(), EnumError> {
+        check::<DecisionForm>("decision", self.decision)
+    }
+}
+
+impl ValidateEnums for ThresholdModifiers {
+    fn validate_enums(&self) -> Result<(), EnumError> {
+        check::<ApprovalMode>("threshold_modifiers.approval_mode", self.approval_mode)?;
+        check::<LevelClass>("threshold_modifiers.novelty_tightening", self.novelty_tightening)?;
+        check::<LevelClass>("threshold_modifiers.chain_tightening", self.chain_tightening)?;
+        check::<LevelClass>(
+            "threshold_modifiers.export_strictness_tightening",
+            self.export_strictness_tightening,
+        )?;
+        check::<CooldownClass>("threshold_modifiers.cooldown_class", self.cooldown_class)
+    }
+}
+
+impl ValidateEnums for ControlFrame {
+    fn validate_enums(&self) -> Result<(), EnumError> {
+        check::<ProfileState>("active_profile", self.active_profile)?;
+        self.threshold_modifiers.validate_enums()
+    }
+}
+
+impl ValidateEnums for ExperienceRecord {
+    fn validate_enums(&self) -> Result<(), EnumError> {
+        check::<RecordType>("record_type", self.record_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::decode_fixture;
+
+    #[test]
+    fn fixtures_hold_defined_variants() {
+        assert_eq!(validate_enums(&decode_fixture::<PolicyDecision>("policy_decision")), Ok(()));
+        assert_eq!(
+            validate_enums(&decode_fixture::<ControlFrame>("control_frame_m1_overlays_on")),
+            Ok(())
+        );
+        assert_eq!(
+            validate_enums(&decode_fixture::<ExperienceRecord>("experience_rt_action_exec")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn undefined_values_name_their_field() {
+        let decision = PolicyDecision { decision: 4, ..Default::default() };
+        assert_eq!(validate_enums(&decision), Err(EnumError { field: "decision", value: 4 }));
+
+        let mut frame = decode_fixture::<ControlFrame>("control_frame_m1_overlays_on");
+        frame.threshold_modifiers.as_mut().unwrap().cooldown_class = -1;
+        assert_eq!(
+            validate_enums(&frame),
+            Err(EnumError { field: "threshold_modifiers.cooldown_class", value: -1 })
+        );
+        frame.active_profile = 99;
+        assert_eq!(validate_enums(&frame), Err(EnumError { field: "active_profile", value: 99 }));
+
+        let record = ExperienceRecord { record_type: 10, ..Default::default() };
+        assert_eq!(validate_enums(&record), Err(EnumError { field: "record_type", value: 10 }));
+        assert_eq!(validate_enums(&ExperienceRecord::default()), Ok(()));
+    }
+
+    #[test]
+    fn typed_accessors_round_trip_and_reject_undefined_values() {
+        let intent = CanonicalIntent::default()
+            .with_channel(Channel::Realtime)
+            .with_risk_level(RiskLevel::High)
+            .with_data_class(DataClass::Restricted);
+        assert_eq!(intent.channel, Channel::Realtime as i32);
+        assert_eq!(intent.channel_enum(), Ok(Channel::Realtime));
+        assert_eq!(intent.risk_level_enum(), Ok(RiskLevel::High));
+        assert_eq!(intent.data_class_enum(), Ok(DataClass::Restricted));
+
+        let undefined = CanonicalIntent { channel: 7, ..intent };
+        assert_eq!(undefined.channel(), Channel::Unspecified, "prost getter falls back");
+        assert_eq!(undefined.channel_enum(), Err(EnumError { field: "channel", value: 7 }));
+
+        let decision = PolicyDecision::default().with_decision(DecisionForm::Deny);
+        assert_eq!(decision.decision_enum(), Ok(DecisionForm::Deny));
+
+        let modifiers = ThresholdModifiers::default().with_cooldown_class(CooldownClass::Longer);
+        let frame = ControlFrame {
+            threshold_modifiers: Some(modifiers),
+            ..ControlFrame::default().with_active_profile(ProfileState::M2)
+        };
+        assert_eq!(frame.active_profile_enum(), Ok(ProfileState::M2));
+        assert_eq!(
+            frame.threshold_modifiers.as_ref().unwrap().cooldown_class_enum(),
+            Ok(CooldownClass::Longer)
+        );
+        assert_eq!(validate_enums(&frame), Ok(()));
+    }
+}
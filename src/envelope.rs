@@ -0,0 +1,301 @@
+//! Sealing and verification of [`UcfEnvelope`].
+//!
+//! An envelope's `payload_digest` is the canonical digest of its payload
+//! message: `digest32("ucf-core", schema_id, "1", payload)` where `schema_id` is
+//! selected by `msg_type`. The envelope signature covers the canonical bytes of
+//! the envelope with `signature` cleared, so it binds the epoch, nonce, message
+//! type, payload, and payload digest together.
+
+use std::fmt;
+
+use ed25519_dalek::{Signer, SigningKey};
+use prost::Message;
+use subtle::ConstantTimeEq;
+
+use crate::crypto::{AllowedAlgorithms, SignatureError};
+use crate::ucf::v1::{Digest32, MsgType, Signature, UcfEnvelope};
+use crate::{canonical_bytes, digest32, Domain};
+
+const PAYLOAD_SCHEMA_VERSION: &str = "1";
+
+/// Errors returned when sealing or verifying an envelope.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EnvelopeError {
+    /// `msg_type` is unspecified or not a known [`MsgType`].
+    UnsupportedMsgType(i32),
+    /// The envelope has no `payload_digest`.
+    PayloadDigestMissing,
+    /// `payload_digest` does not match the digest of `payload`.
+    PayloadDigestMismatch { expected: [u8; 32], found: Vec<u8> },
+    /// The envelope has no `signature`.
+    SignatureMissing,
+    /// The envelope signature failed verification.
+    Signature(SignatureError),
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvelopeError::UnsupportedMsgType(msg_type) => {
+                write!(f, "unsupported envelope msg_type {msg_type}")
+            }
+            EnvelopeError::PayloadDigestMissing => write!(f, "envelope has no payload_digest"),
+            EnvelopeError::PayloadDigestMismatch { expected, found } => write!(
+                f,
+                "payload_digest {} does not match payload digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+            EnvelopeError::SignatureMissing => write!(f, "envelope has no signature"),
+            EnvelopeError::Signature(err) => write!(f, "envelope signature: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for EnvelopeError {}
+
+impl From<SignatureError> for EnvelopeError {
+    fn from(err: SignatureError) -> Self {
+        EnvelopeError::Signature(err)
+    }
+}
+
+/// Schema id of the payload message carried under each `MsgType`.
+///
+/// This is the one authoritative mapping; [`MsgType::Unspecified`] carries no
+/// payload schema and is absent.
+pub const PAYLOAD_SCHEMAS: &[(MsgType, &str)] = &[
+    (MsgType::CanonicalIntent, "ucf.v1.CanonicalIntent"),
+    (MsgType::PolicyQuery, "ucf.v1.PolicyQuery"),
+    (MsgType::PolicyDecision, "ucf.v1.PolicyDecision"),
+    (MsgType::PvgsReceipt, "ucf.v1.PVGSReceipt"),
+];
+
+/// Schema id of the payload message carried under `msg_type`, or `None` for
+/// [`MsgType::Unspecified`].
+pub fn payload_schema_id(msg_type: MsgType) -> Option<&'static str> {
+    PAYLOAD_SCHEMAS.iter().find(|(candidate, _)| *candidate == msg_type).map(|(_, schema)| *schema)
+}
+
+/// The `MsgType` whose payload has schema id `schema_id`; the inverse of
+/// [`payload_schema_id`].
+pub fn msg_type_for_schema(schema_id: &str) -> Option<MsgType> {
+    PAYLOAD_SCHEMAS.iter().find(|(_, schema)| *schema == schema_id).map(|(msg_type, _)| *msg_type)
+}
+
+fn payload_schema(msg_type: i32) -> Result<&'static str, EnvelopeError> {
+    MsgType::try_from(msg_type)
+        .ok()
+        .and_then(payload_schema_id)
+        .ok_or(EnvelopeError::UnsupportedMsgType(msg_type))
+}
+
+/// Digest `payload` under the schema selected by `msg_type`.
+pub fn compute_payload_digest(msg_type: i32, payload: &[u8]) -> Result<[u8; 32], EnvelopeError> {
+    let schema = payload_schema(msg_type)?;
+    Ok(digest32(Domain::Core, schema, PAYLOAD_SCHEMA_VERSION, payload))
+}
+
+/// Bytes covered by the envelope signature: the envelope with `signature` cleared.
+pub fn signing_bytes(envelope: &UcfEnvelope) -> Vec<u8> {
+    canonical_bytes(&UcfEnvelope { signature: None, ..envelope.clone() })
+}
+
+/// Set `payload_digest` from the payload and sign the envelope with `signing_key`.
+pub fn seal(envelope: &mut UcfEnvelope, signing_key: &SigningKey) -> Result<(), EnvelopeError> {
+    let digest = compute_payload_digest(envelope.msg_type, &envelope.payload)?;
+    envelope.payload_digest = Some(Digest32 { value: digest.to_vec() });
+
+    let signature = signing_key.sign(&signing_bytes(envelope));
+    envelope.signature = Some(Signature::ed25519(&signing_key.verifying_key(), &signature));
+    Ok(())
+}
+
+/// Builds a sealed [`UcfEnvelope`] from a payload message, so `payload`,
+/// `payload_digest`, and `signature` are always set consistently.
+#[derive(Clone, Debug)]
+pub struct EnvelopeBuilder {
+    envelope: UcfEnvelope,
+}
+
+impl EnvelopeBuilder {
+    pub fn new(epoch_id: impl Into<String>, msg_type: MsgType) -> Self {
+        Self {
+            envelope: UcfEnvelope {
+                epoch_id: epoch_id.into(),
+                msg_type: msg_type as i32,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Set the payload to the canonical bytes of `message`.
+    pub fn payload_message<M: Message>(mut self, message: &M) -> Self {
+        self.envelope.payload = canonical_bytes(message);
+        self
+    }
+
+    pub fn nonce(mut self, nonce: impl Into<Vec<u8>>) -> Self {
+        self.envelope.nonce = nonce.into();
+        self
+    }
+
+    /// Compute `payload_digest` and sign the envelope, as [`seal`] does.
+    pub fn sign(self, signing_key: &SigningKey) -> Result<UcfEnvelope, EnvelopeError> {
+        let mut envelope = self.envelope;
+        seal(&mut envelope, signing_key)?;
+        Ok(envelope)
+    }
+}
+
+/// Confirm `payload_digest` is the digest of `payload` for the envelope's `msg_type`.
+pub fn verify_payload_digest(envelope: &UcfEnvelope) -> Result<(), EnvelopeError> {
+    verify_envelope_payload(envelope, Domain::Core, PAYLOAD_SCHEMA_VERSION)
+}
+
+/// Confirm `payload_digest` is `digest32(domain, schema_id, schema_version, payload)`,
+/// where `schema_id` is [`payload_schema_id`] of the envelope's `msg_type`.
+///
+/// The digests are compared in constant time.
+pub fn verify_envelope_payload(
+    envelope: &UcfEnvelope,
+    domain: impl AsRef<str>,
+    schema_version: &str,
+) -> Result<(), EnvelopeError> {
+    let schema = payload_schema(envelope.msg_type)?;
+    let expected = digest32(domain, schema, schema_version, &envelope.payload);
+    let found = envelope.payload_digest.as_ref().ok_or(EnvelopeError::PayloadDigestMissing)?;
+    if !bool::from(found.value.as_slice().ct_eq(&expected)) {
+        return Err(EnvelopeError::PayloadDigestMismatch { expected, found: found.value.clone() });
+    }
+    Ok(())
+}
+
+/// Verify the payload digest and the envelope signature.
+///
+/// This checks that the signature is valid for its own `signer`; callers decide
+/// separately whether that signer is trusted.
+pub fn verify(envelope: &UcfEnvelope, allowed: &AllowedAlgorithms) -> Result<(), EnvelopeError> {
+    verify_payload_digest(envelope)?;
+    let signature = envelope.signature.as_ref().ok_or(EnvelopeError::SignatureMissing)?;
+    signature.verify(&signing_bytes(envelope), allowed)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ucf::v1::{DecisionForm, PolicyDecision, ReasonCodes};
+
+    #[test]
+    fn sealed_envelope_verifies_and_detects_tampering() {
+        let key = SigningKey::from_bytes(&[4u8; 32]);
+        let mut envelope = UcfEnvelope {
+            epoch_id: "epoch-1".to_string(),
+            nonce: vec![0x01; 16],
+            msg_type: MsgType::PolicyDecision as i32,
+            payload: vec![0x08, 0x01],
+            ..Default::default()
+        };
+        seal(&mut envelope, &key).expect("policy decision seals");
+        let allowed = AllowedAlgorithms::default();
+        assert_eq!(verify(&envelope, &allowed), Ok(()));
+
+        let mut tampered = envelope.clone();
+        tampered.payload = vec![0x08, 0x02];
+        assert!(matches!(
+            verify(&tampered, &allowed),
+            Err(EnvelopeError::PayloadDigestMismatch { .. })
+        ));
+
+        let mut rebound = envelope.clone();
+        rebound.epoch_id = "epoch-2".to_string();
+        assert_eq!(
+            verify(&rebound, &allowed),
+            Err(EnvelopeError::Signature(SignatureError::Invalid))
+        );
+
+        let mut unspecified = envelope;
+        unspecified.msg_type = MsgType::Unspecified as i32;
+        assert_eq!(seal(&mut unspecified, &key), Err(EnvelopeError::UnsupportedMsgType(0)));
+    }
+
+    #[test]
+    fn payload_digest_is_checked_under_the_given_domain_and_version() {
+        let key = SigningKey::from_bytes(&[4u8; 32]);
+        let mut envelope = UcfEnvelope {
+            epoch_id: "epoch-1".to_string(),
+            msg_type: MsgType::CanonicalIntent as i32,
+            payload: vec![0x0a, 0x01, 0x61],
+            ..Default::default()
+        };
+        seal(&mut envelope, &key).expect("canonical intent seals");
+        assert_eq!(payload_schema_id(MsgType::CanonicalIntent), Some("ucf.v1.CanonicalIntent"));
+        assert_eq!(payload_schema_id(MsgType::Unspecified), None);
+
+        assert_eq!(verify_envelope_payload(&envelope, "ucf-core", "1"), Ok(()));
+        assert!(matches!(
+            verify_envelope_payload(&envelope, "UCF:OTHER", "1"),
+            Err(EnvelopeError::PayloadDigestMismatch { .. })
+        ));
+        assert!(matches!(
+            verify_envelope_payload(&envelope, "ucf-core", "2"),
+            Err(EnvelopeError::PayloadDigestMismatch { .. })
+        ));
+
+        let mut truncated = envelope.clone();
+        truncated.payload_digest.as_mut().unwrap().value.truncate(16);
+        assert!(matches!(
+            verify_envelope_payload(&truncated, "ucf-core", "1"),
+            Err(EnvelopeError::PayloadDigestMismatch { .. })
+        ));
+
+        let mut undigested = envelope;
+        undigested.payload_digest = None;
+        assert_eq!(
+            verify_envelope_payload(&undigested, "ucf-core", "1"),
+            Err(EnvelopeError::PayloadDigestMissing)
+        );
+    }
+
+    #[test]
+    fn builder_seals_a_canonical_payload() {
+        let key = SigningKey::from_bytes(&[4u8; 32]);
+        let decision = PolicyDecision {
+            decision: DecisionForm::Allow as i32,
+            reason_codes: Some(ReasonCodes { codes: vec!["baseline".to_string()] }),
+            constraints: None,
+        };
+        let envelope = EnvelopeBuilder::new("epoch-1", MsgType::PolicyDecision)
+            .payload_message(&decision)
+            .nonce([0x01; 16])
+            .sign(&key)
+            .expect("policy decision seals");
+
+        assert_eq!(envelope.payload, canonical_bytes(&decision));
+        assert_eq!(envelope.nonce, vec![0x01; 16]);
+        assert_eq!(verify(&envelope, &AllowedAlgorithms::default()), Ok(()));
+        assert_eq!(signing_bytes(&envelope), {
+            let mut unsigned = envelope.clone();
+            unsigned.signature = None;
+            canonical_bytes(&unsigned)
+        });
+
+        assert_eq!(
+            EnvelopeBuilder::new("epoch-1", MsgType::Unspecified).sign(&key),
+            Err(EnvelopeError::UnsupportedMsgType(0))
+        );
+    }
+
+    #[test]
+    fn every_msg_type_maps_to_a_schema_and_back() {
+        let msg_types: Vec<MsgType> =
+            (0..).map_while(|value| MsgType::try_from(value).ok()).collect();
+        assert_eq!(msg_types.len(), PAYLOAD_SCHEMAS.len() + 1, "only Unspecified lacks a schema");
+        for msg_type in msg_types.into_iter().filter(|msg_type| *msg_type != MsgType::Unspecified) {
+            let schema = payload_schema_id(msg_type).expect("payload msg types have a schema");
+            assert_eq!(msg_type_for_schema(schema), Some(msg_type), "{schema}");
+        }
+        assert_eq!(msg_type_for_schema("ucf.v1.PvgsReceipt"), None, "proto spelling only");
+    }
+}
@@ -0,0 +1,1091 @@
+//! Helpers for experience records and the ranges milestones commit to.
+//!
+//! `finalization_header.record_digest` is the PVGS record digest,
+//! [`record_digest_from_components`], over the header's `prev_record_digest`,
+//! the PVGS commit id, and [`compute_verified_fields_digest`] of the record and
+//! the frames its refs resolve to. [`verify_finalization`] checks a header
+//! against those frames.
+
+use std::fmt;
+
+use prost::Message;
+use subtle::ConstantTimeEq;
+
+use crate::ucf::v1::{
+    CoreFrame, Digest32, ExperienceRange, ExperienceRecord, FinalizationHeader, GovernanceFrame,
+    MetabolicFrame, RecordType, Ref,
+};
+use crate::{canonical_bytes, digest32, Domain};
+
+/// Domain tag prepended to every [`record_digest_from_components`] hash.
+pub const RECORD_DIGEST_DOMAIN: &[u8] = Domain::PvgsRecordDigest.as_bytes();
+
+/// Version of the [`record_digest_from_components`] layout, hashed after the domain tag.
+///
+/// Version 1 hashed the components with no domain tag or version.
+pub const RECORD_DIGEST_VERSION: u32 = 2;
+
+/// BLAKE3 of `RECORD_DIGEST_DOMAIN || RECORD_DIGEST_VERSION (u32 LE) ||
+/// verified_fields_digest || prev_record_digest || commit_id`.
+///
+/// This is `finalization_header.record_digest`, and the digest PVGS receipts
+/// evaluate their VRF over.
+pub fn record_digest_from_components(
+    verified_fields_digest: [u8; 32],
+    prev_record_digest: [u8; 32],
+    commit_id: &[u8],
+) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(RECORD_DIGEST_DOMAIN);
+    hasher.update(&RECORD_DIGEST_VERSION.to_le_bytes());
+    hasher.update(&verified_fields_digest);
+    hasher.update(&prev_record_digest);
+    hasher.update(commit_id);
+    *hasher.finalize().as_bytes()
+}
+
+const RECORD_SCHEMA_ID: &str = "ucf.v1.ExperienceRecord";
+const RECORD_SCHEMA_VERSION: &str = "1";
+
+/// A charter or policy version as carried in a `*_version_digest` string field.
+///
+/// Deployments fill these fields either with a label such as `charter:v3` or
+/// with a lowercase, unprefixed hex digest. Strict verifiers can require the
+/// [`VersionRef::Digest`] form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VersionRef {
+    Label(String),
+    Digest([u8; 32]),
+}
+
+impl VersionRef {
+    /// Interpret `value` as a digest if it is exactly 64 lowercase hex characters.
+    pub fn parse(value: &str) -> Self {
+        let is_lower_hex = value.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+        let mut digest = [0u8; 32];
+        if value.len() == 64 && is_lower_hex && hex::decode_to_slice(value, &mut digest).is_ok() {
+            VersionRef::Digest(digest)
+        } else {
+            VersionRef::Label(value.to_string())
+        }
+    }
+
+    pub fn digest(&self) -> Option<&[u8; 32]> {
+        match self {
+            VersionRef::Digest(digest) => Some(digest),
+            VersionRef::Label(_) => None,
+        }
+    }
+}
+
+impl FinalizationHeader {
+    /// Typed view of `charter_version_digest`.
+    pub fn charter_version(&self) -> VersionRef {
+        VersionRef::parse(&self.charter_version_digest)
+    }
+
+    /// Typed view of `policy_version_digest`.
+    pub fn policy_version(&self) -> VersionRef {
+        VersionRef::parse(&self.policy_version_digest)
+    }
+}
+
+/// Errors returned when an [`ExperienceRange`] does not match its records.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RangeError {
+    /// The range does not carry a `head_record_digest`.
+    HeadDigestMissing,
+    /// No record has `experience_id == end_experience_id`.
+    HeadRecordMissing { end_experience_id: u64 },
+    /// The head record has no `finalization_header.record_digest`.
+    RecordDigestMissing { experience_id: u64 },
+    /// The head record digest differs from the range's `head_record_digest`.
+    HeadDigestMismatch { experience_id: u64, expected: Vec<u8>, found: Vec<u8> },
+    /// `end_experience_id` is smaller than `start_experience_id`.
+    Inverted { start_experience_id: u64, end_experience_id: u64 },
+    /// Experience id arithmetic would overflow `u64`.
+    Overflow { experience_id: u64 },
+    /// The next range does not start right after the previous one ends.
+    NotAdjacent { prev_end_experience_id: u64, next_start_experience_id: u64 },
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::HeadDigestMissing => write!(f, "range has no head_record_digest"),
+            RangeError::HeadRecordMissing { end_experience_id } => {
+                write!(f, "no record with experience_id {end_experience_id} in range")
+            }
+            RangeError::RecordDigestMissing { experience_id } => {
+                write!(f, "record {experience_id} has no record_digest")
+            }
+            RangeError::HeadDigestMismatch { experience_id, expected, found } => write!(
+                f,
+                "record {experience_id} digest {} does not match head_record_digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+            RangeError::Inverted { start_experience_id, end_experience_id } => {
+                write!(f, "range end {end_experience_id} precedes start {start_experience_id}")
+            }
+            RangeError::Overflow { experience_id } => {
+                write!(f, "experience id arithmetic overflows at {experience_id}")
+            }
+            RangeError::NotAdjacent { prev_end_experience_id, next_start_experience_id } => {
+                write!(
+                    f,
+                    "range starting at {next_start_experience_id} does not follow range ending at \
+                     {prev_end_experience_id}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RangeError {}
+
+/// Confirm `range.head_record_digest` is the digest of the range's last record.
+///
+/// The head record is the one whose `experience_id` equals
+/// `range.end_experience_id`; its `finalization_header.record_digest` must equal
+/// the range's `head_record_digest`.
+pub fn verify_range_head(
+    range: &ExperienceRange,
+    records: &[ExperienceRecord],
+) -> Result<(), RangeError> {
+    let expected = range.head_record_digest.as_ref().ok_or(RangeError::HeadDigestMissing)?;
+    let end_experience_id = range.end_experience_id;
+
+    let header = records
+        .iter()
+        .filter_map(|record| record.finalization_header.as_ref())
+        .find(|header| header.experience_id == end_experience_id)
+        .ok_or(RangeError::HeadRecordMissing { end_experience_id })?;
+    let found = header
+        .record_digest
+        .as_ref()
+        .ok_or(RangeError::RecordDigestMissing { experience_id: end_experience_id })?;
+
+    if found.value != expected.value {
+        return Err(RangeError::HeadDigestMismatch {
+            experience_id: end_experience_id,
+            expected: expected.value.clone(),
+            found: found.value.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Number of experience ids covered by `range`, inclusive of both ends.
+///
+/// The full `0..=u64::MAX` span has `u64::MAX + 1` ids and is reported as
+/// [`RangeError::Overflow`] rather than wrapping to zero.
+pub fn range_len(range: &ExperienceRange) -> Result<u64, RangeError> {
+    let start_experience_id = range.start_experience_id;
+    let end_experience_id = range.end_experience_id;
+    let span = end_experience_id
+        .checked_sub(start_experience_id)
+        .ok_or(RangeError::Inverted { start_experience_id, end_experience_id })?;
+    span.checked_add(1).ok_or(RangeError::Overflow { experience_id: end_experience_id })
+}
+
+/// The experience id that follows `experience_id`.
+pub fn next_experience_id(experience_id: u64) -> Result<u64, RangeError> {
+    experience_id.checked_add(1).ok_or(RangeError::Overflow { experience_id })
+}
+
+/// Confirm `next` starts immediately after `prev` ends, with no gap or overlap.
+pub fn check_adjacent(prev: &ExperienceRange, next: &ExperienceRange) -> Result<(), RangeError> {
+    range_len(prev)?;
+    range_len(next)?;
+    if next_experience_id(prev.end_experience_id)? != next.start_experience_id {
+        return Err(RangeError::NotAdjacent {
+            prev_end_experience_id: prev.end_experience_id,
+            next_start_experience_id: next.start_experience_id,
+        });
+    }
+    Ok(())
+}
+
+/// Errors returned by [`verify_experience_chain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChainError {
+    /// The record at `index` has no `finalization_header`.
+    HeaderMissing { index: usize },
+    /// The record has no `finalization_header.record_digest`.
+    RecordDigestMissing { experience_id: u64 },
+    /// `prev_record_digest` differs from the previous record's `record_digest`.
+    PrevDigestMismatch { experience_id: u64, expected: Vec<u8>, found: Vec<u8> },
+    /// `experience_id` does not increase over the previous record.
+    ExperienceIdNotIncreasing { prev_experience_id: u64, experience_id: u64 },
+    /// `timestamp_ms` does not increase over the previous record.
+    TimestampNotIncreasing { experience_id: u64, prev_timestamp_ms: u64, timestamp_ms: u64 },
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainError::HeaderMissing { index } => {
+                write!(f, "record at index {index} has no finalization_header")
+            }
+            ChainError::RecordDigestMissing { experience_id } => {
+                write!(f, "record {experience_id} has no record_digest")
+            }
+            ChainError::PrevDigestMismatch { experience_id, expected, found } => write!(
+                f,
+                "record {experience_id} prev_record_digest {} does not match previous record \
+                 digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+            ChainError::ExperienceIdNotIncreasing { prev_experience_id, experience_id } => {
+                write!(f, "record {experience_id} does not follow record {prev_experience_id}")
+            }
+            ChainError::TimestampNotIncreasing {
+                experience_id,
+                prev_timestamp_ms,
+                timestamp_ms,
+            } => {
+                write!(
+                    f,
+                    "record {experience_id} timestamp {timestamp_ms} does not follow \
+                     {prev_timestamp_ms}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChainError {}
+
+/// Confirm `records` form an unbroken hash chain in order.
+///
+/// Every record needs a `finalization_header` with a `record_digest`. Each
+/// record after the first must carry the previous record's `record_digest` as
+/// its `prev_record_digest`, and strictly greater `experience_id` and
+/// `timestamp_ms`. The first record's `prev_record_digest` is not checked.
+pub fn verify_experience_chain(records: &[ExperienceRecord]) -> Result<(), ChainError> {
+    let mut prev: Option<&FinalizationHeader> = None;
+    for (index, record) in records.iter().enumerate() {
+        let header =
+            record.finalization_header.as_ref().ok_or(ChainError::HeaderMissing { index })?;
+        let experience_id = header.experience_id;
+        if header.record_digest.is_none() {
+            return Err(ChainError::RecordDigestMissing { experience_id });
+        }
+
+        if let Some(prev) = prev {
+            if experience_id <= prev.experience_id {
+                return Err(ChainError::ExperienceIdNotIncreasing {
+                    prev_experience_id: prev.experience_id,
+                    experience_id,
+                });
+            }
+            if header.timestamp_ms <= prev.timestamp_ms {
+                return Err(ChainError::TimestampNotIncreasing {
+                    experience_id,
+                    prev_timestamp_ms: prev.timestamp_ms,
+                    timestamp_ms: header.timestamp_ms,
+                });
+            }
+            let expected = prev.record_digest.as_ref().map(|digest| &digest.value);
+            let found = header.prev_record_digest.as_ref().map(|digest| &digest.value);
+            if found != expected {
+                return Err(ChainError::PrevDigestMismatch {
+                    experience_id,
+                    expected: expected.cloned().unwrap_or_default(),
+                    found: found.cloned().unwrap_or_default(),
+                });
+            }
+        }
+        prev = Some(header);
+    }
+    Ok(())
+}
+
+/// Errors returned by [`verify_finalization`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FinalizationError {
+    /// The record has no `finalization_header`.
+    HeaderMissing,
+    /// The header has no `record_digest`.
+    RecordDigestMissing,
+    /// The header's `prev_record_digest` is missing or not 32 bytes.
+    PrevRecordDigestInvalid,
+    /// A governance frame was supplied without a `governance_frame_ref`, or
+    /// the record has a `governance_frame_ref` and none was supplied.
+    GovernanceFrameMismatch { has_ref: bool },
+    /// The header's `record_digest` differs from the digest of the frames.
+    RecordDigestMismatch { expected: [u8; 32], found: Vec<u8> },
+}
+
+impl fmt::Display for FinalizationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FinalizationError::HeaderMissing => f.write_str("record has no finalization_header"),
+            FinalizationError::RecordDigestMissing => {
+                f.write_str("finalization header has no record_digest")
+            }
+            FinalizationError::PrevRecordDigestInvalid => {
+                f.write_str("finalization header prev_record_digest is missing or not 32 bytes")
+            }
+            FinalizationError::GovernanceFrameMismatch { has_ref: true } => {
+                f.write_str("record has a governance_frame_ref but no governance frame was given")
+            }
+            FinalizationError::GovernanceFrameMismatch { has_ref: false } => {
+                f.write_str("governance frame given for a record without governance_frame_ref")
+            }
+            FinalizationError::RecordDigestMismatch { expected, found } => write!(
+                f,
+                "record_digest {} does not match recomputed digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FinalizationError {}
+
+/// The `verified_fields_digest` of `record` with its frame refs resolved to
+/// `core`, `metabolic`, and `governance`.
+///
+/// `digest32("UCF:EXPERIENCE:RECORD", "ucf.v1.ExperienceRecord", "1", preimage)`
+/// where the preimage is the canonical bytes of `record`, `core`, and
+/// `metabolic`, each as `len (u32 LE) || bytes`, then `0x00` without a
+/// governance frame or `0x01 || len (u32 LE) || bytes` with one. The record is
+/// encoded with `record_digest`, `vrf_digest_ref`, and `proof_receipt_ref`
+/// cleared, since those are derived from the digest.
+pub fn compute_verified_fields_digest(
+    record: &ExperienceRecord,
+    core: &CoreFrame,
+    metabolic: &MetabolicFrame,
+    governance: Option<&GovernanceFrame>,
+) -> [u8; 32] {
+    let mut content = record.clone();
+    if let Some(header) = content.finalization_header.as_mut() {
+        header.record_digest = None;
+        header.vrf_digest_ref = None;
+        header.proof_receipt_ref = None;
+    }
+
+    let mut preimage = Vec::new();
+    push_prefixed(&mut preimage, &content);
+    push_prefixed(&mut preimage, core);
+    push_prefixed(&mut preimage, metabolic);
+    match governance {
+        Some(governance) => {
+            preimage.push(0x01);
+            push_prefixed(&mut preimage, governance);
+        }
+        None => preimage.push(0x00),
+    }
+    digest32(Domain::ExperienceRecord, RECORD_SCHEMA_ID, RECORD_SCHEMA_VERSION, &preimage)
+}
+
+fn push_prefixed<M: Message>(preimage: &mut Vec<u8>, message: &M) {
+    let bytes = canonical_bytes(message);
+    preimage.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    preimage.extend_from_slice(&bytes);
+}
+
+/// Confirm `record`'s `finalization_header.record_digest` is
+/// [`record_digest_from_components`] of [`compute_verified_fields_digest`] over
+/// the resolved frames, the header's `prev_record_digest`, and `commit_id`.
+///
+/// The frame refs are URIs, so the caller resolves them and passes the frames.
+/// `governance` must be given exactly when the record has a
+/// `governance_frame_ref`. `commit_id` is the PVGS commit the record was
+/// finalized under.
+pub fn verify_finalization(
+    record: &ExperienceRecord,
+    core: &CoreFrame,
+    metabolic: &MetabolicFrame,
+    governance: Option<&GovernanceFrame>,
+    commit_id: &[u8],
+) -> Result<(), FinalizationError> {
+    let header = record.finalization_header.as_ref().ok_or(FinalizationError::HeaderMissing)?;
+    let found = header.record_digest.as_ref().ok_or(FinalizationError::RecordDigestMissing)?;
+    let prev_record_digest: [u8; 32] = header
+        .prev_record_digest
+        .as_ref()
+        .and_then(|digest| digest.value.as_slice().try_into().ok())
+        .ok_or(FinalizationError::PrevRecordDigestInvalid)?;
+    let has_ref = record.governance_frame_ref.is_some();
+    if has_ref != governance.is_some() {
+        return Err(FinalizationError::GovernanceFrameMismatch { has_ref });
+    }
+
+    let verified_fields_digest =
+        compute_verified_fields_digest(record, core, metabolic, governance);
+    let expected =
+        record_digest_from_components(verified_fields_digest, prev_record_digest, commit_id);
+    if !bool::from(found.value.as_slice().ct_eq(&expected)) {
+        return Err(FinalizationError::RecordDigestMismatch {
+            expected,
+            found: found.value.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// A frame reference slot on [`ExperienceRecord`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameRef {
+    Core,
+    Metabolic,
+    Governance,
+}
+
+impl FrameRef {
+    /// The `ExperienceRecord` field this slot is stored in.
+    pub fn field(self) -> &'static str {
+        match self {
+            FrameRef::Core => "core_frame_ref",
+            FrameRef::Metabolic => "metabolic_frame_ref",
+            FrameRef::Governance => "governance_frame_ref",
+        }
+    }
+
+    fn get(self, record: &ExperienceRecord) -> Option<&Ref> {
+        match self {
+            FrameRef::Core => record.core_frame_ref.as_ref(),
+            FrameRef::Metabolic => record.metabolic_frame_ref.as_ref(),
+            FrameRef::Governance => record.governance_frame_ref.as_ref(),
+        }
+    }
+}
+
+/// Frame refs each experience record type must carry.
+///
+/// Record types absent from this table are not experience record types and
+/// are rejected by [`validate_experience_record`].
+pub const REQUIRED_FRAME_REFS: &[(RecordType, &[FrameRef])] = &[
+    (RecordType::RtPerception, &[FrameRef::Core, FrameRef::Metabolic]),
+    (RecordType::RtActionExec, &[FrameRef::Core, FrameRef::Metabolic, FrameRef::Governance]),
+    (RecordType::RtOutput, &[FrameRef::Core, FrameRef::Metabolic, FrameRef::Governance]),
+    (RecordType::RtDecision, &[FrameRef::Governance]),
+    (RecordType::RtReplay, &[]),
+    (RecordType::RtConsolidation, &[FrameRef::Metabolic]),
+];
+
+/// Errors returned by [`validate_experience_record`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// `record_type` is not listed in [`REQUIRED_FRAME_REFS`].
+    UnsupportedRecordType(i32),
+    /// A field the record type requires is not set.
+    FieldMissing { record_type: RecordType, field: &'static str },
+    /// `finalization_header.record_digest` is not 32 bytes.
+    BadRecordDigestLength(usize),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::UnsupportedRecordType(record_type) => {
+                write!(f, "record type {record_type} is not an experience record type")
+            }
+            ValidationError::FieldMissing { record_type, field } => {
+                write!(f, "{} record has no {field}", record_type.as_str_name())
+            }
+            ValidationError::BadRecordDigestLength(len) => {
+                write!(f, "record_digest is {len} bytes, expected 32")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Check `record` carries the fields its `record_type` requires.
+///
+/// The frame refs required per type come from [`REQUIRED_FRAME_REFS`]. Every
+/// record also needs a `finalization_header` with a 32-byte `record_digest`.
+pub fn validate_experience_record(record: &ExperienceRecord) -> Result<(), ValidationError> {
+    let (record_type, required) = REQUIRED_FRAME_REFS
+        .iter()
+        .find(|(record_type, _)| *record_type as i32 == record.record_type)
+        .ok_or(ValidationError::UnsupportedRecordType(record.record_type))?;
+    let record_type = *record_type;
+    for frame in required.iter() {
+        if frame.get(record).is_none() {
+            return Err(ValidationError::FieldMissing { record_type, field: frame.field() });
+        }
+    }
+
+    let header = record
+        .finalization_header
+        .as_ref()
+        .ok_or(ValidationError::FieldMissing { record_type, field: "finalization_header" })?;
+    let digest = header.record_digest.as_ref().ok_or(ValidationError::FieldMissing {
+        record_type,
+        field: "finalization_header.record_digest",
+    })?;
+    if digest.value.len() != 32 {
+        return Err(ValidationError::BadRecordDigestLength(digest.value.len()));
+    }
+    Ok(())
+}
+
+fn reference(uri: impl Into<String>, label: impl Into<String>) -> Option<Ref> {
+    Some(Ref { uri: uri.into(), label: label.into() })
+}
+
+/// Builds an [`ExperienceRecord`] checked by [`validate_experience_record`].
+#[derive(Clone, Debug)]
+pub struct ExperienceRecordBuilder {
+    record: ExperienceRecord,
+    header: Option<FinalizationHeaderBuilder>,
+}
+
+impl ExperienceRecordBuilder {
+    pub fn new(record_type: RecordType) -> Self {
+        Self {
+            record: ExperienceRecord { record_type: record_type as i32, ..Default::default() },
+            header: None,
+        }
+    }
+
+    pub fn core_frame(mut self, uri: impl Into<String>, label: impl Into<String>) -> Self {
+        self.record.core_frame_ref = reference(uri, label);
+        self
+    }
+
+    pub fn metabolic_frame(mut self, uri: impl Into<String>, label: impl Into<String>) -> Self {
+        self.record.metabolic_frame_ref = reference(uri, label);
+        self
+    }
+
+    pub fn governance_frame(mut self, uri: impl Into<String>, label: impl Into<String>) -> Self {
+        self.record.governance_frame_ref = reference(uri, label);
+        self
+    }
+
+    /// Append to `related_refs`, whose order is significant.
+    pub fn related_ref(mut self, uri: impl Into<String>, label: impl Into<String>) -> Self {
+        self.record.related_refs.extend(reference(uri, label));
+        self
+    }
+
+    pub fn finalization_header(mut self, header: FinalizationHeaderBuilder) -> Self {
+        self.header = Some(header);
+        self
+    }
+
+    /// Build the record, applying the frame rules of its record type.
+    ///
+    /// The finalization header must set both `record_digest` and
+    /// `prev_record_digest`.
+    pub fn build(self) -> Result<ExperienceRecord, ValidationError> {
+        let mut record = self.record;
+        if let Some(header) = self.header {
+            let record_type = RecordType::try_from(record.record_type)
+                .map_err(|_| ValidationError::UnsupportedRecordType(record.record_type))?;
+            record.finalization_header = Some(header.build(record_type)?);
+        }
+        validate_experience_record(&record)?;
+        Ok(record)
+    }
+}
+
+/// Builds the [`FinalizationHeader`] of an [`ExperienceRecordBuilder`].
+#[derive(Clone, Debug)]
+pub struct FinalizationHeaderBuilder {
+    header: FinalizationHeader,
+}
+
+impl FinalizationHeaderBuilder {
+    pub fn new(experience_id: u64, timestamp_ms: u64) -> Self {
+        Self { header: FinalizationHeader { experience_id, timestamp_ms, ..Default::default() } }
+    }
+
+    pub fn prev_record_digest(mut self, digest: [u8; 32]) -> Self {
+        self.header.prev_record_digest = Some(Digest32 { value: digest.to_vec() });
+        self
+    }
+
+    pub fn record_digest(mut self, digest: [u8; 32]) -> Self {
+        self.header.record_digest = Some(Digest32 { value: digest.to_vec() });
+        self
+    }
+
+    pub fn vrf_digest_ref(mut self, uri: impl Into<String>, label: impl Into<String>) -> Self {
+        self.header.vrf_digest_ref = reference(uri, label);
+        self
+    }
+
+    pub fn proof_receipt_ref(mut self, uri: impl Into<String>, label: impl Into<String>) -> Self {
+        self.header.proof_receipt_ref = reference(uri, label);
+        self
+    }
+
+    pub fn charter_version_digest(mut self, version: impl Into<String>) -> Self {
+        self.header.charter_version_digest = version.into();
+        self
+    }
+
+    pub fn policy_version_digest(mut self, version: impl Into<String>) -> Self {
+        self.header.policy_version_digest = version.into();
+        self
+    }
+
+    pub fn key_epoch_id(mut self, key_epoch_id: u64) -> Self {
+        self.header.key_epoch_id = key_epoch_id;
+        self
+    }
+
+    fn build(self, record_type: RecordType) -> Result<FinalizationHeader, ValidationError> {
+        for (digest, field) in [
+            (&self.header.record_digest, "finalization_header.record_digest"),
+            (&self.header.prev_record_digest, "finalization_header.prev_record_digest"),
+        ] {
+            if digest.is_none() {
+                return Err(ValidationError::FieldMissing { record_type, field });
+            }
+        }
+        Ok(self.header)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::decode_fixture;
+
+    fn record(experience_id: u64, digest: u8) -> ExperienceRecord {
+        ExperienceRecord {
+            finalization_header: Some(FinalizationHeader {
+                experience_id,
+                record_digest: Some(Digest32 { value: vec![digest; 32] }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn range(end_experience_id: u64, head: u8) -> ExperienceRange {
+        ExperienceRange {
+            start_experience_id: 1_001,
+            end_experience_id,
+            head_record_digest: Some(Digest32 { value: vec![head; 32] }),
+        }
+    }
+
+    #[test]
+    fn range_head_matches_last_record() {
+        let records = vec![record(1_001, 0xBB), record(1_002, 0xCC), record(1_003, 0xDD)];
+
+        assert_eq!(verify_range_head(&range(1_003, 0xDD), &records), Ok(()));
+        assert_eq!(
+            verify_range_head(&range(1_003, 0xCC), &records),
+            Err(RangeError::HeadDigestMismatch {
+                experience_id: 1_003,
+                expected: vec![0xCC; 32],
+                found: vec![0xDD; 32],
+            })
+        );
+    }
+
+    #[test]
+    fn range_head_requires_head_record() {
+        let records = vec![record(1_001, 0xBB), record(1_002, 0xCC)];
+
+        assert_eq!(
+            verify_range_head(&range(1_003, 0xDD), &records),
+            Err(RangeError::HeadRecordMissing { end_experience_id: 1_003 })
+        );
+    }
+
+    fn span(start_experience_id: u64, end_experience_id: u64) -> ExperienceRange {
+        ExperienceRange { start_experience_id, end_experience_id, head_record_digest: None }
+    }
+
+    #[test]
+    fn range_arithmetic_is_overflow_safe() {
+        assert_eq!(range_len(&span(1_001, 1_003)), Ok(3));
+        assert_eq!(range_len(&span(u64::MAX, u64::MAX)), Ok(1));
+        assert_eq!(range_len(&span(1, u64::MAX)), Ok(u64::MAX));
+        assert_eq!(
+            range_len(&span(0, u64::MAX)),
+            Err(RangeError::Overflow { experience_id: u64::MAX })
+        );
+        assert_eq!(
+            range_len(&span(5, 4)),
+            Err(RangeError::Inverted { start_experience_id: 5, end_experience_id: 4 })
+        );
+
+        assert_eq!(next_experience_id(u64::MAX - 1), Ok(u64::MAX));
+        assert_eq!(
+            next_experience_id(u64::MAX),
+            Err(RangeError::Overflow { experience_id: u64::MAX })
+        );
+    }
+
+    #[test]
+    fn adjacent_ranges_near_u64_max() {
+        assert_eq!(check_adjacent(&span(1, 10), &span(11, 20)), Ok(()));
+        assert_eq!(
+            check_adjacent(&span(u64::MAX - 10, u64::MAX - 1), &span(u64::MAX, u64::MAX)),
+            Ok(())
+        );
+        assert_eq!(
+            check_adjacent(&span(u64::MAX - 10, u64::MAX), &span(0, 5)),
+            Err(RangeError::Overflow { experience_id: u64::MAX })
+        );
+        assert_eq!(
+            check_adjacent(&span(1, 10), &span(10, 20)),
+            Err(RangeError::NotAdjacent {
+                prev_end_experience_id: 10,
+                next_start_experience_id: 10
+            })
+        );
+    }
+
+    #[test]
+    fn version_fields_parse_as_label_or_digest() {
+        let digest_hex = "ab".repeat(32);
+        let header = FinalizationHeader {
+            charter_version_digest: "charter:v3".to_string(),
+            policy_version_digest: digest_hex.clone(),
+            ..Default::default()
+        };
+
+        assert_eq!(header.charter_version(), VersionRef::Label("charter:v3".to_string()));
+        assert_eq!(header.charter_version().digest(), None);
+        assert_eq!(header.policy_version(), VersionRef::Digest([0xAB; 32]));
+        assert_eq!(header.policy_version().digest(), Some(&[0xAB; 32]));
+
+        assert_eq!(VersionRef::parse(&digest_hex.to_uppercase()).digest(), None);
+        assert_eq!(VersionRef::parse(&digest_hex[..62]).digest(), None);
+        assert_eq!(VersionRef::parse(""), VersionRef::Label(String::new()));
+    }
+
+    fn chained(experience_id: u64, timestamp_ms: u64, prev: u8, digest: u8) -> ExperienceRecord {
+        let mut record = record(experience_id, digest);
+        let header = record.finalization_header.as_mut().unwrap();
+        header.timestamp_ms = timestamp_ms;
+        header.prev_record_digest = Some(Digest32 { value: vec![prev; 32] });
+        record
+    }
+
+    #[test]
+    fn experience_chain_links_digests_in_order() {
+        let records = vec![
+            chained(1_001, 10, 0x00, 0xBB),
+            chained(1_002, 20, 0xBB, 0xCC),
+            chained(1_003, 30, 0xCC, 0xDD),
+        ];
+        assert_eq!(verify_experience_chain(&records), Ok(()));
+        assert_eq!(verify_experience_chain(&[]), Ok(()));
+
+        let gap = vec![records[0].clone(), records[2].clone()];
+        assert_eq!(
+            verify_experience_chain(&gap),
+            Err(ChainError::PrevDigestMismatch {
+                experience_id: 1_003,
+                expected: vec![0xBB; 32],
+                found: vec![0xCC; 32],
+            })
+        );
+
+        let reordered = vec![records[1].clone(), records[0].clone()];
+        assert_eq!(
+            verify_experience_chain(&reordered),
+            Err(ChainError::ExperienceIdNotIncreasing {
+                prev_experience_id: 1_002,
+                experience_id: 1_001
+            })
+        );
+
+        let stalled = vec![records[0].clone(), chained(1_002, 10, 0xBB, 0xCC)];
+        assert_eq!(
+            verify_experience_chain(&stalled),
+            Err(ChainError::TimestampNotIncreasing {
+                experience_id: 1_002,
+                prev_timestamp_ms: 10,
+                timestamp_ms: 10
+            })
+        );
+
+        let headless = vec![records[0].clone(), ExperienceRecord::default()];
+        assert_eq!(verify_experience_chain(&headless), Err(ChainError::HeaderMissing { index: 1 }));
+    }
+
+    #[test]
+    fn experience_fixtures_form_a_chain() {
+        let records: Vec<ExperienceRecord> =
+            ["experience_rt_perception", "experience_rt_action_exec", "experience_rt_output"]
+                .into_iter()
+                .map(decode_fixture)
+                .collect();
+        assert_eq!(verify_experience_chain(&records), Ok(()));
+    }
+
+    type Mutation = fn(&mut ExperienceRecord);
+
+    #[test]
+    fn record_types_require_their_frame_refs() {
+        let frame = Some(Ref { uri: "ucf://frame".to_string(), label: String::new() });
+        let complete = ExperienceRecord {
+            core_frame_ref: frame.clone(),
+            metabolic_frame_ref: frame.clone(),
+            governance_frame_ref: frame,
+            ..record(1_001, 0xAA)
+        };
+
+        let cases: &[(RecordType, Mutation, Result<(), ValidationError>)] = &[
+            (RecordType::RtPerception, |r| r.governance_frame_ref = None, Ok(())),
+            (
+                RecordType::RtPerception,
+                |r| r.core_frame_ref = None,
+                Err(ValidationError::FieldMissing {
+                    record_type: RecordType::RtPerception,
+                    field: "core_frame_ref",
+                }),
+            ),
+            (
+                RecordType::RtActionExec,
+                |r| r.governance_frame_ref = None,
+                Err(ValidationError::FieldMissing {
+                    record_type: RecordType::RtActionExec,
+                    field: "governance_frame_ref",
+                }),
+            ),
+            (
+                RecordType::RtOutput,
+                |r| r.metabolic_frame_ref = None,
+                Err(ValidationError::FieldMissing {
+                    record_type: RecordType::RtOutput,
+                    field: "metabolic_frame_ref",
+                }),
+            ),
+            (RecordType::RtDecision, |r| r.metabolic_frame_ref = None, Ok(())),
+            (
+                RecordType::RtReplay,
+                |r| {
+                    r.core_frame_ref = None;
+                    r.metabolic_frame_ref = None;
+                    r.governance_frame_ref = None;
+                },
+                Ok(()),
+            ),
+            (
+                RecordType::RtConsolidation,
+                |r| r.finalization_header = None,
+                Err(ValidationError::FieldMissing {
+                    record_type: RecordType::RtConsolidation,
+                    field: "finalization_header",
+                }),
+            ),
+            (
+                RecordType::RtDecision,
+                |r| r.finalization_header.as_mut().unwrap().record_digest = None,
+                Err(ValidationError::FieldMissing {
+                    record_type: RecordType::RtDecision,
+                    field: "finalization_header.record_digest",
+                }),
+            ),
+            (
+                RecordType::RtOutput,
+                |r| {
+                    let header = r.finalization_header.as_mut().unwrap();
+                    header.record_digest.as_mut().unwrap().value.pop();
+                },
+                Err(ValidationError::BadRecordDigestLength(31)),
+            ),
+            (
+                RecordType::Policy,
+                |_| {},
+                Err(ValidationError::UnsupportedRecordType(RecordType::Policy as i32)),
+            ),
+        ];
+        for (record_type, mutate, expected) in cases {
+            let mut record =
+                ExperienceRecord { record_type: *record_type as i32, ..complete.clone() };
+            mutate(&mut record);
+            assert_eq!(&validate_experience_record(&record), expected, "{record_type:?}");
+        }
+    }
+
+    #[test]
+    fn experience_fixtures_are_valid() {
+        for name in
+            ["experience_rt_perception", "experience_rt_action_exec", "experience_rt_output"]
+        {
+            let record: ExperienceRecord = decode_fixture(name);
+            assert_eq!(validate_experience_record(&record), Ok(()), "{name}");
+        }
+    }
+
+    #[test]
+    fn builder_reproduces_the_action_exec_fixture() {
+        let fixture: ExperienceRecord = decode_fixture("experience_rt_action_exec");
+
+        let header = FinalizationHeaderBuilder::new(1_002, 1_700_010_250)
+            .prev_record_digest([0xBB; 32])
+            .record_digest([0xCC; 32])
+            .vrf_digest_ref("vrf://digest/seed", "vrf")
+            .proof_receipt_ref("proof://receipt/b", "proof")
+            .charter_version_digest("charter:v3")
+            .policy_version_digest("policy:v5")
+            .key_epoch_id(17);
+        let record = ExperienceRecordBuilder::new(RecordType::RtActionExec)
+            .core_frame("core://action/002", "core-frame")
+            .metabolic_frame("metabolic://action/002", "metabolic-frame")
+            .governance_frame("governance://action/002", "governance-frame")
+            .related_ref("policy://query/001", "policy_query")
+            .related_ref("policy://decision/001", "policy_decision")
+            .related_ref("policy://ruleset/alpha", "ruleset")
+            .finalization_header(header)
+            .build();
+        assert_eq!(record, Ok(fixture));
+    }
+
+    #[test]
+    fn builder_enforces_header_digests_and_frame_rules() {
+        let header = FinalizationHeaderBuilder::new(1, 1).record_digest([0xAA; 32]);
+        assert_eq!(
+            ExperienceRecordBuilder::new(RecordType::RtReplay).finalization_header(header).build(),
+            Err(ValidationError::FieldMissing {
+                record_type: RecordType::RtReplay,
+                field: "finalization_header.prev_record_digest",
+            })
+        );
+
+        let header = FinalizationHeaderBuilder::new(1, 1).prev_record_digest([0xAA; 32]);
+        assert_eq!(
+            ExperienceRecordBuilder::new(RecordType::RtReplay).finalization_header(header).build(),
+            Err(ValidationError::FieldMissing {
+                record_type: RecordType::RtReplay,
+                field: "finalization_header.record_digest",
+            })
+        );
+
+        let header =
+            FinalizationHeaderBuilder::new(1, 1).prev_record_digest([0; 32]).record_digest([1; 32]);
+        assert_eq!(
+            ExperienceRecordBuilder::new(RecordType::RtDecision)
+                .finalization_header(header.clone())
+                .build(),
+            Err(ValidationError::FieldMissing {
+                record_type: RecordType::RtDecision,
+                field: "governance_frame_ref",
+            })
+        );
+        assert!(ExperienceRecordBuilder::new(RecordType::RtDecision)
+            .governance_frame("governance://decision/1", "governance-frame")
+            .finalization_header(header)
+            .build()
+            .is_ok());
+        assert_eq!(
+            ExperienceRecordBuilder::new(RecordType::RtReplay).build(),
+            Err(ValidationError::FieldMissing {
+                record_type: RecordType::RtReplay,
+                field: "finalization_header",
+            })
+        );
+    }
+
+    const COMMIT: &[u8] = b"commit-abc123";
+
+    fn finalized(
+        governance: Option<&GovernanceFrame>,
+    ) -> (ExperienceRecord, CoreFrame, MetabolicFrame) {
+        let core = CoreFrame { core_frame_id: "core-1".to_string(), ..Default::default() };
+        let metabolic =
+            MetabolicFrame { metabolic_frame_id: "metabolic-1".to_string(), ..Default::default() };
+        let mut builder = ExperienceRecordBuilder::new(RecordType::RtPerception)
+            .core_frame("ucf://frames/core-1", "core")
+            .metabolic_frame("ucf://frames/metabolic-1", "metabolic");
+        if governance.is_some() {
+            builder = builder.governance_frame("ucf://frames/governance-1", "governance");
+        }
+        let mut record = builder
+            .finalization_header(
+                FinalizationHeaderBuilder::new(7, 1_000)
+                    .prev_record_digest([0xAA; 32])
+                    .record_digest([0; 32]),
+            )
+            .build()
+            .expect("record builds");
+        let verified = compute_verified_fields_digest(&record, &core, &metabolic, governance);
+        let digest = record_digest_from_components(verified, [0xAA; 32], COMMIT);
+        let header = record.finalization_header.as_mut().unwrap();
+        header.record_digest = Some(Digest32 { value: digest.to_vec() });
+        header.vrf_digest_ref = reference("ucf://vrf/7", "vrf");
+        (record, core, metabolic)
+    }
+
+    #[test]
+    fn finalization_binds_record_digest_to_frames() {
+        let (record, core, metabolic) = finalized(None);
+        assert_eq!(verify_finalization(&record, &core, &metabolic, None, COMMIT), Ok(()));
+
+        let other_core = CoreFrame { step_id: "step-2".to_string(), ..core.clone() };
+        assert!(matches!(
+            verify_finalization(&record, &other_core, &metabolic, None, COMMIT),
+            Err(FinalizationError::RecordDigestMismatch { .. })
+        ));
+
+        let governance = GovernanceFrame {
+            governance_frame_id: "governance-1".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            verify_finalization(&record, &core, &metabolic, Some(&governance), COMMIT),
+            Err(FinalizationError::GovernanceFrameMismatch { has_ref: false })
+        );
+
+        let (record, core, metabolic) = finalized(Some(&governance));
+        assert_eq!(
+            verify_finalization(&record, &core, &metabolic, Some(&governance), COMMIT),
+            Ok(())
+        );
+        assert_eq!(
+            verify_finalization(&record, &core, &metabolic, None, COMMIT),
+            Err(FinalizationError::GovernanceFrameMismatch { has_ref: true })
+        );
+        let empty = GovernanceFrame::default();
+        assert!(matches!(
+            verify_finalization(&record, &core, &metabolic, Some(&empty), COMMIT),
+            Err(FinalizationError::RecordDigestMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn finalization_binds_prev_digest_and_commit() {
+        let (mut record, core, metabolic) = finalized(None);
+        assert!(matches!(
+            verify_finalization(&record, &core, &metabolic, None, b"commit-other"),
+            Err(FinalizationError::RecordDigestMismatch { .. })
+        ));
+        let header = record.finalization_header.as_mut().unwrap();
+        header.prev_record_digest = Some(Digest32 { value: vec![0xBB; 32] });
+        assert!(matches!(
+            verify_finalization(&record, &core, &metabolic, None, COMMIT),
+            Err(FinalizationError::RecordDigestMismatch { .. })
+        ));
+        record.finalization_header.as_mut().unwrap().prev_record_digest = None;
+        assert_eq!(
+            verify_finalization(&record, &core, &metabolic, None, COMMIT),
+            Err(FinalizationError::PrevRecordDigestInvalid)
+        );
+    }
+
+    #[test]
+    fn finalization_requires_a_record_digest() {
+        let (mut record, core, metabolic) = finalized(None);
+        record.finalization_header.as_mut().unwrap().record_digest = None;
+        assert_eq!(
+            verify_finalization(&record, &core, &metabolic, None, COMMIT),
+            Err(FinalizationError::RecordDigestMissing)
+        );
+        record.finalization_header = None;
+        assert_eq!(
+            verify_finalization(&record, &core, &metabolic, None, COMMIT),
+            Err(FinalizationError::HeaderMissing)
+        );
+    }
+}
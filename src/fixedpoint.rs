@@ -0,0 +1,73 @@
+//! Q16.16 fixed-point conversions for quantized biophysics fields.
+//!
+//! Fields with a `_q` suffix, such as `SynapseParams.g_max_q` and
+//! `SynapseParams.stp_u_q`, hold unsigned Q16.16 values: the upper 16 bits
+//! are the integer part and the lower 16 bits the fraction, so the value is
+//! `q / 65536`. `65_536` is `1.0` and `32_768` is `0.5`.
+
+use crate::ucf::v1::SynapseParams;
+
+/// `1.0` in Q16.16.
+pub const Q16_ONE: u32 = 1 << 16;
+
+/// The value of the Q16.16 number `q`.
+///
+/// `f32` has a 24-bit significand, so values at or above `256.0` lose their
+/// lowest fractional bits.
+pub fn q16_to_f32(q: u32) -> f32 {
+    (f64::from(q) / f64::from(Q16_ONE)) as f32
+}
+
+/// `x` as Q16.16, rounded to the nearest step of `1 / 65536`.
+///
+/// Negative values and NaN become `0`, and values above the largest Q16.16
+/// number saturate to `u32::MAX`.
+pub fn f32_to_q16(x: f32) -> u32 {
+    (f64::from(x) * f64::from(Q16_ONE)).round() as u32
+}
+
+impl SynapseParams {
+    /// `g_max_q` as a conductance.
+    pub fn g_max(&self) -> f32 {
+        q16_to_f32(self.g_max_q)
+    }
+
+    /// `stp_u_q` as a release probability.
+    pub fn stp_u(&self) -> f32 {
+        q16_to_f32(self.stp_u_q)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::decode_fixture;
+    use crate::ucf::v1::SynapseParamsSetPayload;
+
+    #[test]
+    fn converts_q16_16() {
+        assert_eq!(q16_to_f32(Q16_ONE), 1.0);
+        assert_eq!(q16_to_f32(0x0002_8000), 2.5);
+        assert_eq!(q16_to_f32(1), 1.0 / 65536.0);
+        assert_eq!(f32_to_q16(0.25), 16_384);
+        assert_eq!(f32_to_q16(1.0 / 131072.0), 1);
+        for q in [0, 1, 3, 16_384, Q16_ONE, 0x00FF_FFFF] {
+            assert_eq!(f32_to_q16(q16_to_f32(q)), q, "{q:#x}");
+        }
+    }
+
+    #[test]
+    fn f32_to_q16_saturates() {
+        assert_eq!(f32_to_q16(-1.0), 0);
+        assert_eq!(f32_to_q16(f32::NAN), 0);
+        assert_eq!(f32_to_q16(65536.0), u32::MAX);
+        assert_eq!(f32_to_q16(f32::INFINITY), u32::MAX);
+    }
+
+    #[test]
+    fn synapse_fixture_accessors() {
+        let payload: SynapseParamsSetPayload = decode_fixture("biophys_synapse_params_set_v1");
+        let values: Vec<_> = payload.params.iter().map(|p| (p.g_max(), p.stp_u())).collect();
+        assert_eq!(values, [(1.0, 0.5), (0.5, 0.25)]);
+    }
+}
@@ -0,0 +1,323 @@
+//! Batch encoding, digesting, and writing of golden fixtures.
+//!
+//! A [`FixtureSet`] collects messages together with the schema id and domain
+//! of their digest. [`FixtureSet::write_all`] writes each entry as a message
+//! file plus a `<name>.digest` file in the formats described in
+//! [`crate::codec`], and [`FixtureSet::verify_all`] confirms a directory still
+//! holds exactly those bytes and digests. The generators under `examples/` use
+//! it, and downstream crates can maintain their own fixture sets the same way.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use prost::Message;
+
+use crate::codec::{
+    b64_fixture_body, hex_fixture_body, normalize_b64_fixture, normalize_hex_fixture,
+};
+use crate::{canonical_bytes, digest32_with, DigestAlgo};
+
+const SCHEMA_VERSION: &str = "1";
+
+/// How a fixture's message bytes are stored on disk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixtureFormat {
+    /// `<name>.hex`, see [`crate::codec`].
+    Hex,
+    /// `<name>.bin`, raw bytes.
+    Bin,
+    /// `<name>.b64`, unpadded base64url, see [`crate::codec`].
+    Base64Url,
+}
+
+impl FixtureFormat {
+    /// Every format, in the order [`FixtureFormat::detect`] tries them.
+    pub const ALL: [FixtureFormat; 3] =
+        [FixtureFormat::Hex, FixtureFormat::Base64Url, FixtureFormat::Bin];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            FixtureFormat::Hex => "hex",
+            FixtureFormat::Bin => "bin",
+            FixtureFormat::Base64Url => "b64",
+        }
+    }
+
+    /// The format stored in files ending in `.<extension>`.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.extension() == extension)
+    }
+
+    /// The format of the message file fixture `name` has under `dir`.
+    pub fn detect(dir: &Path, name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|format| dir.join(format!("{name}.{}", format.extension())).is_file())
+    }
+}
+
+/// One canonically encoded message and the parameters of its digest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FixtureEntry {
+    pub name: String,
+    pub schema: String,
+    pub domain: String,
+    pub format: FixtureFormat,
+    pub algo: DigestAlgo,
+    pub bytes: Vec<u8>,
+}
+
+impl FixtureEntry {
+    /// `digest32_with(algo, domain, schema, "1", bytes)`.
+    pub fn digest(&self) -> [u8; 32] {
+        digest32_with(self.algo, &self.domain, &self.schema, SCHEMA_VERSION, &self.bytes)
+    }
+
+    /// Write the message and digest files under `dir`, which must exist.
+    ///
+    /// Entries write disjoint files, so a set can be written concurrently.
+    pub fn write(&self, dir: &Path) -> io::Result<()> {
+        let path = dir.join(format!("{}.{}", self.name, self.format.extension()));
+        match self.format {
+            FixtureFormat::Hex => fs::write(path, hex_fixture_body(&self.bytes))?,
+            FixtureFormat::Bin => fs::write(path, &self.bytes)?,
+            FixtureFormat::Base64Url => fs::write(path, b64_fixture_body(&self.bytes))?,
+        }
+        fs::write(dir.join(format!("{}.digest", self.name)), hex_fixture_body(&self.digest()))
+    }
+}
+
+/// Errors returned by [`FixtureSet::verify_all`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FixtureError {
+    /// A fixture file could not be read or decoded.
+    Unreadable { name: String, reason: String },
+    /// The stored message bytes differ from the canonical encoding.
+    BytesMismatch { name: String },
+    /// The stored digest differs from the recomputed digest.
+    DigestMismatch { name: String, expected: [u8; 32], found: [u8; 32] },
+}
+
+impl fmt::Display for FixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixtureError::Unreadable { name, reason } => {
+                write!(f, "fixture {name} is unreadable: {reason}")
+            }
+            FixtureError::BytesMismatch { name } => {
+                write!(f, "fixture {name} does not match its canonical encoding")
+            }
+            FixtureError::DigestMismatch { name, expected, found } => write!(
+                f,
+                "fixture {name} digest {} does not match {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FixtureError {}
+
+/// An ordered collection of fixtures to write or verify together.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FixtureSet {
+    entries: Vec<FixtureEntry>,
+}
+
+impl FixtureSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `message` as a hex fixture named `name`.
+    pub fn push<M: Message>(
+        &mut self,
+        name: &str,
+        schema: &str,
+        domain: impl AsRef<str>,
+        message: &M,
+    ) {
+        self.push_with_format(name, schema, domain, FixtureFormat::Hex, message);
+    }
+
+    /// Add `message` as a fixture named `name` stored in `format`.
+    pub fn push_with_format<M: Message>(
+        &mut self,
+        name: &str,
+        schema: &str,
+        domain: impl AsRef<str>,
+        format: FixtureFormat,
+        message: &M,
+    ) {
+        self.entries.push(FixtureEntry {
+            name: name.to_string(),
+            schema: schema.to_string(),
+            domain: domain.as_ref().to_string(),
+            format,
+            algo: DigestAlgo::default(),
+            bytes: canonical_bytes(message),
+        });
+    }
+
+    /// Add `message` as a hex fixture named `name` digested with `algo`.
+    pub fn push_with_algo<M: Message>(
+        &mut self,
+        name: &str,
+        schema: &str,
+        domain: impl AsRef<str>,
+        algo: DigestAlgo,
+        message: &M,
+    ) {
+        self.push(name, schema, domain, message);
+        if let Some(entry) = self.entries.last_mut() {
+            entry.algo = algo;
+        }
+    }
+
+    pub fn entries(&self) -> &[FixtureEntry] {
+        &self.entries
+    }
+
+    /// Write every entry's message and digest files under `dir`, creating it if needed.
+    pub fn write_all(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        self.entries.iter().try_for_each(|entry| entry.write(dir))
+    }
+
+    /// Confirm every entry's files under `dir` hold its canonical bytes and digest.
+    pub fn verify_all(&self, dir: &Path) -> Result<(), FixtureError> {
+        for entry in &self.entries {
+            let (bytes, stored) = read_fixture(dir, &entry.name, entry.format)
+                .map_err(|reason| FixtureError::Unreadable { name: entry.name.clone(), reason })?;
+            if bytes != entry.bytes {
+                return Err(FixtureError::BytesMismatch { name: entry.name.clone() });
+            }
+            let expected = entry.digest();
+            if stored != expected {
+                return Err(FixtureError::DigestMismatch {
+                    name: entry.name.clone(),
+                    expected,
+                    found: stored,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read a message file, choosing its format from the extension.
+///
+/// Files whose extension is not a [`FixtureFormat`] are read as raw bytes.
+pub fn read_message_file(path: &Path) -> Result<Vec<u8>, String> {
+    let format = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(FixtureFormat::from_extension)
+        .unwrap_or(FixtureFormat::Bin);
+    read_message(path, format)
+}
+
+fn read_message(path: &Path, format: FixtureFormat) -> Result<Vec<u8>, String> {
+    let text = || fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()));
+    match format {
+        FixtureFormat::Hex => normalize_hex_fixture(&text()?).map_err(|err| err.to_string()),
+        FixtureFormat::Base64Url => normalize_b64_fixture(&text()?).map_err(|err| err.to_string()),
+        FixtureFormat::Bin => fs::read(path).map_err(|err| err.to_string()),
+    }
+    .map_err(|err| format!("{}: {err}", path.display()))
+}
+
+/// Read the message bytes and stored digest of fixture `name` under `dir`.
+pub(crate) fn read_fixture(
+    dir: &Path,
+    name: &str,
+    format: FixtureFormat,
+) -> Result<(Vec<u8>, [u8; 32]), String> {
+    let bytes = read_message(&dir.join(format!("{name}.{}", format.extension())), format)?;
+
+    let path = dir.join(format!("{name}.digest"));
+    let raw = fs::read_to_string(&path).map_err(|err| format!("{}: {err}", path.display()))?;
+    let digest = normalize_hex_fixture(&raw).map_err(|err| format!("{}: {err}", path.display()))?;
+    let digest =
+        digest.try_into().map_err(|_| format!("{}: digest must be 32 bytes", path.display()))?;
+
+    Ok((bytes, digest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ucf::v1::ReasonCodes;
+
+    #[test]
+    fn written_fixtures_verify_and_detect_drift() {
+        let dir = std::env::temp_dir().join(format!("ucf-fixture-set-{}", std::process::id()));
+        let reason_codes = ReasonCodes { codes: vec!["coverage".to_string()] };
+
+        let mut set = FixtureSet::new();
+        set.push("reason_codes", "ucf.v1.ReasonCodes", "ucf-core", &reason_codes);
+        set.push_with_format(
+            "reason_codes_bin",
+            "ucf.v1.ReasonCodes",
+            "ucf-core",
+            FixtureFormat::Bin,
+            &reason_codes,
+        );
+        set.write_all(&dir).expect("fixtures write");
+        assert_eq!(set.verify_all(&dir), Ok(()));
+        assert_eq!(
+            fs::read_to_string(dir.join("reason_codes.hex")).unwrap(),
+            hex_fixture_body(&set.entries()[0].bytes)
+        );
+        assert_eq!(fs::read(dir.join("reason_codes_bin.bin")).unwrap(), set.entries()[1].bytes);
+
+        let mut drifted = FixtureSet::new();
+        let changed = ReasonCodes { codes: vec!["drift".to_string()] };
+        drifted.push("reason_codes", "ucf.v1.ReasonCodes", "ucf-core", &changed);
+        assert_eq!(
+            drifted.verify_all(&dir),
+            Err(FixtureError::BytesMismatch { name: "reason_codes".to_string() })
+        );
+
+        let mut other_domain = FixtureSet::new();
+        other_domain.push("reason_codes", "ucf.v1.ReasonCodes", "UCF:OTHER", &reason_codes);
+        assert!(matches!(other_domain.verify_all(&dir), Err(FixtureError::DigestMismatch { .. })));
+
+        fs::remove_dir_all(&dir).expect("temp dir removed");
+    }
+
+    #[test]
+    fn b64_fixture_matches_hex_twin() {
+        let dir = std::env::temp_dir().join(format!("ucf-fixture-b64-{}", std::process::id()));
+        let (bytes, digest) =
+            read_fixture(&crate::testing::fixture_dir(), "reason_codes_basic", FixtureFormat::Hex)
+                .expect("shipped fixture reads");
+        let reason_codes = ReasonCodes::decode(bytes.as_slice()).expect("fixture decodes");
+
+        let mut set = FixtureSet::new();
+        for format in [FixtureFormat::Hex, FixtureFormat::Base64Url] {
+            set.push_with_format("twin", "ucf.v1.ReasonCodes", "ucf-core", format, &reason_codes);
+        }
+        set.write_all(&dir).expect("fixtures write");
+        assert_eq!(set.verify_all(&dir), Ok(()));
+
+        let hex = read_message_file(&dir.join("twin.hex")).expect("hex twin reads");
+        let b64 = read_message_file(&dir.join("twin.b64")).expect("b64 twin reads");
+        assert_eq!(hex, bytes);
+        assert_eq!(b64, bytes);
+        assert!(
+            fs::metadata(dir.join("twin.b64")).unwrap().len()
+                < fs::metadata(dir.join("twin.hex")).unwrap().len()
+        );
+        assert_eq!(read_fixture(&dir, "twin", FixtureFormat::Base64Url), Ok((bytes, digest)));
+
+        fs::remove_file(dir.join("twin.hex")).unwrap();
+        assert_eq!(FixtureFormat::detect(&dir, "twin"), Some(FixtureFormat::Base64Url));
+        assert_eq!(FixtureFormat::detect(&dir, "missing"), None);
+
+        fs::remove_dir_all(&dir).expect("temp dir removed");
+    }
+}
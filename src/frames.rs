@@ -0,0 +1,473 @@
+//! Signal frame roll-ups and control frame chain checks.
+//!
+//! [`aggregate_signal_frames`] folds a window's frames into one frame:
+//!
+//! - Every count is summed per category, saturating at `u64::MAX`. A stats
+//!   block is present when any input carries it.
+//! - `human_stats.stop_invoked_flag` is set when any input sets it, and
+//!   `recovery_stage` is that of the last input with a non-empty stage.
+//! - `integrity_state` is the worst across inputs: `FAIL` over `DEGRADED` over
+//!   `OK`. An input with an unspecified or undefined state counts as
+//!   `DEGRADED`, since nothing vouches for it.
+//! - `reason_codes` and each block's `top_reason_codes` are merged by ranking
+//!   codes on how many inputs list them, most first and bytewise among ties,
+//!   keeping the first [`MAX_REASON_CODES`], and sorting those bytewise. The
+//!   result is independent of the order of the inputs.
+//!
+//! The aggregate's `epoch_id` is the window's and its `timestamp_ms` the latest
+//! input's. `signal_frame_id` and `signal_frame_digest` are left for the
+//! producer to assign.
+//!
+//! [`verify_control_frame_chain`] checks that control frames link by
+//! `prev_control_frame_digest` and that a frame with `deescalation_lock` set
+//! is never followed by a looser one. Strictness is compared per
+//! `threshold_modifiers` field in enum order, where a higher value is
+//! stricter: `approval_mode` (`NORMAL` < `STRICT`), the three `*_tightening`
+//! levels (`LOW` < `MED` < `HIGH`), and `cooldown_class` (`BASE` < `LONGER`).
+//! `UNSPECIFIED`, and a missing `threshold_modifiers`, is the loosest setting.
+//! Clearing `deescalation_lock` itself also counts as loosening, so a locked
+//! frame can only be followed by another locked frame.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::enums::EnumError;
+use crate::ucf::v1::{
+    BudgetStats, ControlFrame, DlpStats, ExecStats, HumanStats, IntegrityState, PolicyStats,
+    ReasonCodes, ReceiptStats, SignalFrame, TopReasonCodes, WindowRef,
+};
+
+/// Most reason codes an aggregated list keeps.
+pub const MAX_REASON_CODES: usize = 8;
+
+/// One frame for `window` summarising `frames`, per the [module docs](self).
+pub fn aggregate_signal_frames(frames: &[SignalFrame], window: WindowRef) -> SignalFrame {
+    SignalFrame {
+        epoch_id: window.epoch_id,
+        timestamp_ms: frames.iter().map(|frame| frame.timestamp_ms).max().unwrap_or_default(),
+        window: Some(window),
+        integrity_state: worst_integrity(frames) as i32,
+        policy_stats: blocks(frames, |frame| frame.policy_stats.as_ref()).map(|stats| {
+            PolicyStats {
+                deny_count: total(&stats, |s| s.deny_count),
+                allow_count: total(&stats, |s| s.allow_count),
+                require_approval_count: total(&stats, |s| s.require_approval_count),
+                require_simulation_count: total(&stats, |s| s.require_simulation_count),
+                top_reason_codes: top_codes(stats.iter().map(|s| s.top_reason_codes.as_ref())),
+            }
+        }),
+        dlp_stats: blocks(frames, |frame| frame.dlp_stats.as_ref()).map(|stats| DlpStats {
+            dlp_block_count: total(&stats, |s| s.dlp_block_count),
+            dlp_redact_count: total(&stats, |s| s.dlp_redact_count),
+            classify_upgrade_count: total(&stats, |s| s.classify_upgrade_count),
+            top_reason_codes: top_codes(stats.iter().map(|s| s.top_reason_codes.as_ref())),
+        }),
+        exec_stats: blocks(frames, |frame| frame.exec_stats.as_ref()).map(|stats| ExecStats {
+            timeout_count: total(&stats, |s| s.timeout_count),
+            partial_failure_count: total(&stats, |s| s.partial_failure_count),
+            tool_unavailable_count: total(&stats, |s| s.tool_unavailable_count),
+            top_reason_codes: top_codes(stats.iter().map(|s| s.top_reason_codes.as_ref())),
+        }),
+        budget_stats: blocks(frames, |frame| frame.budget_stats.as_ref()).map(|stats| {
+            BudgetStats {
+                near_exhaustion_count: total(&stats, |s| s.near_exhaustion_count),
+                chain_limit_hits: total(&stats, |s| s.chain_limit_hits),
+                concurrency_limit_hits: total(&stats, |s| s.concurrency_limit_hits),
+                top_reason_codes: top_codes(stats.iter().map(|s| s.top_reason_codes.as_ref())),
+            }
+        }),
+        human_stats: blocks(frames, |frame| frame.human_stats.as_ref()).map(|stats| HumanStats {
+            approval_denied_count: total(&stats, |s| s.approval_denied_count),
+            stop_invoked_flag: stats.iter().any(|s| s.stop_invoked_flag),
+            recovery_stage: stats
+                .iter()
+                .rev()
+                .map(|s| &s.recovery_stage)
+                .find(|stage| !stage.is_empty())
+                .cloned()
+                .unwrap_or_default(),
+        }),
+        receipt_stats: blocks(frames, |frame| frame.receipt_stats.as_ref()).map(|stats| {
+            ReceiptStats {
+                receipt_missing_count: total(&stats, |s| s.receipt_missing_count),
+                receipt_invalid_count: total(&stats, |s| s.receipt_invalid_count),
+                top_reason_codes: top_codes(stats.iter().map(|s| s.top_reason_codes.as_ref())),
+            }
+        }),
+        reason_codes: ranked_codes(frames.iter().map(|frame| frame.reason_codes.as_ref())),
+        ..Default::default()
+    }
+}
+
+fn worst_integrity(frames: &[SignalFrame]) -> IntegrityState {
+    let rank = |frame: &SignalFrame| match IntegrityState::try_from(frame.integrity_state) {
+        Ok(IntegrityState::Ok) => 0,
+        Ok(IntegrityState::Fail) => 2,
+        _ => 1,
+    };
+    match frames.iter().map(rank).max() {
+        None => IntegrityState::Unspecified,
+        Some(0) => IntegrityState::Ok,
+        Some(1) => IntegrityState::Degraded,
+        Some(_) => IntegrityState::Fail,
+    }
+}
+
+fn blocks<'a, T>(
+    frames: &'a [SignalFrame],
+    block: impl Fn(&'a SignalFrame) -> Option<&'a T>,
+) -> Option<Vec<&'a T>> {
+    let stats: Vec<&T> = frames.iter().filter_map(block).collect();
+    (!stats.is_empty()).then_some(stats)
+}
+
+fn total<T>(stats: &[&T], count: impl Fn(&T) -> u64) -> u64 {
+    stats.iter().fold(0, |sum, s| sum.saturating_add(count(s)))
+}
+
+fn top_codes<'a>(tops: impl Iterator<Item = Option<&'a TopReasonCodes>>) -> Option<TopReasonCodes> {
+    let tops: Vec<&TopReasonCodes> = tops.flatten().collect();
+    if tops.is_empty() {
+        return None;
+    }
+    Some(TopReasonCodes {
+        reason_codes: ranked_codes(tops.iter().map(|top| top.reason_codes.as_ref())),
+    })
+}
+
+fn ranked_codes<'a>(lists: impl Iterator<Item = Option<&'a ReasonCodes>>) -> Option<ReasonCodes> {
+    let mut counts: BTreeMap<&String, usize> = BTreeMap::new();
+    let mut present = false;
+    for list in lists.flatten() {
+        present = true;
+        for code in list.codes.iter().collect::<BTreeSet<_>>() {
+            *counts.entry(code).or_default() += 1;
+        }
+    }
+    if !present {
+        return None;
+    }
+    // `counts` iterates bytewise, and the stable sort keeps that order among ties.
+    let mut ranked: Vec<(&String, usize)> = counts.into_iter().collect();
+    ranked.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+    let kept: BTreeSet<&String> =
+        ranked.into_iter().take(MAX_REASON_CODES).map(|(code, _)| code).collect();
+    Some(ReasonCodes { codes: kept.into_iter().cloned().collect() })
+}
+
+/// Errors returned by [`verify_control_frame_chain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ControlChainError {
+    /// The frame has no `control_frame_digest`.
+    DigestMissing { control_frame_id: String },
+    /// `prev_control_frame_digest` differs from the previous frame's `control_frame_digest`.
+    PrevDigestMismatch { control_frame_id: String, expected: Vec<u8>, found: Vec<u8> },
+    /// A `threshold_modifiers` field holds an undefined enum value.
+    Enum { control_frame_id: String, error: EnumError },
+    /// The frame loosens `field` although the previous frame set `deescalation_lock`.
+    Loosened {
+        prev_control_frame_id: String,
+        control_frame_id: String,
+        field: &'static str,
+        from: &'static str,
+        to: &'static str,
+    },
+}
+
+impl fmt::Display for ControlChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ControlChainError::DigestMissing { control_frame_id } => {
+                write!(f, "control frame {control_frame_id} has no control_frame_digest")
+            }
+            ControlChainError::PrevDigestMismatch { control_frame_id, expected, found } => write!(
+                f,
+                "control frame {control_frame_id} prev_control_frame_digest {} does not match \
+                 previous frame digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+            ControlChainError::Enum { control_frame_id, error } => {
+                write!(f, "control frame {control_frame_id}: {error}")
+            }
+            ControlChainError::Loosened {
+                prev_control_frame_id,
+                control_frame_id,
+                field,
+                from,
+                to,
+            } => write!(
+                f,
+                "control frame {control_frame_id} loosens {field} from {from} to {to} under the \
+                 deescalation lock of {prev_control_frame_id}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ControlChainError {}
+
+/// One `threshold_modifiers` field's setting, ranked by strictness.
+struct Strictness {
+    field: &'static str,
+    rank: i32,
+    name: &'static str,
+}
+
+fn strictness(frame: &ControlFrame) -> Result<[Strictness; 5], ControlChainError> {
+    let modifiers = frame.threshold_modifiers.clone().unwrap_or_default();
+    let enum_error =
+        |error| ControlChainError::Enum { control_frame_id: frame.control_frame_id.clone(), error };
+    let approval = modifiers.approval_mode_enum().map_err(enum_error)?;
+    let novelty = modifiers.novelty_tightening_enum().map_err(enum_error)?;
+    let chain = modifiers.chain_tightening_enum().map_err(enum_error)?;
+    let export = modifiers.export_strictness_tightening_enum().map_err(enum_error)?;
+    let cooldown = modifiers.cooldown_class_enum().map_err(enum_error)?;
+    Ok([
+        Strictness { field: "approval_mode", rank: approval as i32, name: approval.as_str_name() },
+        Strictness {
+            field: "novelty_tightening",
+            rank: novelty as i32,
+            name: novelty.as_str_name(),
+        },
+        Strictness { field: "chain_tightening", rank: chain as i32, name: chain.as_str_name() },
+        Strictness {
+            field: "export_strictness_tightening",
+            rank: export as i32,
+            name: export.as_str_name(),
+        },
+        Strictness { field: "cooldown_class", rank: cooldown as i32, name: cooldown.as_str_name() },
+    ])
+}
+
+/// Confirm `frames` link by digest and never loosen under a deescalation lock.
+///
+/// Every frame must carry a `control_frame_digest`, and each frame after the
+/// first must carry the previous frame's as its `prev_control_frame_digest`.
+/// When a frame sets `deescalation_lock`, the next frame must keep it set and
+/// no strictness field of it may be looser; see the [module docs](self) for
+/// the ordering. The
+/// first frame's `prev_control_frame_digest` is not checked.
+pub fn verify_control_frame_chain(frames: &[ControlFrame]) -> Result<(), ControlChainError> {
+    for frame in frames {
+        if frame.control_frame_digest.is_none() {
+            return Err(ControlChainError::DigestMissing {
+                control_frame_id: frame.control_frame_id.clone(),
+            });
+        }
+    }
+    for pair in frames.windows(2) {
+        let (prev, frame) = (&pair[0], &pair[1]);
+        let expected = prev.control_frame_digest.as_ref().map(|digest| &digest.value);
+        let found = frame.prev_control_frame_digest.as_ref().map(|digest| &digest.value);
+        if found != expected {
+            return Err(ControlChainError::PrevDigestMismatch {
+                control_frame_id: frame.control_frame_id.clone(),
+                expected: expected.cloned().unwrap_or_default(),
+                found: found.cloned().unwrap_or_default(),
+            });
+        }
+        if !prev.deescalation_lock {
+            continue;
+        }
+        for (before, after) in strictness(prev)?.iter().zip(strictness(frame)?.iter()) {
+            if after.rank < before.rank {
+                return Err(ControlChainError::Loosened {
+                    prev_control_frame_id: prev.control_frame_id.clone(),
+                    control_frame_id: frame.control_frame_id.clone(),
+                    field: after.field,
+                    from: before.name,
+                    to: after.name,
+                });
+            }
+        }
+        if !frame.deescalation_lock {
+            return Err(ControlChainError::Loosened {
+                prev_control_frame_id: prev.control_frame_id.clone(),
+                control_frame_id: frame.control_frame_id.clone(),
+                field: "deescalation_lock",
+                from: "true",
+                to: "false",
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::decode_fixture;
+    use crate::ucf::v1::{ApprovalMode, CooldownClass, Digest32, LevelClass, WindowKind};
+
+    fn short_frame() -> SignalFrame {
+        decode_fixture("signal_frame_short_window")
+    }
+
+    fn meso_window() -> WindowRef {
+        WindowRef {
+            window_id: "window-medium-1".to_string(),
+            window_kind: WindowKind::Medium as i32,
+            epoch_id: 43,
+            digest: None,
+        }
+    }
+
+    #[test]
+    fn sums_counts_and_keeps_worst_integrity() {
+        let first = short_frame();
+        let mut second = short_frame();
+        second.timestamp_ms += 1_000;
+        second.integrity_state = IntegrityState::Degraded as i32;
+        second.dlp_stats = None;
+        second.human_stats.as_mut().unwrap().stop_invoked_flag = true;
+        second.human_stats.as_mut().unwrap().recovery_stage = String::new();
+
+        let aggregate = aggregate_signal_frames(&[first.clone(), second.clone()], meso_window());
+        assert_eq!(aggregate.window, Some(meso_window()));
+        assert_eq!(aggregate.epoch_id, 43);
+        assert_eq!(aggregate.timestamp_ms, second.timestamp_ms);
+        assert_eq!(aggregate.integrity_state, IntegrityState::Degraded as i32);
+        assert_eq!(aggregate.signal_frame_digest, None);
+
+        let policy = aggregate.policy_stats.unwrap();
+        assert_eq!((policy.deny_count, policy.allow_count), (6, 14));
+        assert_eq!(policy.top_reason_codes, first.policy_stats.clone().unwrap().top_reason_codes);
+        assert_eq!(aggregate.dlp_stats.unwrap().dlp_block_count, 2);
+        let human = aggregate.human_stats.unwrap();
+        assert!(human.stop_invoked_flag);
+        assert_eq!(human.recovery_stage, "pilot");
+        assert_eq!(aggregate.reason_codes, first.reason_codes);
+
+        let mut failed = short_frame();
+        failed.integrity_state = IntegrityState::Fail as i32;
+        let mut unknown = short_frame();
+        unknown.integrity_state = 99;
+        let state =
+            |frames: &[SignalFrame]| aggregate_signal_frames(frames, meso_window()).integrity_state;
+        assert_eq!(state(&[first.clone(), failed, second]), IntegrityState::Fail as i32);
+        assert_eq!(state(&[first.clone(), unknown]), IntegrityState::Degraded as i32);
+        assert_eq!(state(&[first]), IntegrityState::Ok as i32);
+        assert_eq!(state(&[]), IntegrityState::Unspecified as i32);
+    }
+
+    #[test]
+    fn ranks_and_caps_reason_codes() {
+        let frame = |codes: &[&str]| SignalFrame {
+            reason_codes: Some(ReasonCodes::sorted(codes)),
+            ..Default::default()
+        };
+        let rare: Vec<String> = (0..MAX_REASON_CODES).map(|i| format!("a-rare-{i}")).collect();
+        let rare: Vec<&str> = rare.iter().map(String::as_str).collect();
+        let frames =
+            [frame(&rare), frame(&["z-common", "y-common"]), frame(&["y-common", "z-common"])];
+
+        let codes = aggregate_signal_frames(&frames, meso_window()).reason_codes.unwrap().codes;
+        assert_eq!(codes.len(), MAX_REASON_CODES);
+        assert_eq!(codes[MAX_REASON_CODES - 2..], ["y-common", "z-common"]);
+        assert!(!codes.contains(&rare[MAX_REASON_CODES - 1].to_string()));
+
+        let mut reversed = frames.clone();
+        reversed.reverse();
+        assert_eq!(
+            aggregate_signal_frames(&reversed, meso_window()).reason_codes.unwrap().codes,
+            codes
+        );
+        assert_eq!(aggregate_signal_frames(&[], meso_window()).reason_codes, None);
+    }
+
+    fn control_chain(len: u8) -> Vec<ControlFrame> {
+        let template: ControlFrame = decode_fixture("control_frame_m1_overlays_on");
+        (0..len)
+            .map(|i| ControlFrame {
+                control_frame_id: format!("ctrl-{i}"),
+                control_frame_digest: Some(Digest32 { value: vec![i + 1; 32] }),
+                prev_control_frame_digest: Some(Digest32 { value: vec![i; 32] }),
+                ..template.clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn control_chain_links_and_holds_under_lock() {
+        let mut frames = control_chain(3);
+        assert_eq!(verify_control_frame_chain(&frames), Ok(()));
+
+        frames[2].threshold_modifiers.as_mut().unwrap().chain_tightening = LevelClass::High as i32;
+        assert_eq!(verify_control_frame_chain(&frames), Ok(()));
+
+        frames[2].prev_control_frame_digest = Some(Digest32 { value: vec![0xFF; 32] });
+        assert_eq!(
+            verify_control_frame_chain(&frames),
+            Err(ControlChainError::PrevDigestMismatch {
+                control_frame_id: "ctrl-2".into(),
+                expected: vec![2; 32],
+                found: vec![0xFF; 32],
+            })
+        );
+
+        let mut frames = control_chain(2);
+        frames[1].control_frame_digest = None;
+        assert_eq!(
+            verify_control_frame_chain(&frames),
+            Err(ControlChainError::DigestMissing { control_frame_id: "ctrl-1".into() })
+        );
+    }
+
+    #[test]
+    fn control_chain_reports_loosening_under_lock() {
+        let mut frames = control_chain(3);
+        frames[1].deescalation_lock = false;
+        frames[2].threshold_modifiers.as_mut().unwrap().approval_mode = ApprovalMode::Normal as i32;
+        assert_eq!(
+            verify_control_frame_chain(&frames),
+            Err(ControlChainError::Loosened {
+                prev_control_frame_id: "ctrl-0".into(),
+                control_frame_id: "ctrl-1".into(),
+                field: "deescalation_lock",
+                from: "true",
+                to: "false",
+            })
+        );
+        assert_eq!(verify_control_frame_chain(&frames[1..]), Ok(()));
+
+        frames[1].deescalation_lock = true;
+        assert_eq!(
+            verify_control_frame_chain(&frames),
+            Err(ControlChainError::Loosened {
+                prev_control_frame_id: "ctrl-1".into(),
+                control_frame_id: "ctrl-2".into(),
+                field: "approval_mode",
+                from: "APPROVAL_MODE_STRICT",
+                to: "APPROVAL_MODE_NORMAL",
+            })
+        );
+
+        let mut frames = control_chain(2);
+        frames[1].threshold_modifiers.as_mut().unwrap().cooldown_class = CooldownClass::Base as i32;
+        assert!(matches!(
+            verify_control_frame_chain(&frames),
+            Err(ControlChainError::Loosened { field: "cooldown_class", .. })
+        ));
+
+        frames[1].threshold_modifiers = None;
+        assert!(matches!(
+            verify_control_frame_chain(&frames),
+            Err(ControlChainError::Loosened {
+                field: "approval_mode",
+                to: "APPROVAL_MODE_UNSPECIFIED",
+                ..
+            })
+        ));
+
+        frames[1].threshold_modifiers = frames[0].threshold_modifiers.clone();
+        frames[1].threshold_modifiers.as_mut().unwrap().novelty_tightening = 99;
+        assert_eq!(
+            verify_control_frame_chain(&frames),
+            Err(ControlChainError::Enum {
+                control_frame_id: "ctrl-1".into(),
+                error: EnumError { field: "novelty_tightening", value: 99 },
+            })
+        );
+    }
+}
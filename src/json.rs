@@ -0,0 +1,105 @@
+//! Deterministic JSON rendering of UCF messages for inspection and tooling.
+//!
+//! [`to_canonical_json`] emits compact JSON with object keys sorted bytewise and
+//! every protobuf `bytes` field encoded as unpadded base64url, so the same
+//! message always renders to identical text that can be diffed in review.
+//! Field names are the generated Rust names, enum fields are their proto
+//! variant names, and oneofs are an object keyed by the variant name.
+//!
+//! JSON is never digested: digests are always taken over [`crate::canonical_bytes`].
+
+use prost::Message;
+use serde::Serialize;
+use serde_json::Value;
+
+pub use crate::codec::base64url;
+
+/// Render `message` as canonical JSON.
+pub fn to_canonical_json<M: Message + Serialize>(message: &M) -> String {
+    let value = serde_json::to_value(message).expect("generated messages serialize to JSON");
+    let mut out = String::new();
+    write_value(&value, &mut out);
+    out
+}
+
+fn write_value(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_value(item, out);
+            }
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ucf::v1::{Digest32, ReasonCodes, Ref, Signature, UcfEnvelope};
+
+    #[test]
+    fn base64url_matches_rfc4648() {
+        assert_eq!(base64url(b""), "");
+        assert_eq!(base64url(b"f"), "Zg");
+        assert_eq!(base64url(b"fo"), "Zm8");
+        assert_eq!(base64url(b"foo"), "Zm9v");
+        assert_eq!(base64url(b"foob"), "Zm9vYg");
+        assert_eq!(base64url(&[0xfb, 0xff, 0xbf]), "-_-_");
+    }
+
+    #[test]
+    fn canonical_json_sorts_keys_and_encodes_bytes() {
+        let reference = Ref { uri: "ucf://a".to_string(), label: "a".to_string() };
+        assert_eq!(to_canonical_json(&reference), r#"{"label":"a","uri":"ucf://a"}"#);
+
+        let envelope = UcfEnvelope {
+            epoch_id: "epoch-1".to_string(),
+            nonce: vec![0x01, 0x02, 0x03],
+            signature: Some(Signature {
+                algorithm: "ed25519".to_string(),
+                signer: vec![0xfb, 0xff],
+                signature: Vec::new(),
+            }),
+            payload_digest: Some(Digest32 { value: vec![0x10; 4] }),
+            msg_type: 1,
+            payload: Vec::new(),
+        };
+        let json = to_canonical_json(&envelope);
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"epoch_id":"epoch-1","msg_type":"MSG_TYPE_CANONICAL_INTENT","#,
+                r#""nonce":"AQID","payload":"","#,
+                r#""payload_digest":{"value":"EBAQEA"},"#,
+                r#""signature":{"algorithm":"ed25519","signature":"","signer":"-_8"}}"#
+            )
+        );
+        assert_eq!(to_canonical_json(&envelope), json, "rendering is deterministic");
+    }
+
+    #[test]
+    fn repeated_fields_keep_their_order() {
+        let codes = ReasonCodes { codes: vec!["b".to_string(), "a".to_string()] };
+        assert_eq!(to_canonical_json(&codes), r#"{"codes":["b","a"]}"#);
+    }
+}
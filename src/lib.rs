@@ -1,8 +1,57 @@
 #![forbid(unsafe_code)]
 
 //! UCF protocol core types and deterministic helpers.
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
 use blake3::Hasher;
 use prost::Message;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+pub mod approval;
+pub mod assets;
+pub mod biophys;
+pub mod canonicalize;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+pub mod codec;
+pub mod constraints;
+pub mod crypto;
+pub mod digest;
+#[cfg(feature = "dynamic")]
+pub mod dynamic;
+pub mod enums;
+pub mod envelope;
+pub mod experience;
+pub mod fixedpoint;
+pub mod fixtures;
+pub mod frames;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod merkle;
+pub mod milestone;
+pub mod milestone_graph;
+pub mod multihash;
+pub mod parse;
+pub mod policy;
+pub mod reason_codes;
+pub mod refs;
+pub mod replay;
+pub mod replay_guard;
+pub mod resolve;
+pub mod sep;
+#[cfg(feature = "serde")]
+mod serde_fields;
+pub mod testing;
+#[cfg(feature = "testvectors")]
+pub mod testvectors;
+pub mod tooling;
+pub mod trust;
+pub mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub mod ucf {
     pub mod v1 {
@@ -10,26 +59,568 @@ pub mod ucf {
     }
 }
 
+/// Encoded `google.protobuf.FileDescriptorSet` of every `ucf.v1` proto.
+///
+/// For gRPC server reflection and dynamic decoding (see the `dynamic`
+/// feature) without shipping the `.proto` sources.
+pub const FILE_DESCRIPTOR_SET: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/ucf_descriptor.bin"));
+
+/// Invoke `$callback! { "ucf.v1.Name" => $crate::ucf::v1::Name, ... }` with
+/// every top-level message, keyed by its schema id.
+///
+/// This is the one registry of schema ids; the `wasm` exports and `ucf-tool`
+/// build their per-schema dispatch from it. `PVGSReceipt` keeps the proto
+/// spelling rather than prost's `PvgsReceipt`.
+#[macro_export]
+macro_rules! for_each_schema {
+    ($callback:ident) => {
+        $callback! {
+            "ucf.v1.ApprovalArtifactPackage" => $crate::ucf::v1::ApprovalArtifactPackage,
+            "ucf.v1.ApprovalDecision" => $crate::ucf::v1::ApprovalDecision,
+            "ucf.v1.AssetDigest" => $crate::ucf::v1::AssetDigest,
+            "ucf.v1.AssetManifest" => $crate::ucf::v1::AssetManifest,
+            "ucf.v1.CanonicalIntent" => $crate::ucf::v1::CanonicalIntent,
+            "ucf.v1.ChannelParamsSetPayload" => $crate::ucf::v1::ChannelParamsSetPayload,
+            "ucf.v1.CompletenessReport" => $crate::ucf::v1::CompletenessReport,
+            "ucf.v1.ConnectivityGraphPayload" => $crate::ucf::v1::ConnectivityGraphPayload,
+            "ucf.v1.ConsistencyFeedback" => $crate::ucf::v1::ConsistencyFeedback,
+            "ucf.v1.ControlFrame" => $crate::ucf::v1::ControlFrame,
+            "ucf.v1.ExperienceRecord" => $crate::ucf::v1::ExperienceRecord,
+            "ucf.v1.MacroMilestone" => $crate::ucf::v1::MacroMilestone,
+            "ucf.v1.MesoMilestone" => $crate::ucf::v1::MesoMilestone,
+            "ucf.v1.MicroMilestone" => $crate::ucf::v1::MicroMilestone,
+            "ucf.v1.MicrocircuitConfigEvidence" => $crate::ucf::v1::MicrocircuitConfigEvidence,
+            "ucf.v1.MorphologySetPayload" => $crate::ucf::v1::MorphologySetPayload,
+            "ucf.v1.PolicyDecision" => $crate::ucf::v1::PolicyDecision,
+            "ucf.v1.PolicyQuery" => $crate::ucf::v1::PolicyQuery,
+            "ucf.v1.ProofReceipt" => $crate::ucf::v1::ProofReceipt,
+            "ucf.v1.PVGSReceipt" => $crate::ucf::v1::PvgsReceipt,
+            "ucf.v1.ReasonCodes" => $crate::ucf::v1::ReasonCodes,
+            "ucf.v1.ReplayPlan" => $crate::ucf::v1::ReplayPlan,
+            "ucf.v1.ReplayRunEvidence" => $crate::ucf::v1::ReplayRunEvidence,
+            "ucf.v1.SepEvent" => $crate::ucf::v1::SepEvent,
+            "ucf.v1.SessionSeal" => $crate::ucf::v1::SessionSeal,
+            "ucf.v1.SignalFrame" => $crate::ucf::v1::SignalFrame,
+            "ucf.v1.SynapseParamsSetPayload" => $crate::ucf::v1::SynapseParamsSetPayload,
+            "ucf.v1.ToolOnboardingEvent" => $crate::ucf::v1::ToolOnboardingEvent,
+            "ucf.v1.ToolRegistryContainer" => $crate::ucf::v1::ToolRegistryContainer,
+            "ucf.v1.UcfEnvelope" => $crate::ucf::v1::UcfEnvelope,
+        }
+    };
+}
+
+macro_rules! schema_ids {
+    ($($schema:literal => $message:path,)*) => {
+        /// Schema id of every message in [`for_each_schema!`].
+        pub const SCHEMA_IDS: &[&str] = &[$($schema),*];
+    };
+}
+
+for_each_schema!(schema_ids);
+
+use ucf::v1::Digest32;
+
 pub use ucf::v1::{
     AssetDigest, AssetKind, AssetManifest, ChannelParams, ChannelParamsSetPayload, Compartment,
     CompartmentKind, ConnEdge, ConnectivityGraphPayload, LabelKv, ModChannel, MorphNeuron,
     MorphologySetPayload, SynKind, SynType, SynapseParams, SynapseParamsSetPayload,
 };
 
+pub use canonicalize::Canonicalize;
+#[cfg(feature = "cbor")]
+pub use cbor::{from_cbor, to_canonical_cbor};
+pub use digest::{CanonicalDigest, ContentDigest};
+pub use enums::{validate_enums, EnumError, ValidateEnums};
+#[cfg(feature = "json")]
+pub use json::to_canonical_json;
+pub use multihash::{digest32_multihash, parse_multihash, MultihashError};
+pub use parse::{canonical_bytes_strict, parse_strict, CanonError, ParseError};
+pub use reason_codes::sorted_codes;
+
 /// Canonically encode a protobuf message using deterministic field ordering.
 ///
 /// The caller is responsible for ordering any repeated fields that should be
-/// treated as sets before invoking this function.
+/// treated as sets before invoking this function; [`canonical_bytes_sorted`]
+/// does that for messages implementing [`Canonicalize`]. Empty repeated fields are
+/// omitted, so `vec![]` and a defaulted field encode identically; an optional
+/// sub-message set to an empty value (e.g. `Some(ReasonCodes::default())`) is
+/// still encoded and differs from `None`.
 pub fn canonical_bytes<M: Message>(message: &M) -> Vec<u8> {
-    message.encode_to_vec()
+    let mut buf = Vec::new();
+    canonical_bytes_into(message, &mut buf);
+    buf
+}
+
+/// Write [`canonical_bytes`] of `message` into `buf`, replacing its contents.
+///
+/// `buf` keeps its allocation, so a batch of messages can be encoded through
+/// one buffer without allocating per message.
+pub fn canonical_bytes_into<M: Message>(message: &M, buf: &mut Vec<u8>) {
+    buf.clear();
+    buf.reserve(canonical_len(message));
+    message.encode(buf).expect("Vec grows to fit the encoded message");
+}
+
+/// Whether `bytes` is exactly the [`canonical_bytes`] of the `M` it decodes to.
+///
+/// Protobuf accepts many encodings of the same message (fields out of order,
+/// repeated scalar fields, default values written explicitly), and each would
+/// digest differently. Validators should reject input for which this returns
+/// `false` before digesting it.
+pub fn is_canonical<M: Message + Default>(bytes: &[u8]) -> Result<bool, prost::DecodeError> {
+    let message = M::decode(bytes)?;
+    Ok(canonical_bytes(&message) == bytes)
+}
+
+/// [`canonical_bytes`] of a copy of `message` with its set-semantic repeated
+/// fields sorted by [`Canonicalize::canonicalize`].
+pub fn canonical_bytes_sorted<M: Message + Canonicalize + Clone>(message: &M) -> Vec<u8> {
+    let mut sorted = message.clone();
+    sorted.canonicalize();
+    canonical_bytes(&sorted)
+}
+
+/// Exact length in bytes of [`canonical_bytes`] for `message`.
+///
+/// Use this to pre-size buffers when streaming canonical encodings.
+pub fn canonical_len<M: Message>(message: &M) -> usize {
+    message.encoded_len()
+}
+
+/// Every domain tag the UCF crates hash or sign under.
+///
+/// Pass a variant wherever a domain string is expected; spelling a tag out by
+/// hand risks a typo that silently changes every digest taken under it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Domain {
+    Core,
+    McConfig,
+    AssetMorphology,
+    AssetChannelParams,
+    AssetSynapseParams,
+    AssetConnectivity,
+    AssetManifest,
+    MilestoneMicro,
+    MilestoneMeso,
+    MilestoneMacro,
+    MerkleLeaf,
+    MerkleNode,
+    MerkleEmpty,
+    VrfExperienceRecord,
+    VrfCommit,
+    VrfRotate,
+    PvgsRecordDigest,
+    PvgsRejectionDigest,
+    SepEvent,
+    VrfTenantRecord,
+    PvgsTenantRecordDigest,
+    ToolRegistry,
+    ExperienceRecord,
+    ApprovalPackage,
+    PvgsReceiptSignature,
+    VrfSignerProbe,
+}
+
+impl Domain {
+    pub const ALL: &'static [Domain] = &[
+        Domain::Core,
+        Domain::McConfig,
+        Domain::AssetMorphology,
+        Domain::AssetChannelParams,
+        Domain::AssetSynapseParams,
+        Domain::AssetConnectivity,
+        Domain::AssetManifest,
+        Domain::MilestoneMicro,
+        Domain::MilestoneMeso,
+        Domain::MilestoneMacro,
+        Domain::MerkleLeaf,
+        Domain::MerkleNode,
+        Domain::MerkleEmpty,
+        Domain::VrfExperienceRecord,
+        Domain::VrfCommit,
+        Domain::VrfRotate,
+        Domain::PvgsRecordDigest,
+        Domain::PvgsRejectionDigest,
+        Domain::SepEvent,
+        Domain::VrfTenantRecord,
+        Domain::PvgsTenantRecordDigest,
+        Domain::ToolRegistry,
+        Domain::ExperienceRecord,
+        Domain::ApprovalPackage,
+        Domain::PvgsReceiptSignature,
+        Domain::VrfSignerProbe,
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Domain::Core => "ucf-core",
+            Domain::McConfig => "UCF:HASH:MC_CONFIG",
+            Domain::AssetMorphology => "UCF:ASSET:MORPH",
+            Domain::AssetChannelParams => "UCF:ASSET:CHANNEL_PARAMS",
+            Domain::AssetSynapseParams => "UCF:ASSET:SYN_PARAMS",
+            Domain::AssetConnectivity => "UCF:ASSET:CONNECTIVITY",
+            Domain::AssetManifest => "UCF:ASSET:MANIFEST",
+            Domain::MilestoneMicro => "UCF:MILESTONE:MICRO",
+            Domain::MilestoneMeso => "UCF:MILESTONE:MESO",
+            Domain::MilestoneMacro => "UCF:MILESTONE:MACRO",
+            Domain::MerkleLeaf => "UCF:MERKLE:LEAF",
+            Domain::MerkleNode => "UCF:MERKLE:NODE",
+            Domain::MerkleEmpty => "UCF:MERKLE:EMPTY",
+            Domain::VrfExperienceRecord => "UCF:VRF:EXPERIENCE_RECORD",
+            Domain::VrfCommit => "UCF:VRF:COMMIT",
+            Domain::VrfRotate => "UCF:VRF:ROTATE",
+            Domain::PvgsRecordDigest => "UCF:PVGS:RECORD_DIGEST",
+            Domain::PvgsRejectionDigest => "UCF:PVGS:REJECTION_DIGEST",
+            Domain::SepEvent => "UCF:SEP:EVENT",
+            Domain::VrfTenantRecord => "UCF:VRF:TENANT_RECORD",
+            Domain::PvgsTenantRecordDigest => "UCF:PVGS:TENANT_RECORD_DIGEST",
+            Domain::ToolRegistry => "UCF:TOOL:REGISTRY",
+            Domain::ExperienceRecord => "UCF:EXPERIENCE:RECORD",
+            Domain::ApprovalPackage => "UCF:APPROVAL:PACKAGE",
+            Domain::PvgsReceiptSignature => "UCF:PVGS:RECEIPT_SIGNATURE",
+            Domain::VrfSignerProbe => "UCF:VRF:SIGNER_PROBE",
+        }
+    }
+
+    pub const fn as_bytes(self) -> &'static [u8] {
+        self.as_str().as_bytes()
+    }
+}
+
+impl AsRef<str> for Domain {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<Domain> for &'static str {
+    fn from(domain: Domain) -> Self {
+        domain.as_str()
+    }
+}
+
+impl fmt::Display for Domain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Compute a 32-byte digest using BLAKE3 over DOMAIN || schema_id || schema_version || bytes.
-pub fn digest32(domain: &str, schema_id: &str, schema_version: &str, bytes: &[u8]) -> [u8; 32] {
-    let mut hasher = Hasher::new();
-    hasher.update(domain.as_bytes());
-    hasher.update(schema_id.as_bytes());
-    hasher.update(schema_version.as_bytes());
-    hasher.update(bytes);
-    *hasher.finalize().as_bytes()
+///
+/// `domain` is usually a [`Domain`]; any string is accepted for domains
+/// defined outside this crate.
+pub fn digest32(
+    domain: impl AsRef<str>,
+    schema_id: &str,
+    schema_version: &str,
+    bytes: &[u8],
+) -> [u8; 32] {
+    digest32_with(DigestAlgo::Blake3, domain, schema_id, schema_version, bytes)
+}
+
+/// Hash function behind a 32-byte digest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DigestAlgo {
+    #[default]
+    Blake3,
+    Sha256,
+}
+
+impl DigestAlgo {
+    /// `domain` with this algorithm bound into it.
+    ///
+    /// BLAKE3 leaves `domain` unchanged so [`digest32`] keeps its existing
+    /// outputs; every other algorithm appends `:<NAME>`, e.g. `ucf-core:SHA256`,
+    /// so a digest cannot be replayed as one taken with a different algorithm.
+    pub fn bind_domain(self, domain: &str) -> Cow<'_, str> {
+        match self {
+            DigestAlgo::Blake3 => Cow::Borrowed(domain),
+            DigestAlgo::Sha256 => Cow::Owned(format!("{domain}:SHA256")),
+        }
+    }
+}
+
+/// [`digest32`] computed with `algo` over its bound domain
+/// ([`DigestAlgo::bind_domain`]) || schema_id || schema_version || bytes.
+pub fn digest32_with(
+    algo: DigestAlgo,
+    domain: impl AsRef<str>,
+    schema_id: &str,
+    schema_version: &str,
+    bytes: &[u8],
+) -> [u8; 32] {
+    let domain = algo.bind_domain(domain.as_ref());
+    let parts = [domain.as_bytes(), schema_id.as_bytes(), schema_version.as_bytes(), bytes];
+    match algo {
+        DigestAlgo::Blake3 => {
+            let mut hasher = Hasher::new();
+            for part in parts {
+                hasher.update(part);
+            }
+            *hasher.finalize().as_bytes()
+        }
+        DigestAlgo::Sha256 => {
+            let mut hasher = Sha256::new();
+            for part in parts {
+                hasher.update(part);
+            }
+            hasher.finalize().into()
+        }
+    }
+}
+
+/// Recompute [`digest32`] over `bytes` and compare it to `expected` in constant time.
+pub fn verify_digest32(
+    domain: impl AsRef<str>,
+    schema_id: &str,
+    schema_version: &str,
+    bytes: &[u8],
+    expected: &[u8; 32],
+) -> bool {
+    digest32(domain, schema_id, schema_version, bytes).ct_eq(expected).into()
+}
+
+/// A [`Digest32`] known to hold exactly 32 bytes, displayed as lowercase hex.
+///
+/// The generated `Digest32` keeps its `Vec<u8>` for wire compatibility; convert
+/// with `TryFrom<&Digest32>` at the boundary to catch wrong-length digests.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Digest32Bytes(pub [u8; 32]);
+
+impl Digest32Bytes {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+/// Errors converting into a [`Digest32Bytes`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Digest32Error {
+    /// The digest is not 32 bytes long.
+    WrongLength(usize),
+    /// The string is not valid hex.
+    InvalidHex,
+}
+
+impl fmt::Display for Digest32Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Digest32Error::WrongLength(len) => write!(f, "digest must be 32 bytes, found {len}"),
+            Digest32Error::InvalidHex => write!(f, "digest is not valid hex"),
+        }
+    }
+}
+
+impl std::error::Error for Digest32Error {}
+
+impl fmt::Display for Digest32Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl FromStr for Digest32Bytes {
+    type Err = Digest32Error;
+
+    /// Parse 64 hex digits; uppercase is accepted, `Display` always emits lowercase.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(|_| Digest32Error::InvalidHex)?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl TryFrom<&[u8]> for Digest32Bytes {
+    type Error = Digest32Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        bytes.try_into().map(Self).map_err(|_| Digest32Error::WrongLength(bytes.len()))
+    }
+}
+
+impl TryFrom<&Digest32> for Digest32Bytes {
+    type Error = Digest32Error;
+
+    fn try_from(digest: &Digest32) -> Result<Self, Self::Error> {
+        Self::try_from(digest.value.as_slice())
+    }
+}
+
+impl From<[u8; 32]> for Digest32Bytes {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Digest32Bytes> for Digest32 {
+    fn from(digest: Digest32Bytes) -> Self {
+        Digest32 { value: digest.0.to_vec() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ucf::v1::{PolicyDecision, ReasonCodes, Ref};
+
+    #[test]
+    fn schema_registry_covers_fixtures_and_payloads() {
+        let fixtures = testing::FIXTURES.iter().map(|spec| spec.schema);
+        let payloads = envelope::PAYLOAD_SCHEMAS.iter().map(|(_, schema)| *schema);
+        for schema in fixtures.chain(payloads) {
+            assert!(SCHEMA_IDS.contains(&schema), "{schema}");
+        }
+    }
+
+    #[test]
+    fn file_descriptor_set_describes_the_protos() {
+        let set = prost_types::FileDescriptorSet::decode(FILE_DESCRIPTOR_SET)
+            .expect("descriptor set decodes");
+        let policy = set
+            .file
+            .iter()
+            .find(|file| file.name() == "ucf/v1/policy.proto")
+            .expect("policy.proto is described");
+        assert_eq!(policy.package(), "ucf.v1");
+        assert!(policy.message_type.iter().any(|message| message.name() == "PolicyDecision"));
+        assert!(set.file.iter().all(|file| file.package() == "ucf.v1"));
+    }
+
+    #[test]
+    fn digest32_bytes_round_trips_hex_and_wire_form() {
+        let digest = Digest32Bytes(digest32("ucf-core", "ucf.v1.ReasonCodes", "1", b"bytes"));
+        let text = digest.to_string();
+        assert_eq!(text.len(), 64);
+        assert_eq!(text, text.to_lowercase());
+        assert_eq!(text.parse::<Digest32Bytes>(), Ok(digest));
+        assert_eq!(text.to_uppercase().parse::<Digest32Bytes>(), Ok(digest));
+
+        let wire: Digest32 = digest.into();
+        assert_eq!(Digest32Bytes::try_from(&wire), Ok(digest));
+
+        let short = Digest32 { value: vec![0x10; 31] };
+        assert_eq!(Digest32Bytes::try_from(&short), Err(Digest32Error::WrongLength(31)));
+        assert_eq!("00".parse::<Digest32Bytes>(), Err(Digest32Error::WrongLength(1)));
+        assert_eq!("zz".repeat(32).parse::<Digest32Bytes>(), Err(Digest32Error::InvalidHex));
+    }
+
+    #[test]
+    fn is_canonical_rejects_alternate_encodings() {
+        let reference = Ref { uri: "ucf://a".to_string(), label: "a".to_string() };
+        let bytes = canonical_bytes(&reference);
+        assert_eq!(is_canonical::<Ref>(&bytes), Ok(true));
+        assert_eq!(is_canonical::<Ref>(&[]), Ok(true));
+
+        // label (field 2) written before uri (field 1).
+        let reordered = [&bytes[9..], &bytes[..9]].concat();
+        assert_eq!(Ref::decode(reordered.as_slice()).unwrap(), reference);
+        assert_eq!(is_canonical::<Ref>(&reordered), Ok(false));
+
+        // An explicit empty label, which canonical encoding omits.
+        let explicit_default = [&bytes[..9], &[0x12, 0x00]].concat();
+        assert_eq!(is_canonical::<Ref>(&explicit_default), Ok(false));
+
+        // The same field written twice; the last occurrence wins on decode.
+        let duplicated = [&bytes[..], &bytes[9..]].concat();
+        assert_eq!(is_canonical::<Ref>(&duplicated), Ok(false));
+
+        assert!(is_canonical::<Ref>(&[0x0a, 0x05]).is_err());
+    }
+
+    #[test]
+    fn verify_digest32_checks_every_input() {
+        let digest = digest32("ucf-core", "ucf.v1.ReasonCodes", "1", b"bytes");
+        assert!(verify_digest32("ucf-core", "ucf.v1.ReasonCodes", "1", b"bytes", &digest));
+        assert!(!verify_digest32("ucf-core", "ucf.v1.ReasonCodes", "2", b"bytes", &digest));
+        assert!(!verify_digest32("ucf-core", "ucf.v1.ReasonCodes", "1", b"byte", &digest));
+        assert!(!verify_digest32("ucf-core", "ucf.v1.ReasonCodes", "1", b"bytes", &[0u8; 32]));
+    }
+
+    #[test]
+    fn canonical_len_matches_encoded_bytes() {
+        let empty = ReasonCodes::default();
+        assert_eq!(canonical_len(&empty), canonical_bytes(&empty).len());
+
+        let decision = PolicyDecision {
+            decision: 1,
+            reason_codes: Some(ReasonCodes {
+                codes: vec!["missing-proof".to_string(), "scope-limited".to_string()],
+            }),
+            constraints: None,
+        };
+        let bytes = canonical_bytes(&decision);
+        assert_eq!(canonical_len(&decision), bytes.len());
+        assert_eq!(bytes.capacity(), bytes.len(), "buffer should be sized exactly");
+    }
+
+    #[test]
+    fn canonical_bytes_into_reuses_the_buffer() {
+        let long = ReasonCodes { codes: vec!["x".repeat(64), "y".repeat(64)] };
+        let short = ReasonCodes { codes: vec!["z".to_string()] };
+
+        let mut buf = vec![0xFF; 8];
+        canonical_bytes_into(&long, &mut buf);
+        assert_eq!(buf, canonical_bytes(&long));
+        let (ptr, capacity) = (buf.as_ptr(), buf.capacity());
+
+        canonical_bytes_into(&short, &mut buf);
+        assert_eq!(buf, canonical_bytes(&short), "previous contents are cleared");
+        assert_eq!((buf.as_ptr(), buf.capacity()), (ptr, capacity), "allocation is reused");
+    }
+
+    #[test]
+    fn digest32_with_binds_the_algorithm() {
+        let bytes = b"bytes";
+        assert_eq!(
+            digest32_with(DigestAlgo::Blake3, "ucf-core", "ucf.v1.ReasonCodes", "1", bytes),
+            digest32("ucf-core", "ucf.v1.ReasonCodes", "1", bytes)
+        );
+
+        let sha256 =
+            digest32_with(DigestAlgo::Sha256, "ucf-core", "ucf.v1.ReasonCodes", "1", bytes);
+        let unbound: [u8; 32] = Sha256::digest(b"ucf-coreucf.v1.ReasonCodes1bytes").into();
+        let bound: [u8; 32] = Sha256::digest(b"ucf-core:SHA256ucf.v1.ReasonCodes1bytes").into();
+        assert_ne!(sha256, unbound, "domain carries the algorithm");
+        assert_eq!(sha256, bound);
+    }
+
+    #[test]
+    fn domain_tags_are_stable() {
+        let tags: Vec<&str> = Domain::ALL.iter().map(|domain| domain.as_str()).collect();
+        assert_eq!(
+            tags,
+            [
+                "ucf-core",
+                "UCF:HASH:MC_CONFIG",
+                "UCF:ASSET:MORPH",
+                "UCF:ASSET:CHANNEL_PARAMS",
+                "UCF:ASSET:SYN_PARAMS",
+                "UCF:ASSET:CONNECTIVITY",
+                "UCF:ASSET:MANIFEST",
+                "UCF:MILESTONE:MICRO",
+                "UCF:MILESTONE:MESO",
+                "UCF:MILESTONE:MACRO",
+                "UCF:MERKLE:LEAF",
+                "UCF:MERKLE:NODE",
+                "UCF:MERKLE:EMPTY",
+                "UCF:VRF:EXPERIENCE_RECORD",
+                "UCF:VRF:COMMIT",
+                "UCF:VRF:ROTATE",
+                "UCF:PVGS:RECORD_DIGEST",
+                "UCF:PVGS:REJECTION_DIGEST",
+                "UCF:SEP:EVENT",
+                "UCF:VRF:TENANT_RECORD",
+                "UCF:PVGS:TENANT_RECORD_DIGEST",
+                "UCF:TOOL:REGISTRY",
+                "UCF:EXPERIENCE:RECORD",
+                "UCF:APPROVAL:PACKAGE",
+                "UCF:PVGS:RECEIPT_SIGNATURE",
+                "UCF:VRF:SIGNER_PROBE",
+            ]
+        );
+        assert_eq!(
+            digest32(Domain::AssetManifest, "ucf.v1.AssetManifest", "1", b"bytes"),
+            digest32("UCF:ASSET:MANIFEST", "ucf.v1.AssetManifest", "1", b"bytes")
+        );
+    }
 }
@@ -0,0 +1,169 @@
+//! BLAKE3 Merkle trees over 32-byte digests, e.g. the `record_digest`s of a
+//! verified experience chain.
+//!
+//! Leaves and internal nodes are hashed under different domains so a leaf can
+//! never be passed off as an internal node:
+//!
+//! - leaf: `BLAKE3("UCF:MERKLE:LEAF" || digest)`
+//! - node: `BLAKE3("UCF:MERKLE:NODE" || left || right)`
+//!
+//! When a level has an odd number of nodes, the last node is promoted to the
+//! next level unchanged rather than paired with a copy of itself. Duplicating
+//! the last node would give `[a, b, c]` and `[a, b, c, c]` the same root;
+//! promotion keeps every leaf count distinct. The root of an empty tree is
+//! `BLAKE3("UCF:MERKLE:EMPTY")`.
+
+use blake3::Hasher;
+use subtle::ConstantTimeEq;
+
+use crate::Domain;
+
+pub const MERKLE_LEAF_DOMAIN: &str = Domain::MerkleLeaf.as_str();
+pub const MERKLE_NODE_DOMAIN: &str = Domain::MerkleNode.as_str();
+pub const MERKLE_EMPTY_DOMAIN: &str = Domain::MerkleEmpty.as_str();
+
+/// Inclusion proof for one leaf, produced by [`merkle_proof`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub leaf_index: usize,
+    pub leaf_count: usize,
+    /// Sibling hashes from the leaf level up; promoted levels contribute none.
+    pub siblings: Vec<[u8; 32]>,
+}
+
+fn hash_parts(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Hasher::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    *hasher.finalize().as_bytes()
+}
+
+fn leaf_hash(leaf: &[u8; 32]) -> [u8; 32] {
+    hash_parts(&[MERKLE_LEAF_DOMAIN.as_bytes(), leaf])
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hash_parts(&[MERKLE_NODE_DOMAIN.as_bytes(), left, right])
+}
+
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash(left, right),
+            [promoted] => *promoted,
+            _ => unreachable!("chunks(2) yields one or two nodes"),
+        })
+        .collect()
+}
+
+/// Root of the tree whose leaves are `leaf_digests`, in order.
+pub fn merkle_root(leaf_digests: &[[u8; 32]]) -> [u8; 32] {
+    if leaf_digests.is_empty() {
+        return hash_parts(&[MERKLE_EMPTY_DOMAIN.as_bytes()]);
+    }
+    let mut level: Vec<[u8; 32]> = leaf_digests.iter().map(leaf_hash).collect();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// Inclusion proof for `leaves[index]`, or `None` if `index` is out of range.
+pub fn merkle_proof(leaves: &[[u8; 32]], index: usize) -> Option<MerkleProof> {
+    if index >= leaves.len() {
+        return None;
+    }
+    let mut level: Vec<[u8; 32]> = leaves.iter().map(leaf_hash).collect();
+    let mut siblings = Vec::new();
+    let mut position = index;
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(position ^ 1) {
+            siblings.push(*sibling);
+        }
+        level = next_level(&level);
+        position /= 2;
+    }
+    Some(MerkleProof { leaf_index: index, leaf_count: leaves.len(), siblings })
+}
+
+/// Whether `proof` shows `leaf` at `proof.leaf_index` in the tree with `root`.
+pub fn verify_merkle_proof(root: &[u8; 32], leaf: &[u8; 32], proof: &MerkleProof) -> bool {
+    if proof.leaf_index >= proof.leaf_count {
+        return false;
+    }
+    let mut hash = leaf_hash(leaf);
+    let mut siblings = proof.siblings.iter();
+    let (mut position, mut width) = (proof.leaf_index, proof.leaf_count);
+    while width > 1 {
+        let promoted = position % 2 == 0 && position == width - 1;
+        if !promoted {
+            let Some(sibling) = siblings.next() else {
+                return false;
+            };
+            hash = if position % 2 == 0 {
+                node_hash(&hash, sibling)
+            } else {
+                node_hash(sibling, &hash)
+            };
+        }
+        position /= 2;
+        width = width.div_ceil(2);
+    }
+    siblings.next().is_none() && bool::from(hash.ct_eq(root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u8) -> Vec<[u8; 32]> {
+        (0..count).map(|i| [i; 32]).collect()
+    }
+
+    #[test]
+    fn every_leaf_proves_against_the_root() {
+        for count in 1..=9 {
+            let leaves = leaves(count);
+            let root = merkle_root(&leaves);
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = merkle_proof(&leaves, index).expect("index in range");
+                assert!(verify_merkle_proof(&root, leaf, &proof), "{count} leaves, index {index}");
+                assert!(!verify_merkle_proof(&root, &[0xFF; 32], &proof));
+
+                let mut moved = proof.clone();
+                moved.leaf_index = (index + 1) % leaves.len();
+                if leaves.len() > 1 {
+                    assert!(!verify_merkle_proof(&root, leaf, &moved));
+                }
+            }
+            assert_eq!(merkle_proof(&leaves, leaves.len()), None);
+        }
+    }
+
+    #[test]
+    fn odd_nodes_are_promoted_not_duplicated() {
+        let leaves = leaves(3);
+        let [a, b, c] = [leaf_hash(&leaves[0]), leaf_hash(&leaves[1]), leaf_hash(&leaves[2])];
+        assert_eq!(merkle_root(&leaves), node_hash(&node_hash(&a, &b), &c));
+
+        let duplicated = [leaves.clone(), vec![leaves[2]]].concat();
+        assert_ne!(merkle_root(&leaves), merkle_root(&duplicated));
+
+        let proof = merkle_proof(&leaves, 2).unwrap();
+        assert_eq!(proof.siblings, vec![node_hash(&a, &b)], "leaf c skips the promoted level");
+    }
+
+    #[test]
+    fn leaves_and_nodes_are_domain_separated() {
+        assert_eq!(merkle_root(&leaves(1)), leaf_hash(&[0; 32]));
+        assert_ne!(merkle_root(&leaves(1)), [0; 32]);
+        assert_ne!(merkle_root(&[]), merkle_root(&leaves(1)));
+
+        // An internal node presented as a leaf does not reproduce the root.
+        let leaves = leaves(2);
+        let node = node_hash(&leaf_hash(&leaves[0]), &leaf_hash(&leaves[1]));
+        assert_ne!(merkle_root(&[node]), merkle_root(&leaves));
+    }
+}
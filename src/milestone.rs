@@ -0,0 +1,189 @@
+//! Content digests of micro, meso, and macro milestones.
+//!
+//! A milestone's `*_digest` commits to the rest of the milestone: it is
+//! `digest32(domain, schema_id, "1", bytes)` where `bytes` is
+//! [`crate::canonical_bytes_sorted`] of the milestone with the digest field
+//! cleared, and `domain` is `UCF:MILESTONE:MICRO`, `UCF:MILESTONE:MESO`, or
+//! `UCF:MILESTONE:MACRO`. Set-semantic fields are sorted first, so reordering
+//! theme tags or refs does not change the digest.
+
+use std::fmt;
+
+use subtle::ConstantTimeEq;
+
+use crate::digest::{compute_self_digest, SelfDigested};
+use crate::ucf::v1::{Digest32, MacroMilestone, MesoMilestone, MicroMilestone};
+use crate::{Canonicalize, Domain};
+
+pub const MICRO_MILESTONE_DOMAIN: &str = Domain::MilestoneMicro.as_str();
+pub const MESO_MILESTONE_DOMAIN: &str = Domain::MilestoneMeso.as_str();
+pub const MACRO_MILESTONE_DOMAIN: &str = Domain::MilestoneMacro.as_str();
+
+/// Errors returned when a milestone digest does not commit to its content.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MilestoneError {
+    /// The milestone has no `*_digest`.
+    DigestMissing,
+    /// The stored digest differs from the recomputed digest.
+    DigestMismatch { expected: [u8; 32], found: Vec<u8> },
+}
+
+impl fmt::Display for MilestoneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MilestoneError::DigestMissing => write!(f, "milestone has no digest"),
+            MilestoneError::DigestMismatch { expected, found } => write!(
+                f,
+                "milestone digest {} does not match its content digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MilestoneError {}
+
+impl SelfDigested for MicroMilestone {
+    const SCHEMA_ID: &'static str = "ucf.v1.MicroMilestone";
+
+    fn domain() -> &'static str {
+        MICRO_MILESTONE_DOMAIN
+    }
+
+    fn take_digest(&mut self) -> Option<Digest32> {
+        self.micro_digest.take()
+    }
+
+    fn sort_sets(&mut self) {
+        Canonicalize::canonicalize(self);
+    }
+}
+
+impl SelfDigested for MesoMilestone {
+    const SCHEMA_ID: &'static str = "ucf.v1.MesoMilestone";
+
+    fn domain() -> &'static str {
+        MESO_MILESTONE_DOMAIN
+    }
+
+    fn take_digest(&mut self) -> Option<Digest32> {
+        self.meso_digest.take()
+    }
+
+    fn sort_sets(&mut self) {
+        Canonicalize::canonicalize(self);
+    }
+}
+
+impl SelfDigested for MacroMilestone {
+    const SCHEMA_ID: &'static str = "ucf.v1.MacroMilestone";
+
+    fn domain() -> &'static str {
+        MACRO_MILESTONE_DOMAIN
+    }
+
+    fn take_digest(&mut self) -> Option<Digest32> {
+        self.macro_digest.take()
+    }
+
+    fn sort_sets(&mut self) {
+        Canonicalize::canonicalize(self);
+    }
+}
+
+/// Content digest of `milestone`, ignoring `micro_digest`.
+pub fn compute_micro_milestone_digest(milestone: &MicroMilestone) -> [u8; 32] {
+    compute_self_digest(milestone)
+}
+
+/// Content digest of `milestone`, ignoring `meso_digest`.
+pub fn compute_meso_milestone_digest(milestone: &MesoMilestone) -> [u8; 32] {
+    compute_self_digest(milestone)
+}
+
+/// Content digest of `milestone`, ignoring `macro_digest`.
+pub fn compute_macro_milestone_digest(milestone: &MacroMilestone) -> [u8; 32] {
+    compute_self_digest(milestone)
+}
+
+/// Confirm `micro_digest` is [`compute_micro_milestone_digest`] of `milestone`.
+pub fn verify_micro_milestone_digest(milestone: &MicroMilestone) -> Result<(), MilestoneError> {
+    check(milestone.micro_digest.as_ref(), compute_micro_milestone_digest(milestone))
+}
+
+/// Confirm `meso_digest` is [`compute_meso_milestone_digest`] of `milestone`.
+pub fn verify_meso_milestone_digest(milestone: &MesoMilestone) -> Result<(), MilestoneError> {
+    check(milestone.meso_digest.as_ref(), compute_meso_milestone_digest(milestone))
+}
+
+/// Confirm `macro_digest` is [`compute_macro_milestone_digest`] of `milestone`.
+pub fn verify_macro_milestone_digest(milestone: &MacroMilestone) -> Result<(), MilestoneError> {
+    check(milestone.macro_digest.as_ref(), compute_macro_milestone_digest(milestone))
+}
+
+fn check(found: Option<&Digest32>, expected: [u8; 32]) -> Result<(), MilestoneError> {
+    let found = found.ok_or(MilestoneError::DigestMissing)?;
+    if bool::from(found.value.as_slice().ct_eq(&expected)) {
+        Ok(())
+    } else {
+        Err(MilestoneError::DigestMismatch { expected, found: found.value.clone() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::decode_fixture;
+
+    fn digest(value: [u8; 32]) -> Option<Digest32> {
+        Some(Digest32 { value: value.to_vec() })
+    }
+
+    #[test]
+    fn micro_digest_commits_to_range_and_summary() {
+        let mut micro: MicroMilestone = decode_fixture("micro_milestone_sealed");
+        assert!(matches!(
+            verify_micro_milestone_digest(&micro),
+            Err(MilestoneError::DigestMismatch { .. })
+        ));
+
+        micro.micro_digest = digest(compute_micro_milestone_digest(&micro));
+        assert_eq!(verify_micro_milestone_digest(&micro), Ok(()));
+
+        let mut reordered = micro.clone();
+        reordered.theme_tags.reverse();
+        assert_eq!(verify_micro_milestone_digest(&reordered), Ok(()));
+
+        let mut extended = micro.clone();
+        extended.experience_range.as_mut().unwrap().end_experience_id += 1;
+        assert!(verify_micro_milestone_digest(&extended).is_err());
+
+        let mut resummarized = micro.clone();
+        resummarized.summary_digest = digest([0x01; 32]);
+        assert!(verify_micro_milestone_digest(&resummarized).is_err());
+
+        micro.micro_digest = None;
+        assert_eq!(verify_micro_milestone_digest(&micro), Err(MilestoneError::DigestMissing));
+    }
+
+    #[test]
+    fn meso_and_macro_digests_are_domain_separated() {
+        let mut meso: MesoMilestone = decode_fixture("meso_milestone_stable");
+        meso.meso_digest = digest(compute_meso_milestone_digest(&meso));
+        assert_eq!(verify_meso_milestone_digest(&meso), Ok(()));
+        meso.micro_refs.pop();
+        assert!(verify_meso_milestone_digest(&meso).is_err());
+
+        let mut macro_: MacroMilestone = decode_fixture("macro_milestone_finalized");
+        macro_.macro_digest = digest(compute_macro_milestone_digest(&macro_));
+        assert_eq!(verify_macro_milestone_digest(&macro_), Ok(()));
+        macro_.identity_anchor_flag = !macro_.identity_anchor_flag;
+        assert!(verify_macro_milestone_digest(&macro_).is_err());
+
+        assert_ne!(
+            compute_meso_milestone_digest(&MesoMilestone::default()),
+            compute_macro_milestone_digest(&MacroMilestone::default())
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Structural checks for the macro → meso → micro milestone hierarchy.
+//!
+//! Milestones do not carry their own URI; a milestone with id `<layer>-<name>`
+//! is addressed as `ucf://<layer>/<name>` (see [`milestone_uri`]), so
+//! `meso-bridge` is `ucf://meso/bridge`. [`validate_milestone_graph`] resolves
+//! every `meso_refs` and `micro_refs` entry against that address space, across
+//! all three layers, and rejects dangling refs and cycles so a replay walker
+//! always terminates.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::ucf::v1::{MacroMilestone, MesoMilestone, MicroMilestone};
+
+/// Errors returned by [`validate_milestone_graph`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GraphError {
+    /// Two milestones share a URI.
+    DuplicateUri(String),
+    /// The milestone at `from` references `uri`, which no milestone has.
+    Unresolved { from: String, uri: String },
+    /// Following refs leads back to a milestone; the path starts and ends
+    /// with the same URI.
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::DuplicateUri(uri) => write!(f, "more than one milestone is {uri}"),
+            GraphError::Unresolved { from, uri } => {
+                write!(f, "milestone {from} references unknown milestone {uri}")
+            }
+            GraphError::Cycle(path) => write!(f, "milestone cycle {}", path.join(" -> ")),
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+/// The URI of the milestone `id` in `layer` (`"micro"`, `"meso"`, or `"macro"`).
+///
+/// A leading `<layer>-` is dropped from `id`: `milestone_uri("micro", "micro-001")`
+/// is `ucf://micro/001`.
+pub fn milestone_uri(layer: &str, id: &str) -> String {
+    let name = id.strip_prefix(layer).and_then(|rest| rest.strip_prefix('-')).unwrap_or(id);
+    format!("ucf://{layer}/{name}")
+}
+
+/// Confirm the milestones form a DAG whose refs all resolve.
+///
+/// A duplicate URI is reported first, then the first dangling ref in URI
+/// order, then a cycle with its path.
+pub fn validate_milestone_graph(
+    macros: &[MacroMilestone],
+    mesos: &[MesoMilestone],
+    micros: &[MicroMilestone],
+) -> Result<(), GraphError> {
+    let nodes = macros
+        .iter()
+        .map(|m| (milestone_uri("macro", &m.macro_id), &m.meso_refs[..]))
+        .chain(mesos.iter().map(|m| (milestone_uri("meso", &m.meso_id), &m.micro_refs[..])))
+        .chain(micros.iter().map(|m| (milestone_uri("micro", &m.micro_id), &[][..])));
+    let mut edges: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for (uri, refs) in nodes {
+        let targets = refs.iter().map(|reference| reference.uri.as_str()).collect();
+        if edges.insert(uri.clone(), targets).is_some() {
+            return Err(GraphError::DuplicateUri(uri));
+        }
+    }
+    for (from, targets) in &edges {
+        if let Some(uri) = targets.iter().find(|uri| !edges.contains_key(**uri)) {
+            return Err(GraphError::Unresolved { from: from.clone(), uri: uri.to_string() });
+        }
+    }
+
+    let mut done = BTreeMap::new();
+    for start in edges.keys() {
+        let mut path = Vec::new();
+        visit(start, &edges, &mut done, &mut path)?;
+    }
+    Ok(())
+}
+
+/// Depth-first walk from `uri`; `done` maps visited URIs to whether their
+/// walk has finished, and `path` is the chain of URIs being walked.
+fn visit<'a>(
+    uri: &'a str,
+    edges: &'a BTreeMap<String, Vec<&'a str>>,
+    done: &mut BTreeMap<&'a str, bool>,
+    path: &mut Vec<&'a str>,
+) -> Result<(), GraphError> {
+    match done.get(uri) {
+        Some(true) => return Ok(()),
+        Some(false) => {
+            let start = path.iter().position(|on_path| *on_path == uri).unwrap_or(0);
+            let cycle = path[start..].iter().chain([&uri]).map(|uri| uri.to_string());
+            return Err(GraphError::Cycle(cycle.collect()));
+        }
+        None => {}
+    }
+    done.insert(uri, false);
+    path.push(uri);
+    for target in &edges[uri] {
+        visit(target, edges, done, path)?;
+    }
+    path.pop();
+    done.insert(uri, true);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ucf::v1::Ref;
+
+    fn refs(uris: &[&str]) -> Vec<Ref> {
+        uris.iter().map(|uri| Ref { uri: uri.to_string(), label: String::new() }).collect()
+    }
+
+    fn macro_(id: &str, meso_refs: &[&str]) -> MacroMilestone {
+        MacroMilestone {
+            macro_id: id.to_string(),
+            meso_refs: refs(meso_refs),
+            ..Default::default()
+        }
+    }
+
+    fn meso(id: &str, micro_refs: &[&str]) -> MesoMilestone {
+        MesoMilestone {
+            meso_id: id.to_string(),
+            micro_refs: refs(micro_refs),
+            ..Default::default()
+        }
+    }
+
+    fn micro(id: &str) -> MicroMilestone {
+        MicroMilestone { micro_id: id.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn uri_follows_fixture_convention() {
+        assert_eq!(milestone_uri("micro", "micro-001"), "ucf://micro/001");
+        assert_eq!(milestone_uri("meso", "meso-bridge"), "ucf://meso/bridge");
+        assert_eq!(milestone_uri("macro", "root"), "ucf://macro/root");
+    }
+
+    #[test]
+    fn accepts_shared_children() {
+        let macros = [macro_("macro-root", &["ucf://meso/a", "ucf://meso/b"])];
+        let mesos = [
+            meso("meso-a", &["ucf://micro/1", "ucf://micro/2"]),
+            meso("meso-b", &["ucf://micro/2"]),
+        ];
+        let micros = [micro("micro-1"), micro("micro-2")];
+        assert_eq!(validate_milestone_graph(&macros, &mesos, &micros), Ok(()));
+        assert_eq!(validate_milestone_graph(&[], &[], &[]), Ok(()));
+    }
+
+    #[test]
+    fn rejects_dangling_refs_and_duplicates() {
+        let macros = [macro_("macro-root", &["ucf://meso/a"])];
+        let mesos = [meso("meso-a", &["ucf://micro/1", "ucf://micro/9"])];
+        assert_eq!(
+            validate_milestone_graph(&macros, &mesos, &[micro("micro-1")]),
+            Err(GraphError::Unresolved {
+                from: "ucf://meso/a".to_string(),
+                uri: "ucf://micro/9".to_string(),
+            })
+        );
+
+        assert_eq!(
+            validate_milestone_graph(&[], &[], &[micro("micro-1"), micro("1")]),
+            Err(GraphError::DuplicateUri("ucf://micro/1".to_string()))
+        );
+    }
+
+    #[test]
+    fn reports_cycle_path() {
+        let macros = [macro_("macro-root", &["ucf://meso/a"])];
+        let mesos = [meso("meso-a", &["ucf://meso/b"]), meso("meso-b", &["ucf://macro/root"])];
+        assert_eq!(
+            validate_milestone_graph(&macros, &mesos, &[]),
+            Err(GraphError::Cycle(vec![
+                "ucf://macro/root".to_string(),
+                "ucf://meso/a".to_string(),
+                "ucf://meso/b".to_string(),
+                "ucf://macro/root".to_string(),
+            ]))
+        );
+
+        let self_ref = [meso("meso-a", &["ucf://meso/a"])];
+        assert_eq!(
+            validate_milestone_graph(&[], &self_ref, &[]),
+            Err(GraphError::Cycle(vec!["ucf://meso/a".to_string(), "ucf://meso/a".to_string()]))
+        );
+    }
+}
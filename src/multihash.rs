@@ -0,0 +1,103 @@
+//! Self-describing multihash encoding of [`digest32`] outputs.
+//!
+//! A multihash is `varint(code) || varint(length) || digest`. UCF digests are
+//! BLAKE3-256, multicodec `blake3` (code `0x1e`, tag `multihash`) with a 32-byte
+//! length, so the encoding is the two bytes `0x1e 0x20` followed by the raw
+//! digest. Both prefix values are below `0x80` and so encode as one varint
+//! byte each. The multihash covers only the hash output; the domain, schema,
+//! and version the digest was taken over are not recorded.
+
+use std::fmt;
+
+use crate::digest32;
+
+/// Multicodec code of BLAKE3 in the `multihash` table.
+pub const BLAKE3_MULTIHASH_CODE: u8 = 0x1e;
+/// Digest length recorded in the multihash prefix.
+pub const BLAKE3_MULTIHASH_LEN: u8 = 0x20;
+
+/// Errors returned by [`parse_multihash`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MultihashError {
+    /// The input is not 34 bytes long.
+    BadLength(usize),
+    /// The hash function code is not BLAKE3 (`0x1e`).
+    UnsupportedCode(u8),
+    /// The recorded digest length is not 32 (`0x20`).
+    BadDigestLength(u8),
+}
+
+impl fmt::Display for MultihashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MultihashError::BadLength(len) => write!(f, "multihash is {len} bytes, expected 34"),
+            MultihashError::UnsupportedCode(code) => {
+                write!(f, "multihash code {code:#04x} is not blake3 (0x1e)")
+            }
+            MultihashError::BadDigestLength(len) => {
+                write!(f, "multihash digest length {len} is not 32")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MultihashError {}
+
+/// `digest` as a BLAKE3-256 multihash: `0x1e 0x20 || digest`.
+pub fn to_multihash(digest: &[u8; 32]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(34);
+    out.extend_from_slice(&[BLAKE3_MULTIHASH_CODE, BLAKE3_MULTIHASH_LEN]);
+    out.extend_from_slice(digest);
+    out
+}
+
+/// [`digest32`] encoded with [`to_multihash`].
+pub fn digest32_multihash(
+    domain: impl AsRef<str>,
+    schema_id: &str,
+    schema_version: &str,
+    bytes: &[u8],
+) -> Vec<u8> {
+    to_multihash(&digest32(domain, schema_id, schema_version, bytes))
+}
+
+/// The raw digest inside a BLAKE3-256 multihash.
+pub fn parse_multihash(multihash: &[u8]) -> Result<[u8; 32], MultihashError> {
+    let (prefix, digest) = match multihash {
+        [code, len, digest @ ..] if digest.len() == 32 => ((*code, *len), digest),
+        _ => return Err(MultihashError::BadLength(multihash.len())),
+    };
+    match prefix {
+        (BLAKE3_MULTIHASH_CODE, BLAKE3_MULTIHASH_LEN) => {}
+        (BLAKE3_MULTIHASH_CODE, len) => return Err(MultihashError::BadDigestLength(len)),
+        (code, _) => return Err(MultihashError::UnsupportedCode(code)),
+    }
+    Ok(digest.try_into().expect("length checked above"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multihash_wraps_digest32() {
+        let digest = digest32("ucf-core", "ucf.v1.ReasonCodes", "1", b"bytes");
+        let multihash = digest32_multihash("ucf-core", "ucf.v1.ReasonCodes", "1", b"bytes");
+        assert_eq!(&multihash[..2], &[0x1e, 0x20]);
+        assert_eq!(&multihash[2..], &digest);
+        assert_eq!(parse_multihash(&multihash), Ok(digest));
+    }
+
+    #[test]
+    fn parse_rejects_other_prefixes() {
+        let mut multihash = to_multihash(&[0x11; 32]);
+        assert_eq!(parse_multihash(&multihash[..33]), Err(MultihashError::BadLength(33)));
+        assert_eq!(parse_multihash(&[0x11; 32]), Err(MultihashError::BadLength(32)));
+
+        multihash[0] = 0x12;
+        assert_eq!(parse_multihash(&multihash), Err(MultihashError::UnsupportedCode(0x12)));
+        multihash[0] = 0x1e;
+        multihash[1] = 0x40;
+        assert_eq!(parse_multihash(&multihash), Err(MultihashError::BadDigestLength(0x40)));
+    }
+}
@@ -0,0 +1,266 @@
+//! Opt-in strict decoding that rejects unknown protobuf fields.
+//!
+//! `prost` skips field numbers that are not part of the generated schema while
+//! decoding. Re-encoding the decoded message then silently drops those bytes, so
+//! a sender can smuggle extra data inside a message whose canonical digest still
+//! matches. [`parse_strict`] closes that gap for callers that pin the peer to the
+//! same schema version.
+//!
+//! Strict decoding deliberately trades away forward compatibility: a message
+//! produced by a newer schema that adds fields (a MINOR bump, see
+//! `docs/protocol_versioning.md`) is rejected instead of being read as the older
+//! shape. Use `M::decode` where mixed schema versions are expected.
+//!
+//! [`canonical_bytes_strict`] is the verifier's form: it returns the canonical
+//! bytes to digest only when they are exactly the input, so a digest is never
+//! taken over a lossy reinterpretation of what the peer sent.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use prost::{DecodeError, Message};
+
+const WIRE_VARINT: u8 = 0;
+const WIRE_SIXTY_FOUR_BIT: u8 = 1;
+const WIRE_LENGTH_DELIMITED: u8 = 2;
+const WIRE_THIRTY_TWO_BIT: u8 = 5;
+
+/// Errors returned by [`parse_strict`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The bytes are not a valid encoding of the message.
+    Decode(DecodeError),
+    /// A top-level field number is not part of the message schema.
+    UnknownField(u32),
+    /// Re-encoding the decoded message does not reproduce the input length, for
+    /// example because a nested message carried an unknown field.
+    LengthMismatch { input_len: usize, reencoded_len: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Decode(err) => write!(f, "decode failed: {err}"),
+            ParseError::UnknownField(tag) => write!(f, "unknown field number {tag}"),
+            ParseError::LengthMismatch { input_len, reencoded_len } => write!(
+                f,
+                "re-encoded length {reencoded_len} does not match input length {input_len}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<DecodeError> for ParseError {
+    fn from(err: DecodeError) -> Self {
+        ParseError::Decode(err)
+    }
+}
+
+/// Errors returned by [`canonical_bytes_strict`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CanonError {
+    /// The bytes do not decode, or carry fields unknown to the schema.
+    Parse(ParseError),
+    /// Nothing was dropped, but the input is not the canonical encoding.
+    NonCanonical,
+}
+
+impl fmt::Display for CanonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanonError::Parse(err) => write!(f, "strict parse failed: {err}"),
+            CanonError::NonCanonical => f.write_str("input is not the canonical encoding"),
+        }
+    }
+}
+
+impl std::error::Error for CanonError {}
+
+impl From<ParseError> for CanonError {
+    fn from(err: ParseError) -> Self {
+        CanonError::Parse(err)
+    }
+}
+
+/// Decode `bytes` as `M` with [`parse_strict`] and return its canonical bytes.
+///
+/// Fails if any field is unknown to `M`, or if re-encoding does not reproduce
+/// `bytes` exactly, so the result is always identical to the input.
+pub fn canonical_bytes_strict<M: Message + Default>(bytes: &[u8]) -> Result<Vec<u8>, CanonError> {
+    let message: M = parse_strict(bytes)?;
+    let canonical = crate::canonical_bytes(&message);
+    if canonical != bytes {
+        return Err(CanonError::NonCanonical);
+    }
+    Ok(canonical)
+}
+
+/// Decode `bytes` as `M`, rejecting unknown field numbers.
+///
+/// Every top-level field number present in the input is checked against the
+/// schema of `M`, and the decoded message must re-encode to the same length as
+/// the input so that nothing was discarded deeper in the tree.
+pub fn parse_strict<M: Message + Default>(bytes: &[u8]) -> Result<M, ParseError> {
+    let message = M::decode(bytes)?;
+    let reencoded_len = message.encoded_len();
+    let length_mismatch = ParseError::LengthMismatch { input_len: bytes.len(), reencoded_len };
+
+    // `M::decode` already accepted the framing, so the walk only fails on groups,
+    // which proto3 never declares.
+    let fields = top_level_fields(bytes).ok_or(length_mismatch.clone())?;
+    for (tag, wire_type) in fields {
+        if !is_known_field::<M>(tag, wire_type) {
+            return Err(ParseError::UnknownField(tag));
+        }
+    }
+
+    if reencoded_len != bytes.len() {
+        return Err(length_mismatch);
+    }
+
+    Ok(message)
+}
+
+/// Collect the distinct top-level field numbers and their wire types.
+fn top_level_fields(bytes: &[u8]) -> Option<BTreeMap<u32, u8>> {
+    let mut fields = BTreeMap::new();
+    let mut buf = bytes;
+
+    while !buf.is_empty() {
+        let key = read_varint(&mut buf)?;
+        let tag = u32::try_from(key >> 3).ok()?;
+        let wire_type = (key & 0x07) as u8;
+        fields.entry(tag).or_insert(wire_type);
+
+        let skip = match wire_type {
+            WIRE_VARINT => {
+                read_varint(&mut buf)?;
+                0
+            }
+            WIRE_SIXTY_FOUR_BIT => 8,
+            WIRE_THIRTY_TWO_BIT => 4,
+            WIRE_LENGTH_DELIMITED => usize::try_from(read_varint(&mut buf)?).ok()?,
+            _ => return None,
+        };
+        buf = buf.get(skip..)?;
+    }
+
+    Some(fields)
+}
+
+/// Probe whether `M` recognises `tag` by decoding a lone non-default value.
+///
+/// Unknown fields are skipped without error and leave the message empty, while a
+/// known field either fails to decode (wrong payload shape) or re-encodes to a
+/// non-empty message.
+fn is_known_field<M: Message + Default>(tag: u32, wire_type: u8) -> bool {
+    let mut probe = Vec::with_capacity(16);
+    write_varint((u64::from(tag) << 3) | u64::from(wire_type), &mut probe);
+    match wire_type {
+        WIRE_VARINT => probe.push(0x01),
+        WIRE_SIXTY_FOUR_BIT => probe.extend_from_slice(&[0x01; 8]),
+        WIRE_THIRTY_TWO_BIT => probe.extend_from_slice(&[0x01; 4]),
+        WIRE_LENGTH_DELIMITED => probe.extend_from_slice(&[0x01, 0x00]),
+        _ => return false,
+    }
+
+    match M::decode(probe.as_slice()) {
+        Ok(decoded) => decoded.encoded_len() > 0,
+        Err(_) => true,
+    }
+}
+
+fn read_varint(buf: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = buf.split_first()?;
+        *buf = rest;
+        value |= u64::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn write_varint(mut value: u64, buf: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canonical_bytes;
+    use crate::ucf::v1::{CoreFrame, PolicyDecision, ReasonCodes, Ref};
+
+    fn sample_decision() -> PolicyDecision {
+        PolicyDecision {
+            decision: 1,
+            reason_codes: Some(ReasonCodes { codes: vec!["scope-limited".to_string()] }),
+            constraints: None,
+        }
+    }
+
+    #[test]
+    fn strict_parse_accepts_canonical_bytes() {
+        let bytes = canonical_bytes(&sample_decision());
+        let decoded: PolicyDecision = parse_strict(&bytes).expect("canonical bytes parse");
+        assert_eq!(decoded, sample_decision());
+    }
+
+    #[test]
+    fn strict_parse_rejects_unknown_top_level_field() {
+        let mut bytes = canonical_bytes(&sample_decision());
+        // Field 15, length-delimited, carrying three smuggled bytes.
+        bytes.extend_from_slice(&[0x7A, 0x03, 0xDE, 0xAD, 0x00]);
+
+        let lenient = PolicyDecision::decode(bytes.as_slice()).expect("prost skips the field");
+        assert_eq!(canonical_bytes(&lenient), canonical_bytes(&sample_decision()));
+
+        assert_eq!(parse_strict::<PolicyDecision>(&bytes), Err(ParseError::UnknownField(15)));
+    }
+
+    #[test]
+    fn strict_parse_rejects_unknown_nested_field() {
+        let self_state = Ref { uri: "state://self/42".to_string(), label: "baseline".to_string() };
+        let mut nested = canonical_bytes(&self_state);
+        // Field 9, varint, inside the nested Ref.
+        nested.extend_from_slice(&[0x48, 0x01]);
+        // CoreFrame.self_state_ref is field 5, length-delimited.
+        let mut bytes = vec![0x2A, nested.len() as u8];
+        bytes.extend_from_slice(&nested);
+
+        let err = parse_strict::<CoreFrame>(&bytes).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::LengthMismatch { input_len: bytes.len(), reencoded_len: bytes.len() - 2 }
+        );
+    }
+
+    #[test]
+    fn canonical_bytes_strict_refuses_lossy_or_reordered_input() {
+        let bytes = canonical_bytes(&sample_decision());
+        assert_eq!(canonical_bytes_strict::<PolicyDecision>(&bytes), Ok(bytes.clone()));
+
+        let mut smuggled = bytes.clone();
+        smuggled.extend_from_slice(&[0x7A, 0x03, 0xDE, 0xAD, 0x00]);
+        assert_eq!(
+            canonical_bytes_strict::<PolicyDecision>(&smuggled),
+            Err(CanonError::Parse(ParseError::UnknownField(15)))
+        );
+
+        // reason_codes (field 2) written before decision (field 1).
+        let mut reordered = bytes[2..].to_vec();
+        reordered.extend_from_slice(&bytes[..2]);
+        assert_eq!(
+            canonical_bytes_strict::<PolicyDecision>(&reordered),
+            Err(CanonError::NonCanonical)
+        );
+    }
+}
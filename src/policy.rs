@@ -0,0 +1,189 @@
+//! Interpreting and combining [`PolicyDecision`] outcomes.
+//!
+//! Decisions are ordered by strictness, from least to most strict:
+//!
+//! 1. `ALLOW`
+//! 2. `REQUIRE_APPROVAL`
+//! 3. `DENY`
+//!
+//! `UNSPECIFIED` and undefined values are treated as `DENY`, so a malformed
+//! decision never lets an action through. Only `ALLOW` does not block.
+//!
+//! [`merge_decisions`] combines the decisions of independent policy engines.
+//! It is commutative and associative, so folding any number of decisions in
+//! any order gives the same result.
+
+use std::collections::BTreeSet;
+
+use crate::reason_codes;
+use crate::ucf::v1::{ConstraintsDelta, DecisionForm, PolicyDecision};
+
+/// Position of `decision` in the strictness order; higher is stricter.
+pub fn strictness(decision: DecisionForm) -> u8 {
+    match decision {
+        DecisionForm::Allow => 0,
+        DecisionForm::RequireApproval => 1,
+        DecisionForm::Deny | DecisionForm::Unspecified => 2,
+    }
+}
+
+/// The outcome `d` enforces: its decision, or `DENY` if unspecified or undefined.
+pub fn effective_decision(d: &PolicyDecision) -> DecisionForm {
+    match d.decision() {
+        DecisionForm::Unspecified => DecisionForm::Deny,
+        decision => decision,
+    }
+}
+
+/// Whether `d` needs a human approval before the action may proceed.
+pub fn decision_requires_human(d: &PolicyDecision) -> bool {
+    effective_decision(d) == DecisionForm::RequireApproval
+}
+
+/// Whether `d` stops the action from proceeding on its own.
+pub fn decision_blocks(d: &PolicyDecision) -> bool {
+    effective_decision(d) != DecisionForm::Allow
+}
+
+/// Combine two decisions into the stricter outcome.
+///
+/// The decision is the stricter effective decision of `a` and `b`. Reason
+/// codes are the sorted union of both. Constraints added by either delta are
+/// kept, but a constraint is removed only if every present delta removes it and
+/// none adds it, so one engine cannot lift a constraint another still relies
+/// on. The result is absent only when both inputs lack it.
+pub fn merge_decisions(a: &PolicyDecision, b: &PolicyDecision) -> PolicyDecision {
+    let (a_decision, b_decision) = (effective_decision(a), effective_decision(b));
+    let decision =
+        if strictness(b_decision) > strictness(a_decision) { b_decision } else { a_decision };
+
+    let reason_codes = match (&a.reason_codes, &b.reason_codes) {
+        (None, None) => None,
+        (a, b) => Some(reason_codes::merge(&[a, b].into_iter().flatten().collect::<Vec<_>>())),
+    };
+
+    let constraints = match (&a.constraints, &b.constraints) {
+        (None, None) => None,
+        (a, b) => {
+            let deltas: Vec<&ConstraintsDelta> = [a, b].into_iter().flatten().collect();
+            let added: BTreeSet<&String> =
+                deltas.iter().flat_map(|delta| &delta.constraints_added).collect();
+            let removed: BTreeSet<&String> = deltas[0]
+                .constraints_removed
+                .iter()
+                .filter(|constraint| {
+                    deltas.iter().all(|delta| delta.constraints_removed.contains(constraint))
+                })
+                .filter(|constraint| !added.contains(constraint))
+                .collect();
+            Some(ConstraintsDelta {
+                constraints_added: added.into_iter().cloned().collect(),
+                constraints_removed: removed.into_iter().cloned().collect(),
+            })
+        }
+    };
+
+    PolicyDecision { decision: decision as i32, reason_codes, constraints }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ucf::v1::ReasonCodes;
+
+    fn decision(form: DecisionForm, codes: &[&str]) -> PolicyDecision {
+        PolicyDecision {
+            decision: form as i32,
+            reason_codes: Some(ReasonCodes::sorted(codes)),
+            constraints: None,
+        }
+    }
+
+    #[test]
+    fn classifies_decisions() {
+        let cases = [
+            (DecisionForm::Allow as i32, false, false),
+            (DecisionForm::RequireApproval as i32, true, true),
+            (DecisionForm::Deny as i32, false, true),
+            (DecisionForm::Unspecified as i32, false, true),
+            (99, false, true),
+        ];
+        for (value, requires_human, blocks) in cases {
+            let d = PolicyDecision { decision: value, ..Default::default() };
+            assert_eq!(decision_requires_human(&d), requires_human, "{value}");
+            assert_eq!(decision_blocks(&d), blocks, "{value}");
+        }
+    }
+
+    #[test]
+    fn merge_takes_the_stricter_outcome() {
+        let allow = decision(DecisionForm::Allow, &["ok"]);
+        let approval = decision(DecisionForm::RequireApproval, &["pii"]);
+        let deny = decision(DecisionForm::Deny, &["blocked", "ok"]);
+
+        let merged = merge_decisions(&allow, &approval);
+        assert_eq!(merged.decision(), DecisionForm::RequireApproval);
+        assert_eq!(merged.reason_codes, Some(ReasonCodes::sorted(&["ok", "pii"])));
+
+        let merged = merge_decisions(&approval, &deny);
+        assert_eq!(merged.decision(), DecisionForm::Deny);
+        assert_eq!(merged.reason_codes, Some(ReasonCodes::sorted(&["blocked", "ok", "pii"])));
+
+        let unspecified = PolicyDecision::default();
+        let merged = merge_decisions(&allow, &unspecified);
+        assert_eq!(merged.decision(), DecisionForm::Deny);
+        assert_eq!(merged.reason_codes, Some(ReasonCodes::sorted(&["ok"])));
+        assert_eq!(
+            merge_decisions(&unspecified, &unspecified),
+            PolicyDecision { decision: DecisionForm::Deny as i32, ..Default::default() }
+        );
+    }
+
+    #[test]
+    fn merge_is_order_independent() {
+        let mut a = decision(DecisionForm::Allow, &["b", "a"]);
+        a.constraints = Some(ConstraintsDelta {
+            constraints_added: vec!["read_only".to_string()],
+            constraints_removed: vec!["no_network".to_string()],
+        });
+        let mut b = decision(DecisionForm::RequireApproval, &["c"]);
+        b.constraints = Some(ConstraintsDelta {
+            constraints_added: vec!["no_network".to_string()],
+            constraints_removed: vec!["rate_limit".to_string()],
+        });
+        let c = decision(DecisionForm::Allow, &["a"]);
+
+        let merged = merge_decisions(&a, &b);
+        assert_eq!(merged, merge_decisions(&b, &a));
+        assert_eq!(
+            merged.constraints,
+            Some(ConstraintsDelta {
+                constraints_added: vec!["no_network".to_string(), "read_only".to_string()],
+                constraints_removed: vec![],
+            })
+        );
+        assert_eq!(merge_decisions(&merged, &c), merge_decisions(&a, &merge_decisions(&b, &c)));
+    }
+
+    #[test]
+    fn merge_only_removes_constraints_every_delta_removes() {
+        let delta = |removed: &[&str]| {
+            let mut d = decision(DecisionForm::Allow, &[]);
+            d.constraints = Some(ConstraintsDelta {
+                constraints_added: vec![],
+                constraints_removed: removed.iter().map(|c| c.to_string()).collect(),
+            });
+            d
+        };
+        let lifts_both = delta(&["no_network", "rate_limit"]);
+        let lifts_one = delta(&["rate_limit"]);
+
+        let merged = merge_decisions(&lifts_both, &lifts_one);
+        assert_eq!(merged, merge_decisions(&lifts_one, &lifts_both));
+        assert_eq!(merged.constraints.unwrap().constraints_removed, vec!["rate_limit".to_string()]);
+
+        // A decision without a delta does not veto the other's removals.
+        let merged = merge_decisions(&lifts_both, &decision(DecisionForm::Allow, &[]));
+        assert_eq!(merged.constraints, lifts_both.constraints);
+    }
+}
@@ -0,0 +1,143 @@
+//! Human-readable descriptions for known reason codes.
+//!
+//! Reason codes are case-sensitive, stable identifiers (see
+//! `docs/reason_codes.md`). The table below is the single source of truth for
+//! what each known code means; custom codes are valid on the wire but have no
+//! description here.
+//!
+//! [`merge`] and [`merge_into`] combine code lists into the canonical form
+//! required by `docs/determinism_rules.md`: sorted and free of duplicates.
+//! [`sorted_codes`] and [`ReasonCodes::sorted`] build that form from string
+//! literals.
+
+use std::collections::BTreeSet;
+
+use crate::ucf::v1::ReasonCodes;
+
+/// Known reason codes and their descriptions, sorted by code.
+const DESCRIPTIONS: &[(&str, &str)] = &[
+    ("RC.GV.AAP.BLOCKED_BY_TRACE", "Approval artifact package blocked by a risky trace"),
+    ("RC.GV.BIO.COOLDOWN_ACTIVE", "Biophysical change rejected while a cooldown is active"),
+    ("RC.GV.PROPOSAL.ACTIVATED", "Governance proposal activated"),
+    ("RC.GV.PROPOSAL.APPENDED", "Governance proposal appended"),
+    ("RC.GV.PROPOSAL.REJECTED", "Governance proposal rejected"),
+    ("RC.GV.SHADOW.INVALID_LOOSENING", "Shadow evaluation attempted an invalid loosening"),
+    ("RC.GV.TRACE.APPENDED", "Governance trace appended"),
+    ("RC.GV.TRACE.NEUTRAL", "Trace evaluated as neutral"),
+    ("RC.GV.TRACE.PROMISING", "Trace evaluated as promising"),
+    ("RC.GV.TRACE.RISKY", "Trace evaluated as risky"),
+    ("asset-refresh", "Asset bundle refresh triggered the change"),
+    ("baseline", "Baseline request with no special handling"),
+    ("budget-tight", "Remaining budget is close to its limit"),
+    ("chain-limit", "Tool chain length limit reached"),
+    ("checkpoint", "Progress checkpoint reached"),
+    ("consistency-low", "Consistency score fell below threshold"),
+    ("coverage", "Recorded to satisfy a coverage requirement"),
+    ("deny", "Policy denied the request"),
+    ("deterministic", "Outcome is deterministic and reproducible"),
+    ("dlp-approved", "Output cleared data loss prevention checks"),
+    ("dlp-block", "Data loss prevention blocked the content"),
+    ("dlp-redact", "Data loss prevention redacted part of the content"),
+    ("drift-detected", "Behavioural drift detected"),
+    ("edge-gap", "Expected edge missing from the event graph"),
+    ("executor-timeout", "Tool executor timed out"),
+    ("init", "Session or chain initialized"),
+    ("missing", "Required receipt is missing"),
+    ("missing-attestation", "Required attestation is missing"),
+    ("missing-node", "Expected node missing from the event graph"),
+    ("missing-proof", "Required proof is missing"),
+    ("ml-ops", "Machine learning operations workload"),
+    ("near-exhaustion", "Budget is nearly exhausted"),
+    ("operator-trigger", "Triggered manually by an operator"),
+    ("policy", "Outcome determined by policy evaluation"),
+    ("policy-deny", "Denied by policy"),
+    ("query", "Request is a read-only query"),
+    ("receipt-missing", "PVGS receipt is missing"),
+    ("replay-recommended", "Replay is recommended"),
+    ("require-approval", "Human approval is required"),
+    ("risk-review", "Risk review is required"),
+    ("safety", "Safety-sensitive workload"),
+    ("scope-limited", "Scope was limited by policy constraints"),
+    ("sealed", "Milestone sealed"),
+    ("signature-invalid", "Signature failed verification"),
+    ("success", "Completed successfully"),
+    ("tool-unavailable", "Requested tool is unavailable"),
+    ("two-person", "Two-person approval is required"),
+];
+
+/// Return the description of a known reason code, or `None` for custom codes.
+pub fn describe(code: &str) -> Option<&'static str> {
+    DESCRIPTIONS
+        .binary_search_by(|(known, _)| (*known).cmp(code))
+        .ok()
+        .map(|index| DESCRIPTIONS[index].1)
+}
+
+/// Union `sources` into a single sorted, de-duplicated [`ReasonCodes`].
+pub fn merge(sources: &[&ReasonCodes]) -> ReasonCodes {
+    let codes: BTreeSet<&String> = sources.iter().flat_map(|source| &source.codes).collect();
+    ReasonCodes { codes: codes.into_iter().cloned().collect() }
+}
+
+/// `items` as owned strings in canonical order: sorted bytewise, duplicates removed.
+pub fn sorted_codes(items: &[&str]) -> Vec<String> {
+    let codes: BTreeSet<&str> = items.iter().copied().collect();
+    codes.into_iter().map(str::to_string).collect()
+}
+
+impl ReasonCodes {
+    /// Reason codes holding [`sorted_codes`] of `items`.
+    pub fn sorted(items: &[&str]) -> Self {
+        ReasonCodes { codes: sorted_codes(items) }
+    }
+}
+
+/// Add `extra` to `target`, leaving `target` sorted and de-duplicated.
+pub fn merge_into(target: &mut ReasonCodes, extra: &[String]) {
+    target.codes.extend_from_slice(extra);
+    target.codes.sort();
+    target.codes.dedup();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_known_and_custom_codes() {
+        assert!(DESCRIPTIONS.windows(2).all(|pair| pair[0].0 < pair[1].0), "table must be sorted");
+
+        assert_eq!(
+            describe("dlp-redact"),
+            Some("Data loss prevention redacted part of the content")
+        );
+        assert_eq!(describe("RC.GV.TRACE.RISKY"), Some("Trace evaluated as risky"));
+        assert_eq!(describe("custom-code"), None);
+        assert_eq!(describe("DLP-REDACT"), None, "codes are case-sensitive");
+    }
+
+    #[test]
+    fn merge_unions_sorts_and_dedups() {
+        let policy =
+            ReasonCodes { codes: vec!["require-approval".to_string(), "deny".to_string()] };
+        let dlp = ReasonCodes { codes: vec!["dlp-redact".to_string(), "deny".to_string()] };
+
+        let merged = merge(&[&policy, &dlp]);
+        assert_eq!(merged.codes, vec!["deny", "dlp-redact", "require-approval"]);
+        assert_eq!(merge(&[]), ReasonCodes::default());
+
+        let mut target = ReasonCodes { codes: vec!["scope-limited".to_string()] };
+        merge_into(&mut target, &["missing-proof".to_string(), "scope-limited".to_string()]);
+        assert_eq!(target.codes, vec!["missing-proof", "scope-limited"]);
+    }
+
+    #[test]
+    fn sorted_codes_sorts_bytewise_and_dedups() {
+        assert_eq!(
+            sorted_codes(&["deny", "RC.GV.TRACE.RISKY", "deny", "budget-tight"]),
+            vec!["RC.GV.TRACE.RISKY", "budget-tight", "deny"]
+        );
+        assert!(sorted_codes(&[]).is_empty());
+        assert_eq!(ReasonCodes::sorted(&["b", "a", "b"]).codes, vec!["a", "b"]);
+    }
+}
@@ -0,0 +1,203 @@
+//! Validated construction of [`Ref`] URIs.
+//!
+//! `Ref.uri` is a free-form string on the wire, and decoding never rejects it.
+//! [`Ref::new`] and [`Ref::validate`] check the URI has the `scheme:path`
+//! shape, as in `vrf://digest/seed` or `did:example:subject`, and that its
+//! scheme is in an [`AllowedSchemes`] set, so a malformed reference fails where
+//! it is made rather than during replay.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::ucf::v1::Ref;
+
+/// URI schemes used by the protocol's own messages and fixtures.
+pub const PROTOCOL_SCHEMES: &[&str] = &[
+    "action",
+    "artifact",
+    "budget",
+    "candidate",
+    "control",
+    "core",
+    "decision",
+    "did",
+    "dlp",
+    "evidence",
+    "governance",
+    "grant",
+    "hpa",
+    "intent",
+    "just",
+    "metabolic",
+    "outcome",
+    "packet",
+    "pev",
+    "policy",
+    "proof",
+    "pvgs",
+    "rss",
+    "sep",
+    "state",
+    "ucf",
+    "vrf",
+];
+
+/// URI schemes a [`Ref`] may use.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct AllowedSchemes(BTreeSet<String>);
+
+impl AllowedSchemes {
+    /// An empty set that rejects every scheme.
+    pub fn none() -> Self {
+        Self(BTreeSet::new())
+    }
+
+    pub fn allow(mut self, scheme: &str) -> Self {
+        self.0.insert(scheme.to_string());
+        self
+    }
+
+    pub fn contains(&self, scheme: &str) -> bool {
+        self.0.contains(scheme)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl Default for AllowedSchemes {
+    /// The [`PROTOCOL_SCHEMES`].
+    fn default() -> Self {
+        PROTOCOL_SCHEMES.iter().fold(Self::none(), |allowed, scheme| allowed.allow(scheme))
+    }
+}
+
+/// Errors returned when a [`Ref`] URI is not well formed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RefError {
+    /// `uri` is empty.
+    Empty,
+    /// `uri` is not `scheme:path` with a lowercase scheme and a non-empty path,
+    /// after an optional `//`, free of whitespace and control characters.
+    Malformed(String),
+    /// The scheme is not in the allowed set.
+    SchemeNotAllowed(String),
+}
+
+impl fmt::Display for RefError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RefError::Empty => write!(f, "ref uri is empty"),
+            RefError::Malformed(uri) => write!(f, "ref uri {uri:?} is not scheme:path"),
+            RefError::SchemeNotAllowed(scheme) => {
+                write!(f, "ref uri scheme {scheme:?} is not allowed")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RefError {}
+
+/// Split `uri` into its scheme and path if it has the `scheme:path` shape. A
+/// `//` after the colon belongs to neither.
+fn split_uri(uri: &str) -> Option<(&str, &str)> {
+    let (scheme, rest) = uri.split_once(':')?;
+    let path = rest.strip_prefix("//").unwrap_or(rest);
+    let mut chars = scheme.chars();
+    let scheme_ok = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "+-.".contains(c));
+    let path_ok = !path.is_empty() && !path.chars().any(|c| c.is_whitespace() || c.is_control());
+    (scheme_ok && path_ok).then_some((scheme, path))
+}
+
+impl Ref {
+    /// A ref whose `uri` passes [`Ref::validate`] against the default
+    /// [`AllowedSchemes`].
+    pub fn new(uri: impl Into<String>, label: impl Into<String>) -> Result<Self, RefError> {
+        Self::new_with(uri, label, &AllowedSchemes::default())
+    }
+
+    /// A ref whose `uri` passes [`Ref::validate`] against `allowed`.
+    pub fn new_with(
+        uri: impl Into<String>,
+        label: impl Into<String>,
+        allowed: &AllowedSchemes,
+    ) -> Result<Self, RefError> {
+        let reference = Ref { uri: uri.into(), label: label.into() };
+        reference.validate(allowed)?;
+        Ok(reference)
+    }
+
+    /// The URI scheme, or `None` if `uri` is not `scheme:path`.
+    pub fn scheme(&self) -> Option<&str> {
+        split_uri(&self.uri).map(|(scheme, _)| scheme)
+    }
+
+    /// Confirm `uri` is `scheme:path` with a scheme in `allowed`.
+    pub fn validate(&self, allowed: &AllowedSchemes) -> Result<(), RefError> {
+        if self.uri.is_empty() {
+            return Err(RefError::Empty);
+        }
+        let scheme = self.scheme().ok_or_else(|| RefError::Malformed(self.uri.clone()))?;
+        if !allowed.contains(scheme) {
+            return Err(RefError::SchemeNotAllowed(scheme.to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::decode_fixture;
+    use crate::ucf::v1::ExperienceRecord;
+
+    #[test]
+    fn new_accepts_protocol_uris() {
+        let reference = Ref::new("vrf://digest/seed", "vrf").expect("valid ref");
+        assert_eq!(reference, Ref { uri: "vrf://digest/seed".to_string(), label: "vrf".into() });
+        assert_eq!(reference.scheme(), Some("vrf"));
+
+        let subject = Ref::new("did:example:subject", "primary").expect("valid ref");
+        assert_eq!(subject.scheme(), Some("did"));
+        assert_eq!(Ref::new("vrf:/digest", "x").unwrap().scheme(), Some("vrf"));
+    }
+
+    #[test]
+    fn new_rejects_malformed_uris() {
+        assert_eq!(Ref::new("", "x"), Err(RefError::Empty));
+        for uri in ["vrf", "vrf:", "vrf://", "VRF://digest", "://digest", ":digest", "vrf://a b"] {
+            assert_eq!(Ref::new(uri, "x"), Err(RefError::Malformed(uri.to_string())), "{uri}");
+        }
+        assert_eq!(
+            Ref::new("https://example.com", "x"),
+            Err(RefError::SchemeNotAllowed("https".to_string()))
+        );
+    }
+
+    #[test]
+    fn allowlist_is_configurable() {
+        let allowed = AllowedSchemes::none().allow("https");
+        assert!(Ref::new_with("https://example.com", "x", &allowed).is_ok());
+        assert_eq!(
+            Ref::new_with("ucf://a", "x", &allowed),
+            Err(RefError::SchemeNotAllowed("ucf".to_string()))
+        );
+    }
+
+    #[test]
+    fn experience_fixture_refs_are_valid() {
+        let record: ExperienceRecord = decode_fixture("experience_rt_action_exec");
+        let header = record.finalization_header.as_ref().unwrap();
+        let refs = [&record.core_frame_ref, &header.vrf_digest_ref, &header.proof_receipt_ref]
+            .into_iter()
+            .flatten()
+            .chain(&record.related_refs);
+        for reference in refs {
+            assert_eq!(reference.validate(&AllowedSchemes::default()), Ok(()), "{reference:?}");
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Consistency checks and timing for replay run evidence.
+//!
+//! A run advances `steps` ticks of `dt_us` microseconds each, and every tick
+//! is split into `substeps_per_tick` substeps. Both `dt_us` and
+//! `substeps_per_tick` must be non-zero; [`validate_timing`] checks this.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::ucf::v1::ReplayRunEvidence;
+
+/// Errors returned by [`validate_micro_config_versions`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConfigVersionError {
+    /// The same module appears twice with the same `config_version`.
+    Duplicate { module: i32, config_version: u32 },
+    /// A module's `config_version` decreased relative to its previous entry.
+    NonMonotonic { module: i32, previous: u32, found: u32 },
+}
+
+impl fmt::Display for ConfigVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigVersionError::Duplicate { module, config_version } => {
+                write!(f, "micro module {module} lists config_version {config_version} twice")
+            }
+            ConfigVersionError::NonMonotonic { module, previous, found } => write!(
+                f,
+                "micro module {module} config_version {found} follows higher version {previous}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigVersionError {}
+
+/// Errors returned by [`validate_timing`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TimingError {
+    /// `dt_us` is zero, so the run covers no simulated time.
+    ZeroDt,
+    /// `substeps_per_tick` is zero, so a tick has no substep length.
+    ZeroSubsteps,
+}
+
+impl fmt::Display for TimingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimingError::ZeroDt => write!(f, "replay run has dt_us 0"),
+            TimingError::ZeroSubsteps => write!(f, "replay run has substeps_per_tick 0"),
+        }
+    }
+}
+
+impl std::error::Error for TimingError {}
+
+impl ReplayRunEvidence {
+    /// Simulated time the run covers: `steps * dt_us`.
+    ///
+    /// Computed in `u64`, so it cannot overflow.
+    pub fn total_duration_us(&self) -> u64 {
+        u64::from(self.steps) * u64::from(self.dt_us)
+    }
+
+    /// Length of one substep, `dt_us / substeps_per_tick` rounded down, or
+    /// `None` when `substeps_per_tick` is zero.
+    pub fn effective_substep_dt_us(&self) -> Option<u64> {
+        u64::from(self.dt_us).checked_div(u64::from(self.substeps_per_tick))
+    }
+}
+
+/// Check that `run.dt_us` and `run.substeps_per_tick` are non-zero.
+pub fn validate_timing(run: &ReplayRunEvidence) -> Result<(), TimingError> {
+    if run.dt_us == 0 {
+        return Err(TimingError::ZeroDt);
+    }
+    if run.substeps_per_tick == 0 {
+        return Err(TimingError::ZeroSubsteps);
+    }
+    Ok(())
+}
+
+/// Check that each module's `config_version`s in `run.micro_configs` are unique
+/// and strictly increasing in the order they are listed.
+pub fn validate_micro_config_versions(run: &ReplayRunEvidence) -> Result<(), ConfigVersionError> {
+    let mut latest: BTreeMap<i32, u32> = BTreeMap::new();
+    for config in &run.micro_configs {
+        let module = config.module;
+        let found = config.config_version;
+        if let Some(&previous) = latest.get(&module) {
+            if found == previous {
+                return Err(ConfigVersionError::Duplicate { module, config_version: found });
+            }
+            if found < previous {
+                return Err(ConfigVersionError::NonMonotonic { module, previous, found });
+            }
+        }
+        latest.insert(module, found);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ucf::v1::{MicroModule, MicrocircuitConfigEvidence};
+
+    fn run(configs: &[(MicroModule, u32)]) -> ReplayRunEvidence {
+        ReplayRunEvidence {
+            micro_configs: configs
+                .iter()
+                .map(|&(module, config_version)| MicrocircuitConfigEvidence {
+                    module: module as i32,
+                    config_version,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn config_versions_increase_per_module() {
+        let ok = run(&[(MicroModule::Lc, 1), (MicroModule::Sn, 4), (MicroModule::Lc, 2)]);
+        assert_eq!(validate_micro_config_versions(&ok), Ok(()));
+
+        let duplicate = run(&[(MicroModule::Hpa, 3), (MicroModule::Lc, 1), (MicroModule::Hpa, 3)]);
+        assert_eq!(
+            validate_micro_config_versions(&duplicate),
+            Err(ConfigVersionError::Duplicate {
+                module: MicroModule::Hpa as i32,
+                config_version: 3
+            })
+        );
+
+        let regressed = run(&[(MicroModule::Sn, 5), (MicroModule::Sn, 2)]);
+        assert_eq!(
+            validate_micro_config_versions(&regressed),
+            Err(ConfigVersionError::NonMonotonic {
+                module: MicroModule::Sn as i32,
+                previous: 5,
+                found: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn timing_uses_ticks_and_substeps() {
+        let mut run =
+            ReplayRunEvidence { steps: 42, dt_us: 25, substeps_per_tick: 4, ..Default::default() };
+        assert_eq!(validate_timing(&run), Ok(()));
+        assert_eq!(run.total_duration_us(), 1_050);
+        assert_eq!(run.effective_substep_dt_us(), Some(6));
+
+        run.steps = u32::MAX;
+        run.dt_us = u32::MAX;
+        assert_eq!(run.total_duration_us(), u64::from(u32::MAX) * u64::from(u32::MAX));
+
+        run.substeps_per_tick = 0;
+        assert_eq!(run.effective_substep_dt_us(), None);
+        assert_eq!(validate_timing(&run), Err(TimingError::ZeroSubsteps));
+        run.dt_us = 0;
+        assert_eq!(validate_timing(&run), Err(TimingError::ZeroDt));
+    }
+}
@@ -0,0 +1,204 @@
+//! Rejection of replayed envelope nonces.
+//!
+//! A gateway records each accepted envelope's `(epoch_id, nonce)` in a
+//! [`NonceTracker`] and refuses a pair it has already seen. Memory is bounded:
+//! the tracker holds at most `capacity` nonces for each of at most
+//! `max_epochs` epochs, so roughly `2 * max_epochs * capacity * nonce_len`
+//! bytes of nonce data. A nonce evicted to stay within `capacity` is accepted
+//! again, so `capacity` should cover the envelopes one epoch can carry.
+//!
+//! Epochs are never evicted implicitly: once `max_epochs` are tracked, nonces
+//! for any further epoch are refused until the gateway closes an epoch with
+//! [`NonceTracker::retire_epoch`]. Otherwise a sender could cycle fresh epoch
+//! ids to flush a live epoch's nonces and then replay them. Retire an epoch
+//! only once envelopes for it are refused before they reach the tracker.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use crate::ucf::v1::UcfEnvelope;
+
+/// Epochs a [`NonceTracker`] keeps unless configured otherwise.
+pub const DEFAULT_MAX_EPOCHS: usize = 4;
+
+/// Errors returned by [`NonceTracker::check_and_record`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The nonce is empty.
+    NonceEmpty,
+    /// The nonce was already recorded for `epoch_id`.
+    NonceReused { epoch_id: String, nonce: Vec<u8> },
+    /// `epoch_id` is not tracked and `max_epochs` epochs already are.
+    TooManyEpochs { epoch_id: String, max_epochs: usize },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::NonceEmpty => write!(f, "envelope nonce is empty"),
+            ReplayError::NonceReused { epoch_id, nonce } => {
+                write!(f, "nonce {} already used in epoch {epoch_id:?}", hex::encode(nonce))
+            }
+            ReplayError::TooManyEpochs { epoch_id, max_epochs } => {
+                write!(f, "epoch {epoch_id:?} exceeds the {max_epochs} tracked epochs")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// The nonces seen in one epoch, oldest first.
+#[derive(Debug, Default)]
+struct EpochNonces {
+    seen: HashSet<Vec<u8>>,
+    order: VecDeque<Vec<u8>>,
+}
+
+/// Bounded record of the `(epoch_id, nonce)` pairs already accepted.
+///
+/// Each epoch keeps its `capacity` most recently recorded nonces. A new epoch
+/// beyond `max_epochs` is refused until one is retired.
+#[derive(Debug)]
+pub struct NonceTracker {
+    capacity: usize,
+    max_epochs: usize,
+    epochs: HashMap<String, EpochNonces>,
+}
+
+impl NonceTracker {
+    /// A tracker keeping up to `capacity` nonces (at least one) per epoch for
+    /// [`DEFAULT_MAX_EPOCHS`] epochs.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), max_epochs: DEFAULT_MAX_EPOCHS, epochs: HashMap::new() }
+    }
+
+    /// Keep nonces for up to `max_epochs` epochs (at least one).
+    pub fn with_max_epochs(mut self, max_epochs: usize) -> Self {
+        self.max_epochs = max_epochs.max(1);
+        self
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn max_epochs(&self) -> usize {
+        self.max_epochs
+    }
+
+    /// Record `nonce` for `epoch_id`, failing if it is already recorded or
+    /// `epoch_id` would exceed `max_epochs`.
+    pub fn check_and_record(&mut self, epoch_id: &str, nonce: &[u8]) -> Result<(), ReplayError> {
+        if nonce.is_empty() {
+            return Err(ReplayError::NonceEmpty);
+        }
+        if !self.epochs.contains_key(epoch_id) && self.epochs.len() >= self.max_epochs {
+            return Err(ReplayError::TooManyEpochs {
+                epoch_id: epoch_id.to_string(),
+                max_epochs: self.max_epochs,
+            });
+        }
+        let epoch = self.epochs.entry(epoch_id.to_string()).or_default();
+        if epoch.seen.contains(nonce) {
+            return Err(ReplayError::NonceReused {
+                epoch_id: epoch_id.to_string(),
+                nonce: nonce.to_vec(),
+            });
+        }
+        if epoch.order.len() == self.capacity {
+            if let Some(oldest) = epoch.order.pop_front() {
+                epoch.seen.remove(&oldest);
+            }
+        }
+        epoch.seen.insert(nonce.to_vec());
+        epoch.order.push_back(nonce.to_vec());
+        Ok(())
+    }
+
+    /// Forget `epoch_id`'s nonces, making room for another epoch. Returns
+    /// whether the epoch was tracked.
+    ///
+    /// Nonces of a retired epoch are accepted again if it reappears, so call
+    /// this only for epochs the gateway no longer accepts.
+    pub fn retire_epoch(&mut self, epoch_id: &str) -> bool {
+        self.epochs.remove(epoch_id).is_some()
+    }
+
+    /// [`NonceTracker::check_and_record`] for `envelope`'s epoch and nonce.
+    pub fn check_envelope(&mut self, envelope: &UcfEnvelope) -> Result<(), ReplayError> {
+        self.check_and_record(&envelope.epoch_id, &envelope.nonce)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nonce_is_accepted_once_per_epoch() {
+        let mut tracker = NonceTracker::new(8);
+        assert_eq!(tracker.check_and_record("epoch-1", &[0x01; 16]), Ok(()));
+        for _ in 0..2 {
+            assert_eq!(
+                tracker.check_and_record("epoch-1", &[0x01; 16]),
+                Err(ReplayError::NonceReused {
+                    epoch_id: "epoch-1".to_string(),
+                    nonce: vec![0x01; 16],
+                })
+            );
+        }
+        assert_eq!(tracker.check_and_record("epoch-2", &[0x01; 16]), Ok(()));
+        assert_eq!(tracker.check_and_record("epoch-1", &[0x02; 16]), Ok(()));
+        assert_eq!(tracker.check_and_record("epoch-1", &[]), Err(ReplayError::NonceEmpty));
+    }
+
+    #[test]
+    fn memory_stays_within_capacity() {
+        let mut tracker = NonceTracker::new(2).with_max_epochs(2);
+        for nonce in 1..=3u8 {
+            tracker.check_and_record("a", &[nonce]).unwrap();
+        }
+        // Only the two most recent nonces of epoch "a" are kept.
+        assert!(tracker.check_and_record("a", &[3]).is_err());
+        assert!(tracker.check_and_record("a", &[2]).is_err());
+        assert_eq!(tracker.check_and_record("a", &[1]), Ok(()));
+
+        // A third epoch is refused until one is retired.
+        tracker.check_and_record("b", &[1]).unwrap();
+        assert_eq!(
+            tracker.check_and_record("c", &[1]),
+            Err(ReplayError::TooManyEpochs { epoch_id: "c".to_string(), max_epochs: 2 })
+        );
+        assert!(tracker.retire_epoch("a"));
+        assert!(!tracker.retire_epoch("a"));
+        assert_eq!(tracker.check_and_record("c", &[1]), Ok(()));
+        assert!(tracker.epochs.values().all(|epoch| epoch.order.len() <= 2));
+        assert!(tracker.epochs.len() <= 2);
+    }
+
+    #[test]
+    fn fresh_epochs_cannot_flush_a_live_epoch() {
+        let mut tracker = NonceTracker::new(8).with_max_epochs(3);
+        tracker.check_and_record("live", &[0x01; 16]).unwrap();
+        for i in 0..tracker.max_epochs() {
+            let _ = tracker.check_and_record(&format!("fresh-{i}"), &[0x02; 16]);
+        }
+        assert_eq!(
+            tracker.check_and_record("live", &[0x01; 16]),
+            Err(ReplayError::NonceReused { epoch_id: "live".to_string(), nonce: vec![0x01; 16] })
+        );
+    }
+
+    #[test]
+    fn check_envelope_uses_epoch_and_nonce() {
+        let envelope = UcfEnvelope {
+            epoch_id: "epoch-7".to_string(),
+            nonce: vec![0x09; 16],
+            ..Default::default()
+        };
+        let mut tracker = NonceTracker::new(4);
+        assert_eq!(tracker.check_envelope(&envelope), Ok(()));
+        assert!(tracker.check_envelope(&envelope).is_err());
+    }
+}
@@ -0,0 +1,616 @@
+//! Cross-message checks that `proof_receipt_ref` URIs point at valid receipts.
+//!
+//! Milestones, asset digests, SEP seals, and other evidence messages reference
+//! the PVGS receipt that admitted them by URI. [`verify_receipt_refs`] resolves
+//! each of those URIs against a bundle of receipts keyed by URI and confirms the
+//! receipt it lands on is structurally valid. [`verify_bundle`] additionally
+//! applies a [`TrustConfig`]: to the receipts' validators, and to the charter,
+//! timestamps, and two-person requirements of the messages themselves.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::approval::required_signers;
+use crate::trust::TrustConfig;
+use crate::ucf::v1::{
+    ApprovalArtifactPackage, AssetDigest, AssetManifest, CompletenessReport, ConsistencyFeedback,
+    ExperienceRecord, MacroMilestone, MesoMilestone, MicroMilestone, MicrocircuitConfigEvidence,
+    ProofReceipt, ReceiptStatus, RecursiveSelfState, Ref, ReplayPlan, ReplayRunEvidence,
+    RequestedOperation, SessionSeal,
+};
+use crate::Domain;
+
+/// A borrowed UCF message that may carry a `proof_receipt_ref` or be subject
+/// to [`TrustConfig`] policy.
+#[derive(Clone, Copy, Debug)]
+pub enum UcfMessage<'a> {
+    MicroMilestone(&'a MicroMilestone),
+    MesoMilestone(&'a MesoMilestone),
+    MacroMilestone(&'a MacroMilestone),
+    ReplayPlan(&'a ReplayPlan),
+    ReplayRunEvidence(&'a ReplayRunEvidence),
+    AssetDigest(&'a AssetDigest),
+    AssetManifest(&'a AssetManifest),
+    SessionSeal(&'a SessionSeal),
+    CompletenessReport(&'a CompletenessReport),
+    RecursiveSelfState(&'a RecursiveSelfState),
+    ConsistencyFeedback(&'a ConsistencyFeedback),
+    MicrocircuitConfigEvidence(&'a MicrocircuitConfigEvidence),
+    /// Carries its reference on `finalization_header.proof_receipt_ref`.
+    ExperienceRecord(&'a ExperienceRecord),
+    /// Carries no reference; checked against two-person requirements only.
+    ApprovalArtifactPackage(&'a ApprovalArtifactPackage),
+}
+
+impl<'a> UcfMessage<'a> {
+    /// Fully qualified schema id of the wrapped message.
+    pub fn schema_id(&self) -> &'static str {
+        match self {
+            UcfMessage::MicroMilestone(_) => "ucf.v1.MicroMilestone",
+            UcfMessage::MesoMilestone(_) => "ucf.v1.MesoMilestone",
+            UcfMessage::MacroMilestone(_) => "ucf.v1.MacroMilestone",
+            UcfMessage::ReplayPlan(_) => "ucf.v1.ReplayPlan",
+            UcfMessage::ReplayRunEvidence(_) => "ucf.v1.ReplayRunEvidence",
+            UcfMessage::AssetDigest(_) => "ucf.v1.AssetDigest",
+            UcfMessage::AssetManifest(_) => "ucf.v1.AssetManifest",
+            UcfMessage::SessionSeal(_) => "ucf.v1.SessionSeal",
+            UcfMessage::CompletenessReport(_) => "ucf.v1.CompletenessReport",
+            UcfMessage::RecursiveSelfState(_) => "ucf.v1.RecursiveSelfState",
+            UcfMessage::ConsistencyFeedback(_) => "ucf.v1.ConsistencyFeedback",
+            UcfMessage::MicrocircuitConfigEvidence(_) => "ucf.v1.MicrocircuitConfigEvidence",
+            UcfMessage::ExperienceRecord(_) => "ucf.v1.ExperienceRecord",
+            UcfMessage::ApprovalArtifactPackage(_) => "ucf.v1.ApprovalArtifactPackage",
+        }
+    }
+
+    /// The receipt reference carried by the message, if any.
+    pub fn proof_receipt_ref(&self) -> Option<&'a Ref> {
+        match *self {
+            UcfMessage::MicroMilestone(m) => m.proof_receipt_ref.as_ref(),
+            UcfMessage::MesoMilestone(m) => m.proof_receipt_ref.as_ref(),
+            UcfMessage::MacroMilestone(m) => m.proof_receipt_ref.as_ref(),
+            UcfMessage::ReplayPlan(m) => m.proof_receipt_ref.as_ref(),
+            UcfMessage::ReplayRunEvidence(m) => m.proof_receipt_ref.as_ref(),
+            UcfMessage::AssetDigest(m) => m.proof_receipt_ref.as_ref(),
+            UcfMessage::AssetManifest(m) => m.proof_receipt_ref.as_ref(),
+            UcfMessage::SessionSeal(m) => m.proof_receipt_ref.as_ref(),
+            UcfMessage::CompletenessReport(m) => m.proof_receipt_ref.as_ref(),
+            UcfMessage::RecursiveSelfState(m) => m.proof_receipt_ref.as_ref(),
+            UcfMessage::ConsistencyFeedback(m) => m.proof_receipt_ref.as_ref(),
+            UcfMessage::MicrocircuitConfigEvidence(m) => m.proof_receipt_ref.as_ref(),
+            UcfMessage::ExperienceRecord(m) => {
+                m.finalization_header.as_ref().and_then(|h| h.proof_receipt_ref.as_ref())
+            }
+            UcfMessage::ApprovalArtifactPackage(_) => None,
+        }
+    }
+
+    /// The charter the message is bound to, if it names one.
+    pub fn charter_digest(&self) -> Option<&'a str> {
+        match *self {
+            UcfMessage::ExperienceRecord(m) => {
+                m.finalization_header.as_ref().map(|h| h.charter_version_digest.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    /// The time the message was created or finalized, if it records one.
+    pub fn timestamp_ms(&self) -> Option<u64> {
+        match *self {
+            UcfMessage::ReplayRunEvidence(m) => Some(m.created_at_ms),
+            UcfMessage::AssetDigest(m) => Some(m.created_at_ms),
+            UcfMessage::AssetManifest(m) => Some(m.created_at_ms),
+            UcfMessage::SessionSeal(m) => Some(m.created_at_ms),
+            UcfMessage::MicrocircuitConfigEvidence(m) => Some(m.created_at_ms),
+            UcfMessage::ExperienceRecord(m) => {
+                m.finalization_header.as_ref().map(|h| h.timestamp_ms)
+            }
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_from_message {
+    ($($variant:ident),* $(,)?) => {
+        $(
+            impl<'a> From<&'a $variant> for UcfMessage<'a> {
+                fn from(message: &'a $variant) -> Self {
+                    UcfMessage::$variant(message)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_message!(
+    MicroMilestone,
+    MesoMilestone,
+    MacroMilestone,
+    ReplayPlan,
+    ReplayRunEvidence,
+    AssetDigest,
+    AssetManifest,
+    SessionSeal,
+    CompletenessReport,
+    RecursiveSelfState,
+    ConsistencyFeedback,
+    MicrocircuitConfigEvidence,
+    ExperienceRecord,
+    ApprovalArtifactPackage,
+);
+
+/// Why a `proof_receipt_ref` failed to resolve to a valid receipt.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DanglingReason {
+    /// No receipt in the bundle is keyed by the URI.
+    Unresolved,
+    /// The receipt exists but its status is not `RECEIPT_STATUS_ACCEPTED`.
+    NotAccepted(i32),
+    /// The receipt has no 32-byte `receipt_digest`.
+    MissingReceiptDigest,
+    /// The receipt has no validator signature.
+    MissingValidator,
+    /// The validator signature uses an algorithm the [`TrustConfig`] does not allow.
+    UnsupportedAlgorithm(String),
+    /// The validator signer is not a key trusted by the [`TrustConfig`].
+    UntrustedValidator,
+    /// The validator signature does not verify over [`receipt_signing_bytes`].
+    InvalidSignature,
+}
+
+/// A `proof_receipt_ref` that does not point at a valid receipt.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DanglingRef {
+    /// Index of the offending message in the input slice.
+    pub index: usize,
+    pub schema_id: &'static str,
+    pub uri: String,
+    pub reason: DanglingReason,
+}
+
+impl fmt::Display for DanglingRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let DanglingRef { index, schema_id, uri, reason } = self;
+        write!(f, "message {index} ({schema_id}) proof_receipt_ref {uri:?}: ")?;
+        match reason {
+            DanglingReason::Unresolved => write!(f, "no receipt with this uri"),
+            DanglingReason::NotAccepted(status) => {
+                write!(f, "receipt status {status} is not accepted")
+            }
+            DanglingReason::MissingReceiptDigest => {
+                write!(f, "receipt has no 32-byte receipt_digest")
+            }
+            DanglingReason::MissingValidator => write!(f, "receipt has no validator signature"),
+            DanglingReason::UnsupportedAlgorithm(algorithm) => {
+                write!(f, "receipt validator algorithm {algorithm:?} is not allowed")
+            }
+            DanglingReason::UntrustedValidator => write!(f, "receipt validator is not trusted"),
+            DanglingReason::InvalidSignature => {
+                write!(f, "receipt validator signature does not verify")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DanglingRef {}
+
+/// Why a message breaks the [`TrustConfig`] policy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolicyReason {
+    /// The message is bound to a charter the config does not accept.
+    CharterNotAccepted(String),
+    /// The message's timestamp is further ahead of `now_ms` than the config's skew allows.
+    TimestampAhead { timestamp_ms: u64, now_ms: u64 },
+    /// An approval package for an operation the config reserves for two-person
+    /// approval demands fewer than two signers.
+    TwoPersonRequired(RequestedOperation),
+}
+
+/// A message in a bundle that breaks the [`TrustConfig`] policy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolicyViolation {
+    /// Index of the offending message in the input slice.
+    pub index: usize,
+    pub schema_id: &'static str,
+    pub reason: PolicyReason,
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let PolicyViolation { index, schema_id, reason } = self;
+        write!(f, "message {index} ({schema_id}): ")?;
+        match reason {
+            PolicyReason::CharterNotAccepted(charter) => {
+                write!(f, "charter {charter:?} is not accepted")
+            }
+            PolicyReason::TimestampAhead { timestamp_ms, now_ms } => {
+                write!(f, "timestamp {timestamp_ms} is too far ahead of {now_ms}")
+            }
+            PolicyReason::TwoPersonRequired(op) => {
+                write!(f, "{} requires two-person approval", op.as_str_name())
+            }
+        }
+    }
+}
+
+impl std::error::Error for PolicyViolation {}
+
+/// An error found by [`verify_bundle`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BundleError {
+    Dangling(DanglingRef),
+    Policy(PolicyViolation),
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::Dangling(dangling) => dangling.fmt(f),
+            BundleError::Policy(violation) => violation.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+/// Domain tag prepended to `receipt_digest` in [`receipt_signing_bytes`].
+pub const RECEIPT_SIGNATURE_DOMAIN: &[u8] = Domain::PvgsReceiptSignature.as_bytes();
+
+/// The bytes a PVGS validator signs for a receipt:
+/// `RECEIPT_SIGNATURE_DOMAIN || receipt_digest`.
+///
+/// The tag keeps a receipt signature from being replayed as a signature over
+/// any other 32-byte digest the same key signs.
+pub fn receipt_signing_bytes(receipt_digest: &[u8]) -> Vec<u8> {
+    [RECEIPT_SIGNATURE_DOMAIN, receipt_digest].concat()
+}
+
+/// Check that every `proof_receipt_ref` in `messages` resolves to a valid receipt.
+///
+/// Messages without a `proof_receipt_ref` are skipped. All dangling references
+/// are collected rather than stopping at the first one.
+pub fn verify_receipt_refs(
+    messages: &[UcfMessage<'_>],
+    receipts: &HashMap<String, ProofReceipt>,
+) -> Result<(), Vec<DanglingRef>> {
+    check_receipt_refs(messages, receipts, None)
+}
+
+/// Like [`verify_receipt_refs`], and also apply `trust` at `now_ms`.
+///
+/// Each receipt's validator must use an allowed algorithm and a signer key
+/// trusted by `trust`, and its signature must verify over
+/// [`receipt_signing_bytes`] of the receipt's `receipt_digest`. Each message
+/// bound to a charter must name an accepted one, no message timestamp may be
+/// further ahead of `now_ms` than the configured skew, and an approval package whose `requested_operation` is
+/// reserved for two-person approval must demand two signers. Dangling
+/// references are reported first, then policy violations, each in message
+/// order.
+pub fn verify_bundle(
+    messages: &[UcfMessage<'_>],
+    receipts: &HashMap<String, ProofReceipt>,
+    trust: &TrustConfig,
+    now_ms: u64,
+) -> Result<(), Vec<BundleError>> {
+    let mut errors: Vec<BundleError> = match check_receipt_refs(messages, receipts, Some(trust)) {
+        Ok(()) => Vec::new(),
+        Err(dangling) => dangling.into_iter().map(BundleError::Dangling).collect(),
+    };
+    for (index, message) in messages.iter().enumerate() {
+        let schema_id = message.schema_id();
+        let violation = |reason| BundleError::Policy(PolicyViolation { index, schema_id, reason });
+        if let Some(charter) = message.charter_digest() {
+            if !trust.accepts_charter(charter) {
+                errors.push(violation(PolicyReason::CharterNotAccepted(charter.to_string())));
+            }
+        }
+        if let Some(timestamp_ms) = message.timestamp_ms() {
+            if !trust.within_skew(timestamp_ms, now_ms) {
+                errors.push(violation(PolicyReason::TimestampAhead { timestamp_ms, now_ms }));
+            }
+        }
+        if let UcfMessage::ApprovalArtifactPackage(aap) = message {
+            let op = aap.requested_operation();
+            if trust.requires_two_person(op) && required_signers(aap.two_person_requirement()) < 2 {
+                errors.push(violation(PolicyReason::TwoPersonRequired(op)));
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn check_receipt_refs(
+    messages: &[UcfMessage<'_>],
+    receipts: &HashMap<String, ProofReceipt>,
+    trust: Option<&TrustConfig>,
+) -> Result<(), Vec<DanglingRef>> {
+    let dangling: Vec<DanglingRef> = messages
+        .iter()
+        .enumerate()
+        .filter_map(|(index, message)| {
+            let receipt_ref = message.proof_receipt_ref()?;
+            let reason = match receipts.get(&receipt_ref.uri) {
+                Some(receipt) => receipt_defect(receipt, trust)?,
+                None => DanglingReason::Unresolved,
+            };
+            Some(DanglingRef {
+                index,
+                schema_id: message.schema_id(),
+                uri: receipt_ref.uri.clone(),
+                reason,
+            })
+        })
+        .collect();
+
+    if dangling.is_empty() {
+        Ok(())
+    } else {
+        Err(dangling)
+    }
+}
+
+fn receipt_defect(receipt: &ProofReceipt, trust: Option<&TrustConfig>) -> Option<DanglingReason> {
+    if receipt.status != ReceiptStatus::Accepted as i32 {
+        return Some(DanglingReason::NotAccepted(receipt.status));
+    }
+    let Some(digest) = receipt.receipt_digest.as_ref().filter(|digest| digest.value.len() == 32)
+    else {
+        return Some(DanglingReason::MissingReceiptDigest);
+    };
+    let Some(validator) = receipt.validator.as_ref() else {
+        return Some(DanglingReason::MissingValidator);
+    };
+    if let Some(trust) = trust {
+        if !trust.allowed_algorithms().contains(&validator.algorithm) {
+            return Some(DanglingReason::UnsupportedAlgorithm(validator.algorithm.clone()));
+        }
+        if !trust.is_trusted_key_bytes(&validator.signer) {
+            return Some(DanglingReason::UntrustedValidator);
+        }
+        let message = receipt_signing_bytes(&digest.value);
+        if validator.verify(&message, trust.allowed_algorithms()).is_err() {
+            return Some(DanglingReason::InvalidSignature);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    use super::*;
+    use crate::ucf::v1::{Digest32, FinalizationHeader, Signature, TwoPersonRequirement};
+
+    fn receipt(status: ReceiptStatus) -> ProofReceipt {
+        ProofReceipt {
+            status: status as i32,
+            receipt_digest: Some(Digest32 { value: vec![0x11; 32] }),
+            validator: Some(Signature {
+                algorithm: "ed25519".to_string(),
+                signer: vec![0xAA; 32],
+                signature: vec![0xBB; 64],
+            }),
+            vrf_digest: None,
+        }
+    }
+
+    fn receipt_ref(uri: &str) -> Option<Ref> {
+        Some(Ref { uri: uri.to_string(), label: "receipt".to_string() })
+    }
+
+    #[test]
+    fn receipt_refs_resolve_to_valid_receipts() {
+        let receipts = HashMap::from([
+            ("proof://micro/1".to_string(), receipt(ReceiptStatus::Accepted)),
+            ("proof://seal/1".to_string(), receipt(ReceiptStatus::Rejected)),
+        ]);
+        let micro = MicroMilestone {
+            proof_receipt_ref: receipt_ref("proof://micro/1"),
+            ..Default::default()
+        };
+        let seal =
+            SessionSeal { proof_receipt_ref: receipt_ref("proof://seal/1"), ..Default::default() };
+        let manifest = AssetManifest {
+            proof_receipt_ref: receipt_ref("proof://manifest/missing"),
+            ..Default::default()
+        };
+        let unreferenced = AssetDigest::default();
+
+        assert_eq!(
+            verify_receipt_refs(&[(&micro).into(), (&unreferenced).into()], &receipts),
+            Ok(())
+        );
+
+        let errors =
+            verify_receipt_refs(&[(&micro).into(), (&seal).into(), (&manifest).into()], &receipts)
+                .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                DanglingRef {
+                    index: 1,
+                    schema_id: "ucf.v1.SessionSeal",
+                    uri: "proof://seal/1".to_string(),
+                    reason: DanglingReason::NotAccepted(ReceiptStatus::Rejected as i32),
+                },
+                DanglingRef {
+                    index: 2,
+                    schema_id: "ucf.v1.AssetManifest",
+                    uri: "proof://manifest/missing".to_string(),
+                    reason: DanglingReason::Unresolved,
+                },
+            ]
+        );
+    }
+
+    fn signed_receipt(key: &SigningKey) -> ProofReceipt {
+        let mut receipt = receipt(ReceiptStatus::Accepted);
+        let digest = &receipt.receipt_digest.as_ref().unwrap().value;
+        let signature = key.sign(&receipt_signing_bytes(digest));
+        receipt.validator = Some(Signature::ed25519(&key.verifying_key(), &signature));
+        receipt
+    }
+
+    #[test]
+    fn bundle_requires_trusted_validator() {
+        let key = SigningKey::from_bytes(&[3u8; 32]);
+        let trusted = key.verifying_key();
+        let receipts =
+            HashMap::from([("proof://micro/1".to_string(), receipt(ReceiptStatus::Accepted))]);
+        let micro = MicroMilestone {
+            proof_receipt_ref: receipt_ref("proof://micro/1"),
+            ..Default::default()
+        };
+
+        let errors = verify_bundle(
+            &[(&micro).into()],
+            &receipts,
+            &TrustConfig::new().trust_key("k", trusted),
+            0,
+        )
+        .unwrap_err();
+        assert_eq!(dangling_reason(&errors[0]), &DanglingReason::UntrustedValidator);
+
+        let receipts = HashMap::from([("proof://micro/1".to_string(), signed_receipt(&key))]);
+        assert_eq!(
+            verify_bundle(
+                &[(&micro).into()],
+                &receipts,
+                &TrustConfig::new().trust_key("k", trusted),
+                0
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn bundle_rejects_forged_validator_signature() {
+        let key = SigningKey::from_bytes(&[3u8; 32]);
+        let trust = TrustConfig::new().trust_key("k", key.verifying_key());
+        let micro = MicroMilestone {
+            proof_receipt_ref: receipt_ref("proof://micro/1"),
+            ..Default::default()
+        };
+
+        // Naming a trusted key is not enough without its signature.
+        let mut forged = receipt(ReceiptStatus::Accepted);
+        forged.validator.as_mut().unwrap().signer = key.verifying_key().as_bytes().to_vec();
+        let mut tampered = signed_receipt(&key);
+        tampered.receipt_digest = Some(Digest32 { value: vec![0x12; 32] });
+        // A signature over the bare digest is not a receipt signature.
+        let mut untagged = receipt(ReceiptStatus::Accepted);
+        let digest = &untagged.receipt_digest.as_ref().unwrap().value;
+        let signature = Signature::ed25519(&key.verifying_key(), &key.sign(digest));
+        untagged.validator = Some(signature);
+
+        for receipt in [forged, tampered, untagged] {
+            let receipts = HashMap::from([("proof://micro/1".to_string(), receipt)]);
+            let errors = verify_bundle(&[(&micro).into()], &receipts, &trust, 0).unwrap_err();
+            assert_eq!(dangling_reason(&errors[0]), &DanglingReason::InvalidSignature);
+        }
+    }
+
+    fn dangling_reason(error: &BundleError) -> &DanglingReason {
+        match error {
+            BundleError::Dangling(dangling) => &dangling.reason,
+            BundleError::Policy(violation) => panic!("unexpected policy violation: {violation}"),
+        }
+    }
+
+    fn policy(index: usize, schema_id: &'static str, reason: PolicyReason) -> BundleError {
+        BundleError::Policy(PolicyViolation { index, schema_id, reason })
+    }
+
+    #[test]
+    fn bundle_enforces_charters_skew_and_two_person_ops() {
+        let trust = TrustConfig::new()
+            .accept_charter("charter-v1")
+            .max_skew_ms(100)
+            .require_two_person(RequestedOperation::OpExport);
+        let record = |charter: &str, timestamp_ms| ExperienceRecord {
+            finalization_header: Some(FinalizationHeader {
+                charter_version_digest: charter.to_string(),
+                timestamp_ms,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let package =
+            |op: RequestedOperation, requirement: TwoPersonRequirement| ApprovalArtifactPackage {
+                requested_operation: op as i32,
+                two_person_requirement: requirement as i32,
+                ..Default::default()
+            };
+        let receipts = HashMap::new();
+
+        let accepted = record("charter-v1", 1_100);
+        let export_two = package(RequestedOperation::OpExport, TwoPersonRequirement::Two);
+        let write_one = package(RequestedOperation::OpWrite, TwoPersonRequirement::One);
+        let seal = SessionSeal { created_at_ms: 500, ..Default::default() };
+        assert_eq!(
+            verify_bundle(
+                &[(&accepted).into(), (&export_two).into(), (&write_one).into(), (&seal).into()],
+                &receipts,
+                &trust,
+                1_000,
+            ),
+            Ok(())
+        );
+
+        let other_charter = record("charter-v0", 1_000);
+        let ahead = record("charter-v1", 1_101);
+        let export_one = package(RequestedOperation::OpExport, TwoPersonRequirement::One);
+        let future_seal = SessionSeal { created_at_ms: 2_000, ..Default::default() };
+        assert_eq!(
+            verify_bundle(
+                &[
+                    (&other_charter).into(),
+                    (&ahead).into(),
+                    (&export_one).into(),
+                    (&future_seal).into(),
+                ],
+                &receipts,
+                &trust,
+                1_000,
+            ),
+            Err(vec![
+                policy(
+                    0,
+                    "ucf.v1.ExperienceRecord",
+                    PolicyReason::CharterNotAccepted("charter-v0".to_string()),
+                ),
+                policy(
+                    1,
+                    "ucf.v1.ExperienceRecord",
+                    PolicyReason::TimestampAhead { timestamp_ms: 1_101, now_ms: 1_000 },
+                ),
+                policy(
+                    2,
+                    "ucf.v1.ApprovalArtifactPackage",
+                    PolicyReason::TwoPersonRequired(RequestedOperation::OpExport),
+                ),
+                policy(
+                    3,
+                    "ucf.v1.SessionSeal",
+                    PolicyReason::TimestampAhead { timestamp_ms: 2_000, now_ms: 1_000 },
+                ),
+            ])
+        );
+
+        // With no accepted charters, every charter is refused.
+        assert_eq!(
+            verify_bundle(&[(&accepted).into()], &receipts, &TrustConfig::new(), 2_000),
+            Err(vec![policy(
+                0,
+                "ucf.v1.ExperienceRecord",
+                PolicyReason::CharterNotAccepted("charter-v1".to_string()),
+            )])
+        );
+    }
+}
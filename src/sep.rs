@@ -0,0 +1,806 @@
+//! Semantic checks, chain verification, and completeness reports for session
+//! event protocol (SEP) events.
+//!
+//! An event's `event_digest` commits to the rest of the event: it is
+//! `digest32("UCF:SEP:EVENT", "ucf.v1.SepEvent", "1", bytes)` over the
+//! canonical bytes of the event with `event_digest` and `attestation_sig`
+//! cleared. The signature is excluded because it attests to the event and so
+//! cannot be an input to the digest.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+use subtle::ConstantTimeEq;
+
+use crate::digest::{compute_self_digest, SelfDigested};
+use crate::ucf::v1::{
+    CompletenessReport, CompletenessStatus, Digest32, ExperienceRecord, ReasonCodes, Ref, SepEvent,
+    SepEventType, SessionSeal,
+};
+use crate::Domain;
+
+pub const SEP_EVENT_DOMAIN: &str = Domain::SepEvent.as_str();
+const SEP_EVENT_SCHEMA: &str = "ucf.v1.SepEvent";
+const COMPLETENESS_REPORT_SCHEMA: &str = "ucf.v1.CompletenessReport";
+const SESSION_SEAL_SCHEMA: &str = "ucf.v1.SessionSeal";
+
+/// Reason code for a report with expected edges absent from the event graph.
+pub const EDGE_GAP: &str = "edge-gap";
+/// Reason code for a report with expected events absent from the session.
+pub const MISSING_NODE: &str = "missing-node";
+
+/// Errors returned by [`validate_object_ref`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ObjectRefError {
+    /// `event_type` is unspecified or not a known [`SepEventType`].
+    UnknownEventType(i32),
+    /// The event has no `object_ref`.
+    Missing,
+    /// `object_ref.uri` has no `scheme://` prefix.
+    MissingScheme(String),
+    /// `object_ref.uri` uses a scheme other than the one `event_type` refers to.
+    SchemeMismatch { event_type: i32, expected: &'static str, found: String },
+}
+
+impl fmt::Display for ObjectRefError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectRefError::UnknownEventType(event_type) => {
+                write!(f, "unknown SEP event_type {event_type}")
+            }
+            ObjectRefError::Missing => write!(f, "SEP event has no object_ref"),
+            ObjectRefError::MissingScheme(uri) => {
+                write!(f, "object_ref uri {uri:?} has no scheme")
+            }
+            ObjectRefError::SchemeMismatch { event_type, expected, found } => write!(
+                f,
+                "SEP event_type {event_type} expects a {expected}:// object_ref, found {found}://"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ObjectRefError {}
+
+/// The `object_ref` URI scheme required for `event_type`, if it is constrained.
+///
+/// Event types without a dedicated object kind accept any scheme.
+pub fn expected_object_scheme(event_type: SepEventType) -> Option<&'static str> {
+    match event_type {
+        SepEventType::EvIntent => Some("intent"),
+        SepEventType::EvDecision => Some("decision"),
+        SepEventType::EvOutcome => Some("outcome"),
+        _ => None,
+    }
+}
+
+/// Confirm `event.object_ref` points at the kind of object its `event_type` declares.
+pub fn validate_object_ref(event: &SepEvent) -> Result<(), ObjectRefError> {
+    let event_type = match SepEventType::try_from(event.event_type) {
+        Ok(SepEventType::Unspecified) | Err(_) => {
+            return Err(ObjectRefError::UnknownEventType(event.event_type));
+        }
+        Ok(event_type) => event_type,
+    };
+    let uri = &event.object_ref.as_ref().ok_or(ObjectRefError::Missing)?.uri;
+    let (scheme, _) =
+        uri.split_once("://").ok_or_else(|| ObjectRefError::MissingScheme(uri.clone()))?;
+
+    match expected_object_scheme(event_type) {
+        Some(expected) if scheme != expected => Err(ObjectRefError::SchemeMismatch {
+            event_type: event.event_type,
+            expected,
+            found: scheme.to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Errors returned by [`verify_sep_chain`] and [`verify_sep_chain_across_epochs`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SepChainError {
+    /// The event has no `event_digest`.
+    EventDigestMissing { event_id: String },
+    /// `event_digest` differs from [`compute_sep_event_digest`] of the event.
+    EventDigestMismatch { event_id: String, expected: [u8; 32], found: Vec<u8> },
+    /// `prev_event_digest` differs from the previous event's `event_digest`.
+    PrevDigestMismatch { event_id: String, expected: Vec<u8>, found: Vec<u8> },
+    /// `timestamp_ms` is earlier than the previous event's.
+    TimestampDecreased { event_id: String, prev_timestamp_ms: u64, timestamp_ms: u64 },
+    /// `session_id` differs from the first event's.
+    SessionMismatch { event_id: String, expected: String, found: String },
+    /// `epoch_id` differs from the previous event's, e.g. after key rotation.
+    EpochChanged(EpochChange),
+}
+
+impl fmt::Display for SepChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SepChainError::EventDigestMissing { event_id } => {
+                write!(f, "SEP event {event_id} has no event_digest")
+            }
+            SepChainError::EventDigestMismatch { event_id, expected, found } => write!(
+                f,
+                "SEP event {event_id} event_digest {} does not match its content digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+            SepChainError::PrevDigestMismatch { event_id, expected, found } => write!(
+                f,
+                "SEP event {event_id} prev_event_digest {} does not match previous event digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+            SepChainError::TimestampDecreased { event_id, prev_timestamp_ms, timestamp_ms } => {
+                write!(
+                    f,
+                    "SEP event {event_id} timestamp {timestamp_ms} precedes {prev_timestamp_ms}"
+                )
+            }
+            SepChainError::SessionMismatch { event_id, expected, found } => {
+                write!(f, "SEP event {event_id} belongs to session {found}, not {expected}")
+            }
+            SepChainError::EpochChanged(change) => write!(
+                f,
+                "SEP event {} moves from epoch {} to {}",
+                change.event_id, change.prev_epoch_id, change.epoch_id
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SepChainError {}
+
+/// The point in a SEP chain where `epoch_id` changes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpochChange {
+    /// The first event carrying the new epoch.
+    pub event_id: String,
+    pub prev_epoch_id: u64,
+    pub epoch_id: u64,
+}
+
+impl SelfDigested for SepEvent {
+    const SCHEMA_ID: &'static str = SEP_EVENT_SCHEMA;
+
+    fn domain() -> &'static str {
+        SEP_EVENT_DOMAIN
+    }
+
+    fn take_digest(&mut self) -> Option<Digest32> {
+        self.event_digest.take()
+    }
+
+    fn clear_attestation(&mut self) {
+        self.attestation_sig = None;
+    }
+}
+
+/// Content digest of `event`, ignoring `event_digest` and `attestation_sig`.
+pub fn compute_sep_event_digest(event: &SepEvent) -> [u8; 32] {
+    compute_self_digest(event)
+}
+
+/// Confirm `event_digest` is [`compute_sep_event_digest`] of `event`.
+pub fn verify_sep_event_digest(event: &SepEvent) -> Result<(), SepChainError> {
+    let event_id = event.event_id.clone();
+    let found = event
+        .event_digest
+        .as_ref()
+        .ok_or(SepChainError::EventDigestMissing { event_id: event_id.clone() })?;
+    let expected = compute_sep_event_digest(event);
+    if bool::from(found.value.as_slice().ct_eq(&expected)) {
+        Ok(())
+    } else {
+        Err(SepChainError::EventDigestMismatch { event_id, expected, found: found.value.clone() })
+    }
+}
+
+/// Confirm `events` form one unbroken SEP chain within a single epoch.
+///
+/// An epoch change is reported as [`SepChainError::EpochChanged`]; use
+/// [`verify_sep_chain_across_epochs`] for chains that span key rotation.
+pub fn verify_sep_chain(events: &[SepEvent]) -> Result<(), SepChainError> {
+    match verify_sep_chain_across_epochs(events)?.into_iter().next() {
+        Some(change) => Err(SepChainError::EpochChanged(change)),
+        None => Ok(()),
+    }
+}
+
+/// Confirm `events` form one unbroken SEP chain, returning every epoch change.
+///
+/// Every event must pass [`verify_sep_event_digest`], so a link can only be
+/// made to the content the digest commits to. Each event after the first must
+/// carry the previous event's `event_digest` as its `prev_event_digest`, a
+/// `timestamp_ms` no earlier than the previous event's, and the first event's
+/// `session_id`. The first event's `prev_event_digest` is not checked.
+pub fn verify_sep_chain_across_epochs(
+    events: &[SepEvent],
+) -> Result<Vec<EpochChange>, SepChainError> {
+    let mut changes = Vec::new();
+    let mut prev: Option<&SepEvent> = None;
+    for event in events {
+        verify_sep_event_digest(event)?;
+        let Some(prev_event) = prev else {
+            prev = Some(event);
+            continue;
+        };
+
+        let session_id = &events[0].session_id;
+        if &event.session_id != session_id {
+            return Err(SepChainError::SessionMismatch {
+                event_id: event.event_id.clone(),
+                expected: session_id.clone(),
+                found: event.session_id.clone(),
+            });
+        }
+        if event.timestamp_ms < prev_event.timestamp_ms {
+            return Err(SepChainError::TimestampDecreased {
+                event_id: event.event_id.clone(),
+                prev_timestamp_ms: prev_event.timestamp_ms,
+                timestamp_ms: event.timestamp_ms,
+            });
+        }
+        let expected = prev_event.event_digest.as_ref().map(|digest| &digest.value);
+        let found = event.prev_event_digest.as_ref().map(|digest| &digest.value);
+        if found != expected {
+            return Err(SepChainError::PrevDigestMismatch {
+                event_id: event.event_id.clone(),
+                expected: expected.cloned().unwrap_or_default(),
+                found: found.cloned().unwrap_or_default(),
+            });
+        }
+        if event.epoch_id != prev_event.epoch_id {
+            changes.push(EpochChange {
+                event_id: event.event_id.clone(),
+                prev_epoch_id: prev_event.epoch_id,
+                epoch_id: event.epoch_id,
+            });
+        }
+        prev = Some(event);
+    }
+    Ok(changes)
+}
+
+/// Build the [`CompletenessReport`] of `session_id` against `expected_edges`.
+///
+/// Only events of `session_id` are considered. An event links to the event
+/// whose `event_digest` equals its `prev_event_digest`, giving the edge
+/// `(prev.event_id, event.event_id)`. Every `(from, to)` in `expected_edges`
+/// that is not such an edge is reported in `missing_edges` as `"from->to"`,
+/// and every event id it names that is absent is reported in `missing_nodes`
+/// as `sep://evt/<event_id>`. Both lists and `reason_codes` are sorted and
+/// deduplicated.
+///
+/// The status is `CompOk` when nothing is missing, `CompDegraded` when only
+/// edges are missing, and `CompFail` when events are missing. `report_digest`
+/// is set to [`completeness_report_digest`]; `report_id` and
+/// `proof_receipt_ref` are left for the caller.
+pub fn build_completeness_report(
+    session_id: &str,
+    events: &[SepEvent],
+    expected_edges: &[(String, String)],
+) -> CompletenessReport {
+    let events: Vec<&SepEvent> =
+        events.iter().filter(|event| event.session_id == session_id).collect();
+    let by_digest: HashMap<&[u8], &str> = events
+        .iter()
+        .filter_map(|event| {
+            let digest = event.event_digest.as_ref()?;
+            Some((digest.value.as_slice(), event.event_id.as_str()))
+        })
+        .collect();
+    let edges: HashSet<(&str, &str)> = events
+        .iter()
+        .filter_map(|event| {
+            let prev = by_digest.get(event.prev_event_digest.as_ref()?.value.as_slice())?;
+            Some((*prev, event.event_id.as_str()))
+        })
+        .collect();
+    let nodes: HashSet<&str> = events.iter().map(|event| event.event_id.as_str()).collect();
+
+    let mut missing_nodes = BTreeSet::new();
+    let mut missing_edges = BTreeSet::new();
+    for (from, to) in expected_edges {
+        if !edges.contains(&(from.as_str(), to.as_str())) {
+            missing_edges.insert(format!("{from}->{to}"));
+        }
+        for id in [from, to] {
+            if !nodes.contains(id.as_str()) {
+                missing_nodes.insert(id.as_str());
+            }
+        }
+    }
+
+    let mut codes = Vec::new();
+    if !missing_edges.is_empty() {
+        codes.push(EDGE_GAP.to_string());
+    }
+    if !missing_nodes.is_empty() {
+        codes.push(MISSING_NODE.to_string());
+    }
+    let status = if !missing_nodes.is_empty() {
+        CompletenessStatus::CompFail
+    } else if !missing_edges.is_empty() {
+        CompletenessStatus::CompDegraded
+    } else {
+        CompletenessStatus::CompOk
+    };
+
+    let mut report = CompletenessReport {
+        session_id: session_id.to_string(),
+        status: status as i32,
+        missing_nodes: missing_nodes
+            .into_iter()
+            .map(|id| Ref { uri: format!("sep://evt/{id}"), label: id.to_string() })
+            .collect(),
+        missing_edges: missing_edges.into_iter().collect(),
+        reason_codes: (!codes.is_empty()).then_some(ReasonCodes { codes }),
+        ..Default::default()
+    };
+    report.report_digest = Some(Digest32 { value: completeness_report_digest(&report).to_vec() });
+    report
+}
+
+/// `digest32("ucf-core", "ucf.v1.CompletenessReport", "1", ..)` over the canonical
+/// bytes of `report` with `report_digest` cleared.
+pub fn completeness_report_digest(report: &CompletenessReport) -> [u8; 32] {
+    compute_self_digest(report)
+}
+
+impl SelfDigested for CompletenessReport {
+    const SCHEMA_ID: &'static str = COMPLETENESS_REPORT_SCHEMA;
+
+    fn domain() -> &'static str {
+        Domain::Core.as_str()
+    }
+
+    fn take_digest(&mut self) -> Option<Digest32> {
+        self.report_digest.take()
+    }
+}
+
+/// Errors returned by [`verify_session_seal`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SealError {
+    /// The seal has no `seal_digest`.
+    SealDigestMissing,
+    /// `seal_digest` differs from [`session_seal_digest`] of the seal.
+    SealDigestMismatch { expected: [u8; 32], found: Vec<u8> },
+    /// An event belongs to a session other than the seal's.
+    SessionMismatch { event_id: String, expected: String, found: String },
+    /// The seal has no `final_event_digest`.
+    FinalEventDigestMissing,
+    /// There are no events, or the last event has no `event_digest`.
+    TailEventDigestMissing,
+    /// `final_event_digest` is not the last event's `event_digest`.
+    FinalEventMismatch { seal: Vec<u8>, tail: Vec<u8> },
+    /// The seal has no `final_record_digest`.
+    FinalRecordDigestMissing,
+    /// There are no records, or the last record has no `record_digest`.
+    TailRecordDigestMissing,
+    /// `final_record_digest` is not the last record's `record_digest`.
+    FinalRecordMismatch { seal: Vec<u8>, tail: Vec<u8> },
+}
+
+impl fmt::Display for SealError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SealError::SealDigestMissing => write!(f, "session seal has no seal_digest"),
+            SealError::SealDigestMismatch { expected, found } => write!(
+                f,
+                "seal_digest {} does not match its content digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+            SealError::SessionMismatch { event_id, expected, found } => {
+                write!(f, "SEP event {event_id} belongs to session {found}, not sealed {expected}")
+            }
+            SealError::FinalEventDigestMissing => {
+                write!(f, "session seal has no final_event_digest")
+            }
+            SealError::TailEventDigestMissing => write!(f, "last SEP event has no event_digest"),
+            SealError::FinalEventMismatch { seal, tail } => write!(
+                f,
+                "final_event_digest {} does not match last event digest {}",
+                hex::encode(seal),
+                hex::encode(tail)
+            ),
+            SealError::FinalRecordDigestMissing => {
+                write!(f, "session seal has no final_record_digest")
+            }
+            SealError::TailRecordDigestMissing => {
+                write!(f, "last experience record has no record_digest")
+            }
+            SealError::FinalRecordMismatch { seal, tail } => write!(
+                f,
+                "final_record_digest {} does not match last record digest {}",
+                hex::encode(seal),
+                hex::encode(tail)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SealError {}
+
+/// `digest32("ucf-core", "ucf.v1.SessionSeal", "1", ..)` over the canonical
+/// bytes of `seal` with `seal_digest` cleared.
+pub fn session_seal_digest(seal: &SessionSeal) -> [u8; 32] {
+    compute_self_digest(seal)
+}
+
+impl SelfDigested for SessionSeal {
+    const SCHEMA_ID: &'static str = SESSION_SEAL_SCHEMA;
+
+    fn domain() -> &'static str {
+        Domain::Core.as_str()
+    }
+
+    fn take_digest(&mut self) -> Option<Digest32> {
+        self.seal_digest.take()
+    }
+}
+
+/// Confirm `seal` commits to the tails of the session's SEP and experience chains.
+///
+/// `seal_digest` must be [`session_seal_digest`] of the seal, every event must
+/// carry the seal's `session_id`, and `final_event_digest` and
+/// `final_record_digest` must equal the `event_digest` of the last event and
+/// the `finalization_header.record_digest` of the last record. Every one of
+/// those digests must be present; an empty chain has no tail and never
+/// matches. Chain linkage itself is checked by [`verify_sep_chain`] and
+/// [`crate::experience::verify_experience_chain`].
+pub fn verify_session_seal(
+    seal: &SessionSeal,
+    events: &[SepEvent],
+    records: &[ExperienceRecord],
+) -> Result<(), SealError> {
+    let found = seal.seal_digest.as_ref().ok_or(SealError::SealDigestMissing)?;
+    let expected = session_seal_digest(seal);
+    if !bool::from(found.value.as_slice().ct_eq(&expected)) {
+        return Err(SealError::SealDigestMismatch { expected, found: found.value.clone() });
+    }
+
+    if let Some(event) = events.iter().find(|event| event.session_id != seal.session_id) {
+        return Err(SealError::SessionMismatch {
+            event_id: event.event_id.clone(),
+            expected: seal.session_id.clone(),
+            found: event.session_id.clone(),
+        });
+    }
+
+    let sealed_event =
+        &seal.final_event_digest.as_ref().ok_or(SealError::FinalEventDigestMissing)?.value;
+    let tail_event = &events
+        .last()
+        .and_then(|event| event.event_digest.as_ref())
+        .ok_or(SealError::TailEventDigestMissing)?
+        .value;
+    if sealed_event != tail_event {
+        return Err(SealError::FinalEventMismatch {
+            seal: sealed_event.clone(),
+            tail: tail_event.clone(),
+        });
+    }
+
+    let sealed_record =
+        &seal.final_record_digest.as_ref().ok_or(SealError::FinalRecordDigestMissing)?.value;
+    let tail_record = &records
+        .last()
+        .and_then(|record| record.finalization_header.as_ref())
+        .and_then(|header| header.record_digest.as_ref())
+        .ok_or(SealError::TailRecordDigestMissing)?
+        .value;
+    if sealed_record != tail_record {
+        return Err(SealError::FinalRecordMismatch {
+            seal: sealed_record.clone(),
+            tail: tail_record.clone(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::decode_fixture;
+
+    fn event(event_type: SepEventType, uri: &str) -> SepEvent {
+        SepEvent {
+            event_type: event_type as i32,
+            object_ref: Some(Ref { uri: uri.to_string(), label: String::new() }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn object_ref_scheme_follows_event_type() {
+        assert_eq!(
+            validate_object_ref(&event(SepEventType::EvIntent, "intent://primary/42")),
+            Ok(())
+        );
+        assert_eq!(
+            validate_object_ref(&event(SepEventType::EvDecision, "decision://approval")),
+            Ok(())
+        );
+        assert_eq!(validate_object_ref(&event(SepEventType::EvGrant, "grant://anything")), Ok(()));
+
+        assert_eq!(
+            validate_object_ref(&event(SepEventType::EvIntent, "decision://approval")),
+            Err(ObjectRefError::SchemeMismatch {
+                event_type: SepEventType::EvIntent as i32,
+                expected: "intent",
+                found: "decision".to_string(),
+            })
+        );
+        assert_eq!(
+            validate_object_ref(&event(SepEventType::EvOutcome, "result")),
+            Err(ObjectRefError::MissingScheme("result".to_string()))
+        );
+        assert_eq!(
+            validate_object_ref(&event(SepEventType::Unspecified, "intent://x")),
+            Err(ObjectRefError::UnknownEventType(0))
+        );
+        let unreferenced = SepEvent { object_ref: None, ..event(SepEventType::EvIntent, "") };
+        assert_eq!(validate_object_ref(&unreferenced), Err(ObjectRefError::Missing));
+    }
+
+    fn sep_fixture_chain() -> Vec<SepEvent> {
+        ["sep_event_chain_1", "sep_event_chain_2", "sep_event_chain_3"]
+            .into_iter()
+            .map(decode_fixture)
+            .collect()
+    }
+
+    #[test]
+    fn sep_fixture_chain_links_across_one_rotation() {
+        let events = sep_fixture_chain();
+        let rotation =
+            EpochChange { event_id: "evt-3".to_string(), prev_epoch_id: 100, epoch_id: 101 };
+        assert_eq!(verify_sep_chain_across_epochs(&events), Ok(vec![rotation.clone()]));
+        assert_eq!(verify_sep_chain(&events), Err(SepChainError::EpochChanged(rotation)));
+        assert_eq!(verify_sep_chain(&events[..2]), Ok(()));
+        assert_eq!(verify_sep_chain(&[]), Ok(()));
+    }
+
+    #[test]
+    fn event_digest_binds_content() {
+        let mut event = sep_fixture_chain().remove(0);
+        assert_eq!(verify_sep_event_digest(&event), Ok(()));
+
+        let digest = compute_sep_event_digest(&event);
+        assert_eq!(event.event_digest.as_ref().unwrap().value, digest.to_vec());
+        assert_eq!(compute_sep_event_digest(&event), digest, "event_digest is not an input");
+        event.attestation_sig = Some(Default::default());
+        assert_eq!(verify_sep_event_digest(&event), Ok(()), "attestation_sig is not an input");
+
+        event.object_ref.as_mut().unwrap().uri.push_str("-tampered");
+        assert_ne!(compute_sep_event_digest(&event), digest);
+        assert_eq!(
+            verify_sep_event_digest(&event),
+            Err(SepChainError::EventDigestMismatch {
+                event_id: event.event_id.clone(),
+                expected: compute_sep_event_digest(&event),
+                found: digest.to_vec(),
+            })
+        );
+
+        event.event_digest = None;
+        assert_eq!(
+            verify_sep_event_digest(&event),
+            Err(SepChainError::EventDigestMissing { event_id: event.event_id.clone() })
+        );
+    }
+
+    #[test]
+    fn sep_chain_detects_gaps_reordering_and_foreign_sessions() {
+        let events = sep_fixture_chain();
+        let digest = |event: &SepEvent| event.event_digest.clone().unwrap().value;
+
+        assert_eq!(
+            verify_sep_chain(&[events[0].clone(), events[2].clone()]),
+            Err(SepChainError::PrevDigestMismatch {
+                event_id: "evt-3".to_string(),
+                expected: digest(&events[0]),
+                found: digest(&events[1]),
+            })
+        );
+        assert_eq!(
+            verify_sep_chain(&[events[1].clone(), events[0].clone()]),
+            Err(SepChainError::TimestampDecreased {
+                event_id: "evt-1".to_string(),
+                prev_timestamp_ms: 1_700_002_500,
+                timestamp_ms: 1_700_002_000,
+            })
+        );
+
+        let mut foreign = events[1].clone();
+        foreign.session_id = "session-other".to_string();
+        foreign.event_digest =
+            Some(Digest32 { value: compute_sep_event_digest(&foreign).to_vec() });
+        assert_eq!(
+            verify_sep_chain(&[events[0].clone(), foreign]),
+            Err(SepChainError::SessionMismatch {
+                event_id: "evt-2".to_string(),
+                expected: "session-9000".to_string(),
+                found: "session-other".to_string(),
+            })
+        );
+
+        let mut undigested = events[1].clone();
+        undigested.event_digest = None;
+        assert_eq!(
+            verify_sep_chain(&[events[0].clone(), undigested]),
+            Err(SepChainError::EventDigestMissing { event_id: "evt-2".to_string() })
+        );
+    }
+
+    #[test]
+    fn sep_chain_rejects_tampered_event() {
+        let mut events = sep_fixture_chain();
+        events[1].reason_codes = Some(ReasonCodes { codes: vec!["forged".to_string()] });
+        assert_eq!(
+            verify_sep_chain_across_epochs(&events),
+            Err(SepChainError::EventDigestMismatch {
+                event_id: "evt-2".to_string(),
+                expected: compute_sep_event_digest(&events[1]),
+                found: events[1].event_digest.clone().unwrap().value,
+            })
+        );
+    }
+
+    fn edges(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(from, to)| (from.to_string(), to.to_string())).collect()
+    }
+
+    #[test]
+    fn completeness_report_lists_missing_nodes_and_edges() {
+        let events = sep_fixture_chain();
+
+        let complete = build_completeness_report(
+            "session-9000",
+            &events,
+            &edges(&[("evt-1", "evt-2"), ("evt-2", "evt-3")]),
+        );
+        assert_eq!(complete.status, CompletenessStatus::CompOk as i32);
+        assert!(complete.missing_nodes.is_empty() && complete.missing_edges.is_empty());
+        assert_eq!(complete.reason_codes, None);
+        assert_eq!(
+            complete.report_digest.as_ref().unwrap().value,
+            completeness_report_digest(&complete).to_vec()
+        );
+
+        let degraded =
+            build_completeness_report("session-9000", &events, &edges(&[("evt-1", "evt-3")]));
+        assert_eq!(degraded.status, CompletenessStatus::CompDegraded as i32);
+        assert_eq!(degraded.missing_edges, vec!["evt-1->evt-3".to_string()]);
+        assert_eq!(degraded.reason_codes.unwrap().codes, vec![EDGE_GAP.to_string()]);
+
+        let failed = build_completeness_report(
+            "session-9000",
+            &events,
+            &edges(&[("evt-2", "evt-4"), ("evt-1", "evt-3"), ("evt-2", "evt-3")]),
+        );
+        assert_eq!(failed.status, CompletenessStatus::CompFail as i32);
+        assert_eq!(
+            failed.missing_nodes,
+            vec![Ref { uri: "sep://evt/evt-4".to_string(), label: "evt-4".to_string() }]
+        );
+        assert_eq!(
+            failed.missing_edges,
+            vec!["evt-1->evt-3".to_string(), "evt-2->evt-4".to_string()]
+        );
+        assert_eq!(
+            failed.reason_codes.as_ref().unwrap().codes,
+            vec![EDGE_GAP.to_string(), MISSING_NODE.to_string()]
+        );
+
+        let other_session =
+            build_completeness_report("session-other", &events, &edges(&[("evt-1", "evt-2")]));
+        assert_eq!(other_session.missing_nodes.len(), 2);
+    }
+
+    fn sealed(events: &[SepEvent], records: &[ExperienceRecord]) -> SessionSeal {
+        let header = records.last().unwrap().finalization_header.as_ref().unwrap();
+        let mut seal = SessionSeal {
+            seal_id: "seal-1".to_string(),
+            session_id: events[0].session_id.clone(),
+            final_event_digest: events.last().unwrap().event_digest.clone(),
+            final_record_digest: header.record_digest.clone(),
+            created_at_ms: 1_700_000_000,
+            ..Default::default()
+        };
+        seal.seal_digest = Some(Digest32 { value: session_seal_digest(&seal).to_vec() });
+        seal
+    }
+
+    #[test]
+    fn seal_fixture_names_the_chain_tail() {
+        let seal: SessionSeal = decode_fixture("session_seal");
+        let tail = sep_fixture_chain().pop().unwrap();
+        assert_eq!(seal.final_event_digest, tail.event_digest);
+    }
+
+    #[test]
+    fn session_seal_commits_to_chain_tails() {
+        let events = sep_fixture_chain();
+        let records: Vec<ExperienceRecord> =
+            ["experience_rt_perception", "experience_rt_action_exec", "experience_rt_output"]
+                .into_iter()
+                .map(decode_fixture)
+                .collect();
+        let seal = sealed(&events, &records);
+        assert_eq!(verify_session_seal(&seal, &events, &records), Ok(()));
+
+        let truncated = &events[..2];
+        assert_eq!(
+            verify_session_seal(&seal, truncated, &records),
+            Err(SealError::FinalEventMismatch {
+                seal: seal.final_event_digest.clone().unwrap().value,
+                tail: truncated[1].event_digest.clone().unwrap().value,
+            })
+        );
+        assert!(matches!(
+            verify_session_seal(&seal, &events, &records[..2]),
+            Err(SealError::FinalRecordMismatch { .. })
+        ));
+        assert_eq!(
+            verify_session_seal(&seal, &events, &[]),
+            Err(SealError::TailRecordDigestMissing)
+        );
+        assert_eq!(
+            verify_session_seal(&seal, &[], &records),
+            Err(SealError::TailEventDigestMissing)
+        );
+
+        let mut foreign = events.clone();
+        foreign[1].session_id = "other-session".to_string();
+        assert_eq!(
+            verify_session_seal(&seal, &foreign, &records),
+            Err(SealError::SessionMismatch {
+                event_id: foreign[1].event_id.clone(),
+                expected: seal.session_id.clone(),
+                found: "other-session".to_string(),
+            })
+        );
+
+        // A seal and tail that both lack a digest do not match each other.
+        let mut undigested = events.clone();
+        undigested.last_mut().unwrap().event_digest = None;
+        let mut blank = SessionSeal { final_event_digest: None, ..seal.clone() };
+        blank.seal_digest = Some(Digest32 { value: session_seal_digest(&blank).to_vec() });
+        assert_eq!(
+            verify_session_seal(&blank, &undigested, &records),
+            Err(SealError::FinalEventDigestMissing)
+        );
+        assert_eq!(
+            verify_session_seal(&seal, &undigested, &records),
+            Err(SealError::TailEventDigestMissing)
+        );
+        let mut unfinalized = records.clone();
+        let header = unfinalized.last_mut().unwrap().finalization_header.as_mut().unwrap();
+        header.record_digest = None;
+        let mut blank = SessionSeal { final_record_digest: None, ..seal.clone() };
+        blank.seal_digest = Some(Digest32 { value: session_seal_digest(&blank).to_vec() });
+        assert_eq!(
+            verify_session_seal(&blank, &events, &unfinalized),
+            Err(SealError::FinalRecordDigestMissing)
+        );
+        assert_eq!(
+            verify_session_seal(&seal, &events, &unfinalized),
+            Err(SealError::TailRecordDigestMissing)
+        );
+
+        let restamped = SessionSeal { created_at_ms: 1, ..seal.clone() };
+        assert!(matches!(
+            verify_session_seal(&restamped, &events, &records),
+            Err(SealError::SealDigestMismatch { .. })
+        ));
+        let unsealed = SessionSeal { seal_digest: None, ..seal };
+        assert_eq!(
+            verify_session_seal(&unsealed, &events, &records),
+            Err(SealError::SealDigestMissing)
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! serde representations of the protobuf field shapes prost generates.
+//!
+//! With the `serde` feature, `build.rs` derives `Serialize` and `Deserialize`
+//! on every generated type and points each `bytes` and enum field at the
+//! helpers here:
+//!
+//! - `bytes` fields are unpadded base64url strings.
+//! - Enum fields are their proto variant names, e.g. `"CHANNEL_REALTIME"`.
+//!   Values with no variant are written as numbers so nothing is lost, and
+//!   either form is accepted when deserializing.
+//!
+//! Missing fields deserialize to their protobuf defaults.
+
+use std::marker::PhantomData;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::codec::{base64url, decode_base64url};
+
+/// A `bytes` field shape produced by prost: singular, proto3 optional, or repeated.
+pub(crate) trait BytesField: Sized {
+    fn serialize_bytes<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+    fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+fn bytes_from_str<E: de::Error>(text: &str) -> Result<Vec<u8>, E> {
+    decode_base64url(text).ok_or_else(|| E::custom("expected unpadded base64url"))
+}
+
+impl BytesField for Vec<u8> {
+    fn serialize_bytes<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64url(self))
+    }
+
+    fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        bytes_from_str(&String::deserialize(deserializer)?)
+    }
+}
+
+impl BytesField for Option<Vec<u8>> {
+    fn serialize_bytes<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_deref().map(base64url).serialize(serializer)
+    }
+
+    fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<String>::deserialize(deserializer)?.map(|text| bytes_from_str(&text)).transpose()
+    }
+}
+
+impl BytesField for Vec<Vec<u8>> {
+    fn serialize_bytes<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(|bytes| base64url(bytes)))
+    }
+
+    fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<String>::deserialize(deserializer)?.iter().map(|text| bytes_from_str(text)).collect()
+    }
+}
+
+/// `with` target that build.rs attaches to every `bytes` field.
+pub(crate) mod bytes {
+    use super::*;
+
+    pub(crate) fn serialize<T: BytesField, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize_bytes(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, T: BytesField, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::deserialize_bytes(deserializer)
+    }
+}
+
+/// Name lookups for a generated protobuf enum; implemented by build.rs output.
+pub(crate) trait ProtoEnum {
+    fn name(value: i32) -> Option<&'static str>;
+    fn value(name: &str) -> Option<i32>;
+}
+
+macro_rules! proto_enum {
+    ($($enum:path),* $(,)?) => {
+        $(
+            impl ProtoEnum for $enum {
+                fn name(value: i32) -> Option<&'static str> {
+                    <$enum>::try_from(value).ok().map(|variant| variant.as_str_name())
+                }
+
+                fn value(name: &str) -> Option<i32> {
+                    <$enum>::from_str_name(name).map(|variant| variant as i32)
+                }
+            }
+        )*
+    };
+}
+
+include!(concat!(env!("OUT_DIR"), "/ucf_serde_enums.rs"));
+
+/// One enum value: its variant name, or the bare number if it has none.
+struct EnumValue<E>(i32, PhantomData<E>);
+
+impl<E: ProtoEnum> Serialize for EnumValue<E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match E::name(self.0) {
+            Some(name) => serializer.serialize_str(name),
+            None => serializer.serialize_i32(self.0),
+        }
+    }
+}
+
+impl<'de, E: ProtoEnum> Deserialize<'de> for EnumValue<E> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NameOrNumber<E>(PhantomData<E>);
+
+        impl<E: ProtoEnum> Visitor<'_> for NameOrNumber<E> {
+            type Value = i32;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("an enum variant name or number")
+            }
+
+            fn visit_str<Err: de::Error>(self, name: &str) -> Result<i32, Err> {
+                E::value(name).ok_or_else(|| Err::custom(format!("unknown enum variant {name}")))
+            }
+
+            fn visit_i64<Err: de::Error>(self, value: i64) -> Result<i32, Err> {
+                i32::try_from(value).map_err(|_| Err::custom("enum value out of range"))
+            }
+
+            fn visit_u64<Err: de::Error>(self, value: u64) -> Result<i32, Err> {
+                i32::try_from(value).map_err(|_| Err::custom("enum value out of range"))
+            }
+        }
+
+        let value = deserializer.deserialize_any(NameOrNumber::<E>(PhantomData))?;
+        Ok(EnumValue(value, PhantomData))
+    }
+}
+
+/// An enum field shape produced by prost: singular, proto3 optional, or repeated.
+pub(crate) trait EnumField: Sized {
+    fn serialize_enum<E: ProtoEnum, S: Serializer>(&self, serializer: S)
+        -> Result<S::Ok, S::Error>;
+    fn deserialize_enum<'de, E: ProtoEnum, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error>;
+}
+
+impl EnumField for i32 {
+    fn serialize_enum<E: ProtoEnum, S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        EnumValue::<E>(*self, PhantomData).serialize(serializer)
+    }
+
+    fn deserialize_enum<'de, E: ProtoEnum, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Ok(EnumValue::<E>::deserialize(deserializer)?.0)
+    }
+}
+
+impl EnumField for Option<i32> {
+    fn serialize_enum<E: ProtoEnum, S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.map(|value| EnumValue::<E>(value, PhantomData)).serialize(serializer)
+    }
+
+    fn deserialize_enum<'de, E: ProtoEnum, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Ok(Option::<EnumValue<E>>::deserialize(deserializer)?.map(|value| value.0))
+    }
+}
+
+impl EnumField for Vec<i32> {
+    fn serialize_enum<E: ProtoEnum, S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(|value| EnumValue::<E>(*value, PhantomData)))
+    }
+
+    fn deserialize_enum<'de, E: ProtoEnum, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let values = Vec::<EnumValue<E>>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(|value| value.0).collect())
+    }
+}
+
+/// `serialize_with` target that build.rs attaches to every enum field.
+pub(crate) fn serialize_enum<E: ProtoEnum, T: EnumField, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.serialize_enum::<E, S>(serializer)
+}
+
+/// `deserialize_with` target that build.rs attaches to every enum field.
+pub(crate) fn deserialize_enum<'de, E: ProtoEnum, T: EnumField, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    T::deserialize_enum::<E, D>(deserializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::decode_fixture;
+    use crate::ucf::v1::{ExperienceRecord, ReasonCodes, RecordType};
+
+    #[test]
+    fn experience_record_round_trips_through_json() {
+        let record: ExperienceRecord = decode_fixture("experience_rt_action_exec");
+
+        let json = serde_json::to_value(&record).expect("record serializes");
+        assert_eq!(json["record_type"], "RECORD_TYPE_RT_ACTION_EXEC");
+        let digest = &json["finalization_header"]["record_digest"]["value"];
+        assert!(digest.is_string(), "bytes serialize as base64url");
+
+        let decoded: ExperienceRecord = serde_json::from_value(json).expect("record deserializes");
+        assert_eq!(decoded, record);
+    }
+
+    #[test]
+    fn enums_accept_names_or_numbers_and_missing_fields_default() {
+        let record: ExperienceRecord =
+            serde_json::from_str(r#"{"record_type":"RECORD_TYPE_RT_OUTPUT"}"#).unwrap();
+        assert_eq!(record.record_type, RecordType::RtOutput as i32);
+
+        let record: ExperienceRecord = serde_json::from_str(r#"{"record_type":42}"#).unwrap();
+        assert_eq!(serde_json::to_value(&record).unwrap()["record_type"], 42);
+
+        assert!(serde_json::from_str::<ExperienceRecord>(r#"{"record_type":"NOPE"}"#).is_err());
+        assert_eq!(serde_json::from_str::<ReasonCodes>("{}").unwrap(), ReasonCodes::default());
+    }
+}
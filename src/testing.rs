@@ -0,0 +1,429 @@
+//! Reusable determinism checks over the golden fixtures in `testvectors/`.
+//!
+//! [`verify_determinism_report`] is the library form of the `determinism.rs`
+//! integration test: every registered fixture is decoded, re-encoded, and
+//! re-digested, and any instability is recorded in a [`DeterminismReport`]
+//! instead of panicking. Downstream crates and tooling can run it to confirm
+//! their build of the schema still reproduces the published bytes and digests.
+//!
+//! [`minimal_envelope`] builds a sealed, verifiable envelope of any message type
+//! for test suites that need one without hand-constructing the payload, and
+//! [`canonicalize::assert_empty_stable`] guards repeated fields that may be empty.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::SigningKey;
+use prost::{DecodeError, Message};
+
+use crate::envelope;
+use crate::fixtures::read_fixture;
+pub use crate::fixtures::FixtureFormat;
+use crate::ucf::v1::{
+    ApprovalArtifactPackage, ApprovalDecision, AssetDigest, AssetManifest, CanonicalIntent,
+    ChannelParamsSetPayload, CompletenessReport, ConnectivityGraphPayload, ConsistencyFeedback,
+    ControlFrame, DecisionForm, ExperienceRecord, MacroMilestone, MesoMilestone, MicroMilestone,
+    MicrocircuitConfigEvidence, MorphologySetPayload, MsgType, PolicyDecision, PolicyQuery,
+    PvgsReceipt, ReasonCodes, ReceiptStatus, ReplayPlan, ReplayRunEvidence, SepEvent, SessionSeal,
+    SignalFrame, SynapseParamsSetPayload, ToolOnboardingEvent, ToolRegistryContainer, UcfEnvelope,
+};
+use crate::{canonical_bytes, digest32_with, DigestAlgo, Domain};
+
+const SCHEMA_VERSION: &str = "1";
+
+/// A registered golden fixture and the parameters of its digest.
+#[derive(Clone, Copy, Debug)]
+pub struct FixtureSpec {
+    pub name: &'static str,
+    pub schema: &'static str,
+    pub domain: Domain,
+    pub format: FixtureFormat,
+    pub algo: DigestAlgo,
+    recanonicalize: fn(&[u8]) -> Result<Vec<u8>, DecodeError>,
+}
+
+impl FixtureSpec {
+    const fn new(
+        name: &'static str,
+        schema: &'static str,
+        domain: Domain,
+        format: FixtureFormat,
+        recanonicalize: fn(&[u8]) -> Result<Vec<u8>, DecodeError>,
+    ) -> Self {
+        Self { name, schema, domain, format, algo: DigestAlgo::Blake3, recanonicalize }
+    }
+
+    const fn with_algo(self, algo: DigestAlgo) -> Self {
+        Self { algo, ..self }
+    }
+
+    /// Decode `bytes` as this fixture's message type and re-encode canonically.
+    pub fn recanonicalize(&self, bytes: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        (self.recanonicalize)(bytes)
+    }
+}
+
+fn recanonicalize<M: Message + Default>(bytes: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    Ok(canonical_bytes(&M::decode(bytes)?))
+}
+
+macro_rules! fixture {
+    ($name:literal, $message:ident, $domain:expr) => {
+        fixture!($name, $message, $domain, FixtureFormat::Hex)
+    };
+    ($name:literal, $message:ident, $domain:expr, $format:expr) => {
+        FixtureSpec::new(
+            $name,
+            concat!("ucf.v1.", stringify!($message)),
+            $domain,
+            $format,
+            recanonicalize::<$message>,
+        )
+    };
+}
+
+/// Every golden fixture shipped in `testvectors/`, sorted by name.
+pub const FIXTURES: &[FixtureSpec] = &[
+    fixture!("approval_artifact_package", ApprovalArtifactPackage, Domain::Core),
+    fixture!("approval_decision", ApprovalDecision, Domain::Core),
+    fixture!("asset_digest_morphology_v1", AssetDigest, Domain::AssetMorphology),
+    fixture!("asset_manifest_v1", AssetManifest, Domain::AssetManifest),
+    fixture!("biophys_channel_params_set_v1", ChannelParamsSetPayload, Domain::AssetChannelParams),
+    fixture!("biophys_connectivity_graph_v1", ConnectivityGraphPayload, Domain::AssetConnectivity),
+    fixture!("biophys_morphology_set_v1", MorphologySetPayload, Domain::AssetMorphology),
+    fixture!("biophys_synapse_params_set_v1", SynapseParamsSetPayload, Domain::AssetSynapseParams),
+    fixture!("canonical_intent_query", CanonicalIntent, Domain::Core),
+    fixture!("completeness_report", CompletenessReport, Domain::Core),
+    fixture!("consistency_feedback_low_flags", ConsistencyFeedback, Domain::Core),
+    fixture!("control_frame_m1_overlays_on", ControlFrame, Domain::Core),
+    fixture!("experience_rt_action_exec", ExperienceRecord, Domain::Core),
+    fixture!("experience_rt_output", ExperienceRecord, Domain::Core),
+    fixture!("experience_rt_perception", ExperienceRecord, Domain::Core),
+    fixture!("macro_milestone_finalized", MacroMilestone, Domain::Core),
+    fixture!("mc_cfg_hpa", MicrocircuitConfigEvidence, Domain::McConfig, FixtureFormat::Bin),
+    fixture!("meso_milestone_stable", MesoMilestone, Domain::Core),
+    fixture!("micro_milestone_sealed", MicroMilestone, Domain::Core),
+    fixture!("microcircuit_config_hpa_v1", MicrocircuitConfigEvidence, Domain::McConfig),
+    fixture!("microcircuit_config_lc_v1", MicrocircuitConfigEvidence, Domain::McConfig),
+    fixture!("microcircuit_config_sn_v1", MicrocircuitConfigEvidence, Domain::McConfig),
+    fixture!("policy_decision", PolicyDecision, Domain::Core),
+    // The receipt schema id keeps the proto spelling rather than prost's `PvgsReceipt`.
+    FixtureSpec::new(
+        "pvgs_receipt",
+        "ucf.v1.PVGSReceipt",
+        Domain::Core,
+        FixtureFormat::Hex,
+        recanonicalize::<PvgsReceipt>,
+    ),
+    fixture!("reason_codes_basic", ReasonCodes, Domain::Core),
+    fixture!("reason_codes_sha256", ReasonCodes, Domain::Core).with_algo(DigestAlgo::Sha256),
+    fixture!("replay_plan_asset_manifest_ref", ReplayPlan, Domain::Core),
+    fixture!("replay_plan_high_fidelity", ReplayPlan, Domain::Core),
+    fixture!("replay_run_evidence", ReplayRunEvidence, Domain::Core),
+    fixture!("sep_event_chain_1", SepEvent, Domain::Core),
+    fixture!("sep_event_chain_2", SepEvent, Domain::Core),
+    fixture!("sep_event_chain_3", SepEvent, Domain::Core),
+    fixture!("session_seal", SessionSeal, Domain::Core),
+    fixture!("signal_frame_short_window", SignalFrame, Domain::Core),
+    fixture!("tool_onboarding_event", ToolOnboardingEvent, Domain::Core),
+    fixture!("tool_registry_container", ToolRegistryContainer, Domain::Core),
+    fixture!("ucf_envelope_policy_decision", UcfEnvelope, Domain::Core),
+];
+
+/// Outcome of checking one fixture.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FixtureStatus {
+    /// Bytes and digest reproduce exactly.
+    Stable,
+    /// A fixture file could not be read or its hex could not be decoded.
+    Unreadable(String),
+    /// The bytes are not a valid encoding of the fixture's message type.
+    DecodeFailed(DecodeError),
+    /// Re-encoding the decoded message produced different bytes.
+    NonCanonical { fixture_len: usize, reencoded_len: usize },
+    /// The recomputed digest differs from the stored `.digest` file.
+    DigestMismatch { expected: [u8; 32], found: [u8; 32] },
+}
+
+/// Result for a single registered fixture.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixtureResult {
+    pub name: &'static str,
+    pub status: FixtureStatus,
+}
+
+/// Per-fixture results of [`verify_determinism_report`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DeterminismReport {
+    pub results: Vec<FixtureResult>,
+}
+
+impl DeterminismReport {
+    /// Whether every fixture reproduced its bytes and digest.
+    pub fn is_stable(&self) -> bool {
+        self.results.iter().all(|result| result.status == FixtureStatus::Stable)
+    }
+
+    /// Results for fixtures that did not reproduce.
+    pub fn failures(&self) -> impl Iterator<Item = &FixtureResult> {
+        self.results.iter().filter(|result| result.status != FixtureStatus::Stable)
+    }
+}
+
+impl fmt::Display for DeterminismReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for FixtureResult { name, status } in &self.results {
+            match status {
+                FixtureStatus::Stable => writeln!(f, "ok        {name}")?,
+                FixtureStatus::Unreadable(err) => writeln!(f, "unreadable {name}: {err}")?,
+                FixtureStatus::DecodeFailed(err) => writeln!(f, "decode    {name}: {err}")?,
+                FixtureStatus::NonCanonical { fixture_len, reencoded_len } => writeln!(
+                    f,
+                    "unstable  {name}: {fixture_len} fixture bytes re-encode to {reencoded_len}"
+                )?,
+                FixtureStatus::DigestMismatch { expected, found } => writeln!(
+                    f,
+                    "digest    {name}: expected {} found {}",
+                    hex::encode(expected),
+                    hex::encode(found)
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The `testvectors/` directory shipped with this crate.
+pub fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("testvectors")
+}
+
+/// Decode the hex fixture `name` in [`fixture_dir`] as an `M`.
+///
+/// Panics if the fixture is missing or does not decode.
+pub fn decode_fixture<M: Message + Default>(name: &str) -> M {
+    decode_fixture_with_digest(name).0
+}
+
+/// Like [`decode_fixture`], also returning the fixture's stored digest.
+pub fn decode_fixture_with_digest<M: Message + Default>(name: &str) -> (M, [u8; 32]) {
+    let (bytes, digest) =
+        read_fixture(&fixture_dir(), name, FixtureFormat::Hex).expect("fixture reads");
+    (M::decode(bytes.as_slice()).expect("fixture decodes"), digest)
+}
+
+/// Check every registered fixture in [`fixture_dir`].
+pub fn verify_determinism_report() -> DeterminismReport {
+    verify_determinism_report_in(&fixture_dir())
+}
+
+/// Check every registered fixture stored under `dir`.
+pub fn verify_determinism_report_in(dir: &Path) -> DeterminismReport {
+    let results = FIXTURES
+        .iter()
+        .map(|spec| FixtureResult { name: spec.name, status: check_fixture(dir, spec) })
+        .collect();
+    DeterminismReport { results }
+}
+
+/// Check the registered fixture `name` stored under `dir`, or `None` if no
+/// fixture of that name is registered.
+pub fn verify_fixture_in(dir: &Path, name: &str) -> Option<FixtureResult> {
+    let spec = FIXTURES.iter().find(|spec| spec.name == name)?;
+    Some(FixtureResult { name: spec.name, status: check_fixture(dir, spec) })
+}
+
+fn check_fixture(dir: &Path, spec: &FixtureSpec) -> FixtureStatus {
+    let (bytes, expected) = match read_fixture(dir, spec.name, spec.format) {
+        Ok(fixture) => fixture,
+        Err(err) => return FixtureStatus::Unreadable(err),
+    };
+
+    let reencoded = match spec.recanonicalize(&bytes) {
+        Ok(reencoded) => reencoded,
+        Err(err) => return FixtureStatus::DecodeFailed(err),
+    };
+    if reencoded != bytes {
+        return FixtureStatus::NonCanonical {
+            fixture_len: bytes.len(),
+            reencoded_len: reencoded.len(),
+        };
+    }
+
+    let found = digest32_with(spec.algo, spec.domain, spec.schema, SCHEMA_VERSION, &reencoded);
+    if found != expected {
+        return FixtureStatus::DigestMismatch { expected, found };
+    }
+
+    FixtureStatus::Stable
+}
+
+/// Build a sealed envelope carrying the smallest meaningful payload for `msg_type`.
+///
+/// The nonce is derived from `msg_type`, so the result is deterministic for a
+/// given key. The envelope passes [`envelope::verify`].
+///
+/// # Panics
+///
+/// Panics for `MsgType::Unspecified`, which has no payload schema.
+pub fn minimal_envelope(msg_type: MsgType, signing_key: &SigningKey) -> UcfEnvelope {
+    let payload = match msg_type {
+        MsgType::CanonicalIntent => canonical_bytes(&CanonicalIntent {
+            intent_id: "intent-minimal".to_string(),
+            ..Default::default()
+        }),
+        MsgType::PolicyQuery => canonical_bytes(&PolicyQuery {
+            principal: "principal-minimal".to_string(),
+            ..Default::default()
+        }),
+        MsgType::PolicyDecision => canonical_bytes(&PolicyDecision {
+            decision: DecisionForm::Allow as i32,
+            ..Default::default()
+        }),
+        MsgType::PvgsReceipt => canonical_bytes(&PvgsReceipt {
+            status: ReceiptStatus::Accepted as i32,
+            ..Default::default()
+        }),
+        MsgType::Unspecified => panic!("MSG_TYPE_UNSPECIFIED has no payload schema"),
+    };
+
+    let mut nonce_hasher = blake3::Hasher::new();
+    nonce_hasher.update(b"UCF:TESTING:NONCE");
+    nonce_hasher.update(&(msg_type as i32).to_le_bytes());
+
+    let mut sealed = UcfEnvelope {
+        epoch_id: "epoch-minimal".to_string(),
+        nonce: nonce_hasher.finalize().as_bytes()[..16].to_vec(),
+        msg_type: msg_type as i32,
+        payload,
+        ..Default::default()
+    };
+    envelope::seal(&mut sealed, signing_key).expect("specified msg types have a payload schema");
+    sealed
+}
+
+/// Canonicalization checks for repeated fields.
+pub mod canonicalize {
+    use prost::Message;
+
+    use crate::canonical_bytes;
+
+    /// Assert that an empty repeated field canonicalizes the same however it was emptied.
+    ///
+    /// `field` selects a repeated field of `message`. Proto3 omits empty repeated
+    /// fields from the encoding, so setting it to `vec![]`, resetting it to its
+    /// default, and clearing it after it held elements must all yield identical
+    /// canonical bytes. Use this to guard digests of messages whose set-like
+    /// fields may legitimately be empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the encodings differ.
+    pub fn assert_empty_stable<M, T>(message: &M, field: impl Fn(&mut M) -> &mut Vec<T>)
+    where
+        M: Message + Clone,
+        T: Clone,
+    {
+        let mut literal = message.clone();
+        *field(&mut literal) = vec![];
+
+        let mut defaulted = message.clone();
+        *field(&mut defaulted) = Vec::default();
+
+        let mut cleared = message.clone();
+        let values = field(&mut cleared);
+        if let Some(first) = values.first().cloned() {
+            values.push(first);
+        }
+        values.clear();
+
+        let expected = canonical_bytes(&literal);
+        assert_eq!(canonical_bytes(&defaulted), expected, "defaulted field encodes differently");
+        assert_eq!(canonical_bytes(&cleared), expected, "cleared field encodes differently");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::AllowedAlgorithms;
+    use crate::fixtures::read_fixture;
+    use crate::ucf::v1::Ref;
+    use std::fs;
+
+    #[test]
+    fn shipped_fixtures_are_stable() {
+        let names: Vec<&str> = FIXTURES.iter().map(|spec| spec.name).collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted, "fixture registry should be sorted by name");
+
+        let report = verify_determinism_report();
+        assert_eq!(report.results.len(), FIXTURES.len());
+        assert!(report.is_stable(), "{report}");
+    }
+
+    #[test]
+    fn report_records_instability_instead_of_panicking() {
+        let dir = std::env::temp_dir().join(format!("ucf-determinism-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        // Copy one fixture with a corrupted digest; everything else is missing.
+        let source = fixture_dir();
+        fs::copy(source.join("policy_decision.hex"), dir.join("policy_decision.hex")).unwrap();
+        fs::write(dir.join("policy_decision.digest"), format!("{}\n", "00".repeat(32))).unwrap();
+
+        let report = verify_determinism_report_in(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(!report.is_stable());
+        let policy = report.results.iter().find(|result| result.name == "policy_decision").unwrap();
+        assert!(matches!(
+            policy.status,
+            FixtureStatus::DigestMismatch { expected, .. } if expected == [0u8; 32]
+        ));
+        assert_eq!(report.failures().count(), FIXTURES.len());
+    }
+
+    #[test]
+    fn minimal_envelope_verifies_for_every_msg_type() {
+        let key = SigningKey::from_bytes(&[5u8; 32]);
+        let allowed = AllowedAlgorithms::default();
+        for msg_type in [
+            MsgType::CanonicalIntent,
+            MsgType::PolicyQuery,
+            MsgType::PolicyDecision,
+            MsgType::PvgsReceipt,
+        ] {
+            let sealed = minimal_envelope(msg_type, &key);
+            assert_eq!(sealed.msg_type, msg_type as i32);
+            assert!(!sealed.payload.is_empty(), "{msg_type:?} payload should not be empty");
+            assert_eq!(envelope::verify_payload_digest(&sealed), Ok(()), "{msg_type:?}");
+            assert_eq!(envelope::verify(&sealed, &allowed), Ok(()), "{msg_type:?}");
+            assert_eq!(sealed, minimal_envelope(msg_type, &key), "should be deterministic");
+        }
+    }
+
+    #[test]
+    fn empty_related_refs_match_the_perception_fixture() {
+        let dir = fixture_dir();
+        let (bytes, _) =
+            read_fixture(&dir, "experience_rt_perception", FixtureFormat::Hex).unwrap();
+        let perception = ExperienceRecord::decode(bytes.as_slice()).unwrap();
+        assert!(perception.related_refs.is_empty());
+
+        let explicit = ExperienceRecord { related_refs: vec![], ..perception.clone() };
+        assert_eq!(canonical_bytes(&explicit), bytes);
+        let mut cleared = perception.clone();
+        cleared
+            .related_refs
+            .push(Ref { uri: "dlp://scan/final".to_string(), label: String::new() });
+        cleared.related_refs.clear();
+        assert_eq!(canonical_bytes(&cleared), bytes);
+        canonicalize::assert_empty_stable(&perception, |record| &mut record.related_refs);
+
+        let (bytes, _) = read_fixture(&dir, "experience_rt_output", FixtureFormat::Hex).unwrap();
+        let output = ExperienceRecord::decode(bytes.as_slice()).unwrap();
+        assert!(!output.related_refs.is_empty());
+        canonicalize::assert_empty_stable(&output, |record| &mut record.related_refs);
+    }
+}
@@ -0,0 +1,93 @@
+//! Loaders for the golden fixtures shipped in `testvectors/`.
+//!
+//! These read the on-disk format described in [`crate::codec`] and
+//! `testvectors/README.md`, so downstream crates can assert against the shared
+//! vectors without re-implementing the hex and digest decoding.
+//!
+//! [`verify_all`] checks a vendored copy of the vectors against the fixture
+//! table [`FIXTURES`], so an integrator can confirm the vectors still match
+//! after upgrading this crate.
+
+use std::path::Path;
+
+use crate::fixtures::{read_fixture, FixtureError, FixtureFormat};
+use crate::testing::{fixture_dir, verify_determinism_report_in, DeterminismReport};
+pub use crate::testing::{FixtureResult, FixtureSpec, FixtureStatus, FIXTURES};
+
+/// Per-fixture results of [`verify_all`].
+pub type VerifyReport = DeterminismReport;
+
+/// Load `<name>.hex` and `<name>.digest`, returning the message bytes and digest.
+pub fn load_hex_fixture(name: &str) -> Result<(Vec<u8>, [u8; 32]), FixtureError> {
+    load(name, FixtureFormat::Hex)
+}
+
+/// Load `<name>.bin` and `<name>.digest`, returning the message bytes and digest.
+pub fn load_binary_fixture(name: &str) -> Result<(Vec<u8>, [u8; 32]), FixtureError> {
+    load(name, FixtureFormat::Bin)
+}
+
+/// Load fixture `name` in whichever format it is stored: `.hex`, `.b64`, or `.bin`.
+pub fn load_fixture(name: &str) -> Result<(Vec<u8>, [u8; 32]), FixtureError> {
+    load(name, FixtureFormat::detect(&fixture_dir(), name).unwrap_or(FixtureFormat::Hex))
+}
+
+/// Decode, re-encode, and re-digest every fixture in [`FIXTURES`] stored under `dir`.
+///
+/// Fails only when `dir` is not a readable directory; a missing or unstable
+/// fixture is recorded in the report rather than returned as an error.
+pub fn verify_all(dir: &Path) -> Result<VerifyReport, FixtureError> {
+    if let Err(err) = dir.read_dir() {
+        return Err(FixtureError::Unreadable {
+            name: dir.display().to_string(),
+            reason: err.to_string(),
+        });
+    }
+    Ok(verify_determinism_report_in(dir))
+}
+
+fn load(name: &str, format: FixtureFormat) -> Result<(Vec<u8>, [u8; 32]), FixtureError> {
+    read_fixture(&fixture_dir(), name, format)
+        .map_err(|reason| FixtureError::Unreadable { name: name.to_string(), reason })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ucf::v1::ReasonCodes;
+    use crate::{canonical_bytes, digest32};
+
+    #[test]
+    fn loads_shipped_fixtures() {
+        let (bytes, digest) = load_hex_fixture("reason_codes_basic").expect("hex fixture loads");
+        let codes =
+            ReasonCodes { codes: vec!["deterministic".to_string(), "coverage".to_string()] };
+        assert_eq!(bytes, canonical_bytes(&codes));
+        assert_eq!(digest, digest32("ucf-core", "ucf.v1.ReasonCodes", "1", &bytes));
+
+        let (bytes, _) = load_binary_fixture("mc_cfg_hpa").expect("binary fixture loads");
+        assert!(!bytes.is_empty());
+        assert_eq!(load_fixture("mc_cfg_hpa").expect("detected fixture loads").0, bytes);
+
+        assert!(matches!(
+            load_fixture("missing_fixture"),
+            Err(FixtureError::Unreadable { name, .. }) if name == "missing_fixture"
+        ));
+        assert!(matches!(
+            load_hex_fixture("missing_fixture"),
+            Err(FixtureError::Unreadable { name, .. }) if name == "missing_fixture"
+        ));
+    }
+
+    #[test]
+    fn verifies_every_shipped_fixture() {
+        let report = verify_all(&fixture_dir()).expect("fixture dir is readable");
+        assert_eq!(report.results.len(), FIXTURES.len());
+        assert!(report.is_stable(), "{report}");
+
+        assert!(matches!(
+            verify_all(&fixture_dir().join("missing")),
+            Err(FixtureError::Unreadable { .. })
+        ));
+    }
+}
@@ -0,0 +1,498 @@
+//! Helpers for tool registry profiles and onboarding.
+//!
+//! Profiles carry coarse classes rather than counts or prices. The tables
+//! below fix what each class means so every consumer of a tool registry
+//! derives the same retry budget and cost. `*_UNSPECIFIED` and values prost
+//! cannot map resolve to the most conservative entry: no retries, and the
+//! highest cost.
+//!
+//! Onboarding moves a tool action through the [`OnboardingStage`] graph
+//! checked by [`validate_onboarding_transition`]:
+//!
+//! - `TO0_PROPOSED` -> `TO1_PROFILED` -> `TO2_VALIDATED` -> `TO3_APPROVED` ->
+//!   `TO4_ACTIVE`, one step at a time.
+//! - `TO4_ACTIVE` -> `TO5_PAUSED`, and back.
+//! - `TO4_ACTIVE` or `TO5_PAUSED` -> `TO6_SUSPENDED`.
+//! - `TO6_SUSPENDED` -> `TO2_VALIDATED`: a suspended action is re-validated
+//!   and re-approved before it becomes active again.
+//! - Any stage other than `TO6_REVOKED` -> `TO6_REVOKED`, which is final.
+//!
+//! A registry's `registry_digest` is
+//! `digest32("UCF:TOOL:REGISTRY", "ucf.v1.ToolRegistryContainer", "1", bytes)`
+//! over the canonical bytes of the container with `tool_actions` sorted by
+//! `(tool_id, action_id)` and `registry_digest` and `attestation_sig` cleared,
+//! so it commits to the action set whatever order the actions were added in.
+//! The signature attests to the registry and so cannot be an input to it.
+
+use std::fmt;
+
+use subtle::ConstantTimeEq;
+
+use crate::digest::{compute_self_digest, SelfDigested};
+use crate::enums::EnumError;
+use crate::ucf::v1::{
+    CostClass, CostModel, DataClass, Digest32, OnboardingStage, RetryClass, RetryPolicy,
+    ScopeShape, ToolOnboardingEvent, ToolRegistryContainer,
+};
+use crate::{Canonicalize, Domain};
+
+pub const TOOL_REGISTRY_DOMAIN: &str = Domain::ToolRegistry.as_str();
+const TOOL_REGISTRY_SCHEMA: &str = "ucf.v1.ToolRegistryContainer";
+
+/// Retries allowed for each [`RetryClass`], on top of the first attempt.
+pub const RETRY_CLASS_COUNTS: [(RetryClass, u32); 4] = [
+    (RetryClass::Unspecified, 0),
+    (RetryClass::RetryNone, 0),
+    (RetryClass::RetryLow, 1),
+    (RetryClass::RetryMed, 3),
+];
+
+/// Cost units for a [`CostModel::base_cost_class`].
+pub const COST_CLASS_UNITS: [(CostClass, u64); 4] = [
+    (CostClass::Unspecified, 16),
+    (CostClass::CostLow, 1),
+    (CostClass::CostMed, 4),
+    (CostClass::CostHigh, 16),
+];
+
+/// Factor for each multiplier class of a [`CostModel`].
+pub const COST_CLASS_MULTIPLIERS: [(CostClass, u64); 4] = [
+    (CostClass::Unspecified, 4),
+    (CostClass::CostLow, 1),
+    (CostClass::CostMed, 2),
+    (CostClass::CostHigh, 4),
+];
+
+/// How many times the scope multiplier applies for each [`ScopeShape`].
+pub const SCOPE_SHAPE_STEPS: [(ScopeShape, u32); 5] = [
+    (ScopeShape::Unspecified, 3),
+    (ScopeShape::Single, 0),
+    (ScopeShape::BoundedList, 1),
+    (ScopeShape::QueryBounded, 2),
+    (ScopeShape::QueryUnbounded, 3),
+];
+
+/// How many times the data multiplier applies for each [`DataClass`].
+pub const DATA_CLASS_STEPS: [(DataClass, u32); 4] = [
+    (DataClass::Unspecified, 2),
+    (DataClass::Public, 0),
+    (DataClass::Confidential, 1),
+    (DataClass::Restricted, 2),
+];
+
+fn lookup<K: PartialEq, V: Copy>(table: &[(K, V)], key: K) -> V {
+    table
+        .iter()
+        .find(|(class, _)| *class == key)
+        .map(|(_, value)| *value)
+        .expect("mapping tables cover every variant")
+}
+
+/// Retries `policy` allows after the first attempt, per [`RETRY_CLASS_COUNTS`].
+///
+/// Zero whenever `retry_allowed` is false, whatever the class says.
+pub fn max_retries(policy: &RetryPolicy) -> u32 {
+    if !policy.retry_allowed {
+        return 0;
+    }
+    lookup(&RETRY_CLASS_COUNTS, policy.retry_class())
+}
+
+/// Cost units for one call of an action costed by `model` at `scope` over `data`.
+///
+/// The result is `base * scope_mul^scope_steps * data_mul^data_steps *
+/// irreversibility_mul`, using [`COST_CLASS_UNITS`] for the base,
+/// [`COST_CLASS_MULTIPLIERS`] for each multiplier, and [`SCOPE_SHAPE_STEPS`]
+/// and [`DATA_CLASS_STEPS`] for the exponents. A single public item costs the
+/// base times the irreversibility factor.
+pub fn estimate_cost(model: &CostModel, scope: ScopeShape, data: DataClass) -> u64 {
+    let multiplier = |class| lookup(&COST_CLASS_MULTIPLIERS, class);
+    let scope_factor = multiplier(model.scope_multiplier_class())
+        .saturating_pow(lookup(&SCOPE_SHAPE_STEPS, scope));
+    let data_factor =
+        multiplier(model.data_multiplier_class()).saturating_pow(lookup(&DATA_CLASS_STEPS, data));
+    lookup(&COST_CLASS_UNITS, model.base_cost_class())
+        .saturating_mul(scope_factor)
+        .saturating_mul(data_factor)
+        .saturating_mul(multiplier(model.irreversibility_multiplier_class()))
+}
+
+/// A move between two onboarding stages that the stage graph does not allow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransitionError {
+    pub from: OnboardingStage,
+    pub to: OnboardingStage,
+}
+
+impl fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "onboarding cannot move from {} to {}",
+            self.from.as_str_name(),
+            self.to.as_str_name()
+        )
+    }
+}
+
+impl std::error::Error for TransitionError {}
+
+/// Errors returned by [`validate_onboarding_sequence`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OnboardingError {
+    /// `stage` holds a value that is not an [`OnboardingStage`].
+    UndefinedStage { event_id: String, error: EnumError },
+    /// The first event is not `TO0_PROPOSED`.
+    NotProposed { event_id: String, stage: OnboardingStage },
+    /// `tool_id` or `action_id` differs from the first event's.
+    ActionMismatch { event_id: String, expected: (String, String), found: (String, String) },
+    /// The event's stage does not follow from the previous event's.
+    IllegalTransition { event_id: String, error: TransitionError },
+}
+
+impl fmt::Display for OnboardingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OnboardingError::UndefinedStage { event_id, error } => {
+                write!(f, "onboarding event {event_id}: {error}")
+            }
+            OnboardingError::NotProposed { event_id, stage } => write!(
+                f,
+                "onboarding event {event_id} starts at {} instead of {}",
+                stage.as_str_name(),
+                OnboardingStage::To0Proposed.as_str_name()
+            ),
+            OnboardingError::ActionMismatch { event_id, expected, found } => write!(
+                f,
+                "onboarding event {event_id} is for {}/{}, not {}/{}",
+                found.0, found.1, expected.0, expected.1
+            ),
+            OnboardingError::IllegalTransition { event_id, error } => {
+                write!(f, "onboarding event {event_id}: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OnboardingError {}
+
+/// Confirm the stage graph in the [module docs](self) allows `from` -> `to`.
+///
+/// Staying at the same stage is not a transition and is rejected, as is any
+/// move from or to `ONBOARDING_STAGE_UNSPECIFIED`.
+pub fn validate_onboarding_transition(
+    from: OnboardingStage,
+    to: OnboardingStage,
+) -> Result<(), TransitionError> {
+    use OnboardingStage::*;
+    let allowed = match (from, to) {
+        (Unspecified, _) | (_, Unspecified) | (To6Revoked, _) => false,
+        (_, To6Revoked) => true,
+        (To0Proposed, To1Profiled)
+        | (To1Profiled, To2Validated)
+        | (To2Validated, To3Approved)
+        | (To3Approved, To4Active)
+        | (To4Active, To5Paused)
+        | (To5Paused, To4Active)
+        | (To4Active | To5Paused, To6Suspended)
+        | (To6Suspended, To2Validated) => true,
+        _ => false,
+    };
+    if allowed {
+        Ok(())
+    } else {
+        Err(TransitionError { from, to })
+    }
+}
+
+/// Confirm `events` record one tool action's onboarding in a legal order.
+///
+/// The first event must be `TO0_PROPOSED`, every event must name the first
+/// event's `tool_id` and `action_id`, each stage must follow from the previous
+/// one per [`validate_onboarding_transition`]. Events are taken in slice
+/// order. An empty slice passes.
+pub fn validate_onboarding_sequence(events: &[ToolOnboardingEvent]) -> Result<(), OnboardingError> {
+    let Some(first) = events.first() else {
+        return Ok(());
+    };
+    let stage_of = |event: &ToolOnboardingEvent| {
+        OnboardingStage::try_from(event.stage).map_err(|_| OnboardingError::UndefinedStage {
+            event_id: event.event_id.clone(),
+            error: EnumError { field: "stage", value: event.stage },
+        })
+    };
+    let first_stage = stage_of(first)?;
+    if first_stage != OnboardingStage::To0Proposed {
+        return Err(OnboardingError::NotProposed {
+            event_id: first.event_id.clone(),
+            stage: first_stage,
+        });
+    }
+    let mut prev = first_stage;
+    for event in &events[1..] {
+        if (&event.tool_id, &event.action_id) != (&first.tool_id, &first.action_id) {
+            return Err(OnboardingError::ActionMismatch {
+                event_id: event.event_id.clone(),
+                expected: (first.tool_id.clone(), first.action_id.clone()),
+                found: (event.tool_id.clone(), event.action_id.clone()),
+            });
+        }
+        let stage = stage_of(event)?;
+        validate_onboarding_transition(prev, stage).map_err(|error| {
+            OnboardingError::IllegalTransition { event_id: event.event_id.clone(), error }
+        })?;
+        prev = stage;
+    }
+    Ok(())
+}
+
+/// Errors returned by [`verify_registry_digest`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegistryDigestError {
+    /// The container has no `registry_digest`.
+    DigestMissing,
+    /// `registry_digest` differs from [`compute_registry_digest`] of the container.
+    DigestMismatch { expected: [u8; 32], found: Vec<u8> },
+}
+
+impl fmt::Display for RegistryDigestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryDigestError::DigestMissing => f.write_str("registry has no registry_digest"),
+            RegistryDigestError::DigestMismatch { expected, found } => write!(
+                f,
+                "registry_digest {} does not match its content digest {}",
+                hex::encode(found),
+                hex::encode(expected)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RegistryDigestError {}
+
+impl SelfDigested for ToolRegistryContainer {
+    const SCHEMA_ID: &'static str = TOOL_REGISTRY_SCHEMA;
+
+    fn domain() -> &'static str {
+        TOOL_REGISTRY_DOMAIN
+    }
+
+    fn take_digest(&mut self) -> Option<Digest32> {
+        self.registry_digest.take()
+    }
+
+    fn clear_attestation(&mut self) {
+        self.attestation_sig = None;
+    }
+
+    fn sort_sets(&mut self) {
+        Canonicalize::canonicalize(self);
+    }
+}
+
+/// Content digest of `container`, independent of the order of its `tool_actions`.
+pub fn compute_registry_digest(container: &ToolRegistryContainer) -> [u8; 32] {
+    compute_self_digest(container)
+}
+
+/// Confirm `registry_digest` is [`compute_registry_digest`] of `container`.
+pub fn verify_registry_digest(
+    container: &ToolRegistryContainer,
+) -> Result<(), RegistryDigestError> {
+    let found = container.registry_digest.as_ref().ok_or(RegistryDigestError::DigestMissing)?;
+    let expected = compute_registry_digest(container);
+    if bool::from(found.value.as_slice().ct_eq(&expected)) {
+        Ok(())
+    } else {
+        Err(RegistryDigestError::DigestMismatch { expected, found: found.value.clone() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ucf::v1::{Signature, ToolActionProfile};
+
+    fn cost_model(base: CostClass, scope: CostClass, data: CostClass, irr: CostClass) -> CostModel {
+        CostModel {
+            base_cost_class: base as i32,
+            scope_multiplier_class: scope as i32,
+            data_multiplier_class: data as i32,
+            irreversibility_multiplier_class: irr as i32,
+        }
+    }
+
+    #[test]
+    fn retries_follow_class_when_allowed() {
+        let policy = |retry_allowed, class: RetryClass| RetryPolicy {
+            retry_allowed,
+            retry_class: class as i32,
+        };
+        assert_eq!(max_retries(&policy(true, RetryClass::RetryNone)), 0);
+        assert_eq!(max_retries(&policy(true, RetryClass::RetryLow)), 1);
+        assert_eq!(max_retries(&policy(true, RetryClass::RetryMed)), 3);
+        assert_eq!(max_retries(&policy(false, RetryClass::RetryMed)), 0);
+        assert_eq!(max_retries(&policy(true, RetryClass::Unspecified)), 0);
+        assert_eq!(max_retries(&RetryPolicy { retry_allowed: true, retry_class: 99 }), 0);
+    }
+
+    #[test]
+    fn cost_scales_with_scope_and_data() {
+        use CostClass::*;
+        let model = cost_model(CostMed, CostMed, CostHigh, CostLow);
+        assert_eq!(estimate_cost(&model, ScopeShape::Single, DataClass::Public), 4);
+        assert_eq!(estimate_cost(&model, ScopeShape::BoundedList, DataClass::Public), 8);
+        assert_eq!(estimate_cost(&model, ScopeShape::QueryBounded, DataClass::Confidential), 64);
+        assert_eq!(estimate_cost(&model, ScopeShape::QueryUnbounded, DataClass::Restricted), 512);
+
+        let irreversible = cost_model(CostLow, CostLow, CostLow, CostHigh);
+        assert_eq!(estimate_cost(&irreversible, ScopeShape::QueryUnbounded, DataClass::Public), 4);
+    }
+
+    #[test]
+    fn unspecified_classes_cost_the_most() {
+        use CostClass::*;
+        let unspecified = CostModel::default();
+        let highest = cost_model(CostHigh, CostHigh, CostHigh, CostHigh);
+        for (scope, data) in [
+            (ScopeShape::Single, DataClass::Public),
+            (ScopeShape::Unspecified, DataClass::Unspecified),
+        ] {
+            assert_eq!(
+                estimate_cost(&unspecified, scope, data),
+                estimate_cost(&highest, scope, data)
+            );
+        }
+        assert_eq!(
+            estimate_cost(&highest, ScopeShape::Unspecified, DataClass::Unspecified),
+            estimate_cost(&highest, ScopeShape::QueryUnbounded, DataClass::Restricted),
+        );
+    }
+
+    fn onboarding(event_id: &str, stage: OnboardingStage) -> ToolOnboardingEvent {
+        ToolOnboardingEvent {
+            event_id: event_id.to_string(),
+            tool_id: "sensor-service".to_string(),
+            action_id: "read-latest".to_string(),
+            stage: stage as i32,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn transitions_follow_the_stage_graph() {
+        use OnboardingStage::*;
+        for (from, to) in [
+            (To0Proposed, To1Profiled),
+            (To3Approved, To4Active),
+            (To4Active, To5Paused),
+            (To5Paused, To4Active),
+            (To5Paused, To6Suspended),
+            (To6Suspended, To2Validated),
+            (To1Profiled, To6Revoked),
+            (To6Suspended, To6Revoked),
+        ] {
+            assert_eq!(validate_onboarding_transition(from, to), Ok(()), "{from:?} -> {to:?}");
+        }
+        for (from, to) in [
+            (To0Proposed, To2Validated),
+            (To6Suspended, To3Approved),
+            (To6Suspended, To4Active),
+            (To2Validated, To6Suspended),
+            (To6Revoked, To0Proposed),
+            (To6Revoked, To6Revoked),
+            (To4Active, To4Active),
+            (Unspecified, To0Proposed),
+            (To4Active, Unspecified),
+        ] {
+            assert_eq!(
+                validate_onboarding_transition(from, to),
+                Err(TransitionError { from, to }),
+                "{from:?} -> {to:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn sequence_checks_start_action_and_stages() {
+        use OnboardingStage::*;
+        let mut events = vec![
+            onboarding("e0", To0Proposed),
+            onboarding("e1", To1Profiled),
+            onboarding("e2", To2Validated),
+            onboarding("e3", To3Approved),
+            onboarding("e4", To4Active),
+            onboarding("e5", To6Suspended),
+            onboarding("e6", To2Validated),
+        ];
+        assert_eq!(validate_onboarding_sequence(&events), Ok(()));
+        assert_eq!(validate_onboarding_sequence(&[]), Ok(()));
+
+        assert_eq!(
+            validate_onboarding_sequence(&events[1..]),
+            Err(OnboardingError::NotProposed { event_id: "e1".into(), stage: To1Profiled })
+        );
+
+        events[6].stage = To4Active as i32;
+        assert_eq!(
+            validate_onboarding_sequence(&events),
+            Err(OnboardingError::IllegalTransition {
+                event_id: "e6".into(),
+                error: TransitionError { from: To6Suspended, to: To4Active },
+            })
+        );
+
+        events[6].stage = 42;
+        assert!(matches!(
+            validate_onboarding_sequence(&events),
+            Err(OnboardingError::UndefinedStage { event_id, .. }) if event_id == "e6"
+        ));
+
+        events[6].stage = To2Validated as i32;
+        events[2].action_id = "write-latest".to_string();
+        assert!(matches!(
+            validate_onboarding_sequence(&events),
+            Err(OnboardingError::ActionMismatch { event_id, .. }) if event_id == "e2"
+        ));
+    }
+
+    fn tool_action(tool_id: &str, action_id: &str) -> ToolActionProfile {
+        ToolActionProfile {
+            tool_id: tool_id.to_string(),
+            action_id: action_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn registry_digest_ignores_action_order() {
+        let mut registry = ToolRegistryContainer {
+            registry_id: "registry-alpha".to_string(),
+            tool_actions: vec![
+                tool_action("sensor-service", "read-latest"),
+                tool_action("archive", "write"),
+                tool_action("sensor-service", "export"),
+            ],
+            ..Default::default()
+        };
+        let digest = compute_registry_digest(&registry);
+        registry.tool_actions.reverse();
+        assert_eq!(compute_registry_digest(&registry), digest);
+
+        assert_eq!(verify_registry_digest(&registry), Err(RegistryDigestError::DigestMissing));
+        registry.registry_digest = Some(Digest32 { value: digest.to_vec() });
+        registry.attestation_sig = Some(Signature::default());
+        assert_eq!(compute_registry_digest(&registry), digest);
+        assert_eq!(verify_registry_digest(&registry), Ok(()));
+
+        registry.tool_actions.push(tool_action("archive", "read"));
+        assert_eq!(
+            verify_registry_digest(&registry),
+            Err(RegistryDigestError::DigestMismatch {
+                expected: compute_registry_digest(&registry),
+                found: digest.to_vec(),
+            })
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Verification policy shared by the bundle verifiers.
+//!
+//! A [`TrustConfig`] names the attestation keys a verifier trusts, the
+//! signature algorithms it accepts, the charter versions it accepts, the clock
+//! skew it tolerates, and the operations that require two-person approval.
+//! Verifiers take it by reference instead of a growing list of loose
+//! parameters; [`crate::resolve::verify_bundle`] enforces all of it. With the
+//! `serde` feature enabled the config can be loaded from a configuration file.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use ed25519_dalek::VerifyingKey;
+use subtle::{Choice, ConstantTimeEq};
+
+use crate::crypto::AllowedAlgorithms;
+use crate::ucf::v1::RequestedOperation;
+
+/// Trust anchors and policy limits for verification.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TrustConfig {
+    trusted_keys: BTreeMap<String, VerifyingKey>,
+    allowed_algorithms: AllowedAlgorithms,
+    accepted_charters: BTreeSet<String>,
+    max_skew_ms: u64,
+    /// `RequestedOperation` names, e.g. `REQUESTED_OPERATION_OP_EXPORT`.
+    two_person_ops: BTreeSet<String>,
+}
+
+impl TrustConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust `key` as the attestation key identified by `key_id`.
+    pub fn trust_key(mut self, key_id: impl Into<String>, key: VerifyingKey) -> Self {
+        self.trusted_keys.insert(key_id.into(), key);
+        self
+    }
+
+    /// Accept signatures using `algorithm` in addition to the defaults.
+    pub fn allow_algorithm(mut self, algorithm: &str) -> Self {
+        self.allowed_algorithms = self.allowed_algorithms.allow(algorithm);
+        self
+    }
+
+    /// Accept records bound to `charter_digest`. Records naming any other
+    /// charter are rejected.
+    pub fn accept_charter(mut self, charter_digest: &str) -> Self {
+        self.accepted_charters.insert(charter_digest.to_string());
+        self
+    }
+
+    /// Tolerate timestamps up to `max_skew_ms` ahead of the verifier's clock.
+    /// The default is zero.
+    pub fn max_skew_ms(mut self, max_skew_ms: u64) -> Self {
+        self.max_skew_ms = max_skew_ms;
+        self
+    }
+
+    /// Require approval packages for `op` to demand two distinct signers.
+    pub fn require_two_person(mut self, op: RequestedOperation) -> Self {
+        self.two_person_ops.insert(op.as_str_name().to_string());
+        self
+    }
+
+    /// The trusted key registered under `key_id`, if any.
+    pub fn trusted_key(&self, key_id: &str) -> Option<&VerifyingKey> {
+        self.trusted_keys.get(key_id)
+    }
+
+    /// All trusted keys with their key ids, ordered by key id.
+    pub fn trusted_keys(&self) -> impl Iterator<Item = (&str, &VerifyingKey)> {
+        self.trusted_keys.iter().map(|(key_id, key)| (key_id.as_str(), key))
+    }
+
+    /// Whether `key_bytes` is the encoding of any trusted key.
+    ///
+    /// Every trusted key is compared in constant time, so the check does not
+    /// reveal which key (if any) matched.
+    pub fn is_trusted_key_bytes(&self, key_bytes: &[u8]) -> bool {
+        let matched = self
+            .trusted_keys
+            .values()
+            .fold(Choice::from(0), |matched, key| matched | key.as_bytes().ct_eq(key_bytes));
+        bool::from(matched)
+    }
+
+    pub fn allowed_algorithms(&self) -> &AllowedAlgorithms {
+        &self.allowed_algorithms
+    }
+
+    pub fn accepts_charter(&self, charter_digest: &str) -> bool {
+        self.accepted_charters.contains(charter_digest)
+    }
+
+    /// Whether `timestamp_ms` is no more than the configured skew ahead of
+    /// `now_ms`. Earlier timestamps are always within skew.
+    pub fn within_skew(&self, timestamp_ms: u64, now_ms: u64) -> bool {
+        timestamp_ms <= now_ms.saturating_add(self.max_skew_ms)
+    }
+
+    pub fn requires_two_person(&self, op: RequestedOperation) -> bool {
+        self.two_person_ops.contains(op.as_str_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn builder_collects_policy() {
+        let key = SigningKey::from_bytes(&[7u8; 32]).verifying_key();
+        let config = TrustConfig::new()
+            .trust_key("pvgs-1", key)
+            .accept_charter("charter-v1")
+            .max_skew_ms(500)
+            .require_two_person(RequestedOperation::OpExport);
+
+        assert_eq!(config.trusted_key("pvgs-1"), Some(&key));
+        assert!(config.trusted_key("pvgs-2").is_none());
+        assert!(config.is_trusted_key_bytes(key.as_bytes()));
+        assert!(!config.is_trusted_key_bytes(&[0u8; 32]));
+        assert!(config.allowed_algorithms().contains("ed25519"));
+        assert!(!config.allowed_algorithms().contains("rsa"));
+        assert!(config.clone().allow_algorithm("rsa").allowed_algorithms().contains("rsa"));
+        assert!(config.accepts_charter("charter-v1"));
+        assert!(!config.accepts_charter("charter-v0"));
+        assert!(config.within_skew(1_500, 1_000));
+        assert!(config.within_skew(0, 1_000));
+        assert!(!config.within_skew(1_501, 1_000));
+        assert!(!TrustConfig::new().within_skew(1_001, 1_000));
+        assert!(config.requires_two_person(RequestedOperation::OpExport));
+        assert!(!config.requires_two_person(RequestedOperation::OpWrite));
+    }
+}
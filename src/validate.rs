@@ -0,0 +1,211 @@
+//! Decoding that also applies a message's structural checks.
+//!
+//! `M::decode` accepts any well-formed protobuf, so the result may lack
+//! required sub-messages, hold undefined enum values, or carry short digests.
+//! [`ValidatedMessage`] ties a message type to the crate's validators for it,
+//! and [`decode_validated`] decodes and validates in one call.
+//!
+//! Checks applied, per message:
+//!
+//! - `ExperienceRecord`: [`validate_enums`] and [`validate_experience_record`].
+//! - `ControlFrame`, `PolicyDecision`: [`validate_enums`].
+//! - `SepEvent`: [`validate_object_ref`].
+//! - `MorphologySetPayload`: [`validate_morphology`].
+//! - `AssetManifest`: all four assets present with their slot's kind and a
+//!   32-byte digest (see [`compute_manifest_digest`]); `manifest_digest` itself
+//!   is not checked.
+//! - `ReplayRunEvidence`: [`validate_micro_config_versions`] and
+//!   [`validate_timing`].
+
+use std::fmt;
+
+use prost::{DecodeError, Message};
+
+use crate::assets::{compute_manifest_digest, AssetError};
+use crate::biophys::{validate_morphology, MorphError};
+use crate::enums::{validate_enums, EnumError};
+use crate::experience::{validate_experience_record, ValidationError};
+use crate::replay::{
+    validate_micro_config_versions, validate_timing, ConfigVersionError, TimingError,
+};
+use crate::sep::{validate_object_ref, ObjectRefError};
+use crate::ucf::v1::{
+    AssetManifest, ControlFrame, ExperienceRecord, MorphologySetPayload, PolicyDecision,
+    ReplayRunEvidence, SepEvent,
+};
+
+/// Errors returned by [`decode_validated`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeValidateError {
+    /// The bytes are not a valid encoding of the message.
+    Decode(DecodeError),
+    Enum(EnumError),
+    Experience(ValidationError),
+    ObjectRef(ObjectRefError),
+    Morphology(MorphError),
+    Asset(AssetError),
+    ConfigVersion(ConfigVersionError),
+    Timing(TimingError),
+}
+
+impl fmt::Display for DecodeValidateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeValidateError::Decode(err) => write!(f, "decode failed: {err}"),
+            DecodeValidateError::Enum(err) => write!(f, "{err}"),
+            DecodeValidateError::Experience(err) => write!(f, "{err}"),
+            DecodeValidateError::ObjectRef(err) => write!(f, "{err}"),
+            DecodeValidateError::Morphology(err) => write!(f, "{err}"),
+            DecodeValidateError::Asset(err) => write!(f, "{err}"),
+            DecodeValidateError::ConfigVersion(err) => write!(f, "{err}"),
+            DecodeValidateError::Timing(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeValidateError {}
+
+macro_rules! from_errors {
+    ($($variant:ident($err:ty),)*) => {
+        $(
+            impl From<$err> for DecodeValidateError {
+                fn from(err: $err) -> Self {
+                    DecodeValidateError::$variant(err)
+                }
+            }
+        )*
+    };
+}
+
+from_errors! {
+    Decode(DecodeError),
+    Enum(EnumError),
+    Experience(ValidationError),
+    ObjectRef(ObjectRefError),
+    Morphology(MorphError),
+    Asset(AssetError),
+    ConfigVersion(ConfigVersionError),
+    Timing(TimingError),
+}
+
+/// A message with structural rules beyond what decoding enforces.
+pub trait ValidatedMessage: Message + Default {
+    /// Confirm the message satisfies the protocol's structural rules.
+    fn validate(&self) -> Result<(), DecodeValidateError>;
+}
+
+/// Decode `bytes` as `M` and apply [`ValidatedMessage::validate`].
+pub fn decode_validated<M: ValidatedMessage>(bytes: &[u8]) -> Result<M, DecodeValidateError> {
+    let message = M::decode(bytes)?;
+    message.validate()?;
+    Ok(message)
+}
+
+impl ValidatedMessage for ExperienceRecord {
+    fn validate(&self) -> Result<(), DecodeValidateError> {
+        validate_enums(self)?;
+        Ok(validate_experience_record(self)?)
+    }
+}
+
+impl ValidatedMessage for ControlFrame {
+    fn validate(&self) -> Result<(), DecodeValidateError> {
+        Ok(validate_enums(self)?)
+    }
+}
+
+impl ValidatedMessage for PolicyDecision {
+    fn validate(&self) -> Result<(), DecodeValidateError> {
+        Ok(validate_enums(self)?)
+    }
+}
+
+impl ValidatedMessage for SepEvent {
+    fn validate(&self) -> Result<(), DecodeValidateError> {
+        Ok(validate_object_ref(self)?)
+    }
+}
+
+impl ValidatedMessage for MorphologySetPayload {
+    fn validate(&self) -> Result<(), DecodeValidateError> {
+        Ok(validate_morphology(self)?)
+    }
+}
+
+impl ValidatedMessage for AssetManifest {
+    fn validate(&self) -> Result<(), DecodeValidateError> {
+        compute_manifest_digest(self)?;
+        Ok(())
+    }
+}
+
+impl ValidatedMessage for ReplayRunEvidence {
+    fn validate(&self) -> Result<(), DecodeValidateError> {
+        validate_micro_config_versions(self)?;
+        Ok(validate_timing(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canonical_bytes;
+    use crate::fixtures::{read_fixture, FixtureFormat};
+    use crate::testing::{decode_fixture, fixture_dir};
+
+    fn accepts<M: ValidatedMessage>(name: &str) {
+        let (bytes, _) =
+            read_fixture(&fixture_dir(), name, FixtureFormat::Hex).expect("fixture reads");
+        let message: M = decode_validated(&bytes).unwrap_or_else(|err| panic!("{name}: {err}"));
+        assert_eq!(canonical_bytes(&message), bytes, "{name}");
+    }
+
+    #[test]
+    fn fixtures_decode_validated() {
+        accepts::<ExperienceRecord>("experience_rt_action_exec");
+        accepts::<ControlFrame>("control_frame_m1_overlays_on");
+        accepts::<PolicyDecision>("policy_decision");
+        accepts::<SepEvent>("sep_event_chain_1");
+        accepts::<MorphologySetPayload>("biophys_morphology_set_v1");
+        accepts::<AssetManifest>("asset_manifest_v1");
+        accepts::<ReplayRunEvidence>("replay_run_evidence");
+    }
+
+    #[test]
+    fn rejects_undecodable_and_invalid_messages() {
+        assert!(matches!(
+            decode_validated::<PolicyDecision>(&[0x0A]),
+            Err(DecodeValidateError::Decode(_))
+        ));
+
+        let bad_enum = canonical_bytes(&PolicyDecision { decision: 99, ..Default::default() });
+        assert_eq!(
+            decode_validated::<PolicyDecision>(&bad_enum),
+            Err(DecodeValidateError::Enum(EnumError { field: "decision", value: 99 }))
+        );
+
+        let mut record: ExperienceRecord = decode_fixture("experience_rt_action_exec");
+        record.finalization_header = None;
+        assert!(matches!(
+            decode_validated::<ExperienceRecord>(&canonical_bytes(&record)),
+            Err(DecodeValidateError::Experience(ValidationError::FieldMissing {
+                field: "finalization_header",
+                ..
+            }))
+        ));
+
+        let mut manifest: AssetManifest = decode_fixture("asset_manifest_v1");
+        manifest.connectivity = None;
+        assert_eq!(
+            decode_validated::<AssetManifest>(&canonical_bytes(&manifest)),
+            Err(DecodeValidateError::Asset(AssetError::AssetMissing { field: "connectivity" }))
+        );
+
+        let mut run: ReplayRunEvidence = decode_fixture("replay_run_evidence");
+        run.substeps_per_tick = 0;
+        assert_eq!(
+            decode_validated::<ReplayRunEvidence>(&canonical_bytes(&run)),
+            Err(DecodeValidateError::Timing(TimingError::ZeroSubsteps))
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! `wasm-bindgen` exports for digesting and verifying UCF bytes from JavaScript.
+//!
+//! The surface is deliberately small and synchronous. Byte arguments arrive as
+//! `Uint8Array` and digests are returned as a 32-byte `Uint8Array`. Checks
+//! return `false` rather than throwing, including for an unknown schema id or
+//! bytes that do not decode.
+
+use prost::Message;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::envelope;
+use crate::ucf::v1::UcfEnvelope;
+
+/// [`crate::digest32`] of `bytes` under `domain`, `schema_id`, and `schema_version`.
+#[wasm_bindgen]
+pub fn digest32(domain: &str, schema_id: &str, schema_version: &str, bytes: &[u8]) -> Vec<u8> {
+    crate::digest32(domain, schema_id, schema_version, bytes).to_vec()
+}
+
+/// [`crate::is_canonical`] for the message named by `schema_id`.
+#[wasm_bindgen]
+pub fn is_canonical(schema_id: &str, bytes: &[u8]) -> bool {
+    is_canonical_schema(schema_id, bytes).is_some_and(|canonical| canonical.unwrap_or(false))
+}
+
+/// Decode `envelope_bytes` as a [`UcfEnvelope`] and check its payload digest
+/// with [`envelope::verify_envelope_payload`].
+#[wasm_bindgen]
+pub fn verify_envelope_payload(envelope_bytes: &[u8], domain: &str, schema_version: &str) -> bool {
+    UcfEnvelope::decode(envelope_bytes).is_ok_and(|envelope| {
+        envelope::verify_envelope_payload(&envelope, domain, schema_version).is_ok()
+    })
+}
+
+macro_rules! schemas {
+    ($($schema:literal => $message:path,)*) => {
+        /// [`crate::is_canonical`] for `schema`, or `None` for an unknown schema id.
+        fn is_canonical_schema(
+            schema: &str,
+            bytes: &[u8],
+        ) -> Option<Result<bool, prost::DecodeError>> {
+            match schema {
+                $($schema => Some(crate::is_canonical::<$message>(bytes)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+crate::for_each_schema!(schemas);
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::SigningKey;
+
+    use super::*;
+    use crate::testing::{decode_fixture_with_digest, minimal_envelope};
+    use crate::ucf::v1::{MsgType, ReasonCodes};
+    use crate::{canonical_bytes, Domain};
+
+    #[test]
+    fn digest_matches_the_stored_fixture() {
+        let (codes, expected) = decode_fixture_with_digest::<ReasonCodes>("reason_codes_basic");
+        let bytes = canonical_bytes(&codes);
+        let digest = digest32("ucf-core", "ucf.v1.ReasonCodes", "1", &bytes);
+        assert_eq!(digest.as_slice(), expected.as_slice());
+        assert!(is_canonical("ucf.v1.ReasonCodes", &bytes));
+        assert!(!is_canonical("ucf.v1.Nope", &bytes));
+        assert!(!is_canonical("ucf.v1.ReasonCodes", &[0xff]));
+    }
+
+    #[test]
+    fn envelope_payloads_verify() {
+        let sealed = minimal_envelope(MsgType::PolicyQuery, &SigningKey::from_bytes(&[5u8; 32]));
+        assert!(verify_envelope_payload(&canonical_bytes(&sealed), Domain::Core.as_str(), "1"));
+
+        let tampered = UcfEnvelope { payload: b"tampered".to_vec(), ..sealed };
+        assert!(!verify_envelope_payload(&canonical_bytes(&tampered), Domain::Core.as_str(), "1"));
+        assert!(!verify_envelope_payload(&[0xff], Domain::Core.as_str(), "1"));
+    }
+}
@@ -1,15 +1,17 @@
 #![forbid(unsafe_code)]
 
 use std::collections::HashSet;
-use std::fs;
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use prost::Message;
+use ucf_protocol::sep::compute_sep_event_digest;
+use ucf_protocol::testvectors::{load_binary_fixture, load_hex_fixture, FIXTURES};
 use ucf_protocol::ucf::v1::canonical_intent::Params as CanonicalIntentParams;
 use ucf_protocol::ucf::v1::*;
-use ucf_protocol::{canonical_bytes, digest32};
+use ucf_protocol::{
+    canonical_bytes, digest32_with, sorted_codes, verify_digest32, DigestAlgo, Domain,
+};
 
-const DOMAIN: &str = "ucf-core";
 const INTENT_SCHEMA: &str = "ucf.v1.CanonicalIntent";
 const POLICY_SCHEMA: &str = "ucf.v1.PolicyDecision";
 const PVGS_SCHEMA: &str = "ucf.v1.PVGSReceipt";
@@ -39,12 +41,6 @@ const CHANNEL_PARAMS_SET_SCHEMA: &str = "ucf.v1.ChannelParamsSetPayload";
 const SYNAPSE_PARAMS_SET_SCHEMA: &str = "ucf.v1.SynapseParamsSetPayload";
 const CONNECTIVITY_GRAPH_SCHEMA: &str = "ucf.v1.ConnectivityGraphPayload";
 const VERSION: &str = "1";
-const MICRO_CIRCUIT_DOMAIN: &str = "UCF:HASH:MC_CONFIG";
-const ASSET_MORPHOLOGY_DOMAIN: &str = "UCF:ASSET:MORPH";
-const ASSET_CHANNEL_PARAMS_DOMAIN: &str = "UCF:ASSET:CHANNEL_PARAMS";
-const ASSET_SYN_PARAMS_DOMAIN: &str = "UCF:ASSET:SYN_PARAMS";
-const ASSET_CONNECTIVITY_DOMAIN: &str = "UCF:ASSET:CONNECTIVITY";
-const ASSET_MANIFEST_DOMAIN: &str = "UCF:ASSET:MANIFEST";
 
 struct FixtureCase {
     name: &'static str,
@@ -72,54 +68,27 @@ const PROTO_FILES: &[&str] = &[
     "proto/ucf/v1/replay_run.proto",
 ];
 
-fn sorted_strings(items: &[&str]) -> Vec<String> {
-    let mut values: Vec<String> = items.iter().map(|item| item.to_string()).collect();
-    values.sort();
-    values
-}
-
-fn load_fixture(name: &str) -> Result<(Vec<u8>, [u8; 32])> {
-    let hex_bytes = fs::read_to_string(format!("testvectors/{name}.hex"))
-        .with_context(|| format!("reading {name}.hex"))?;
-    let bytes = hex::decode(hex_bytes.trim()).context("decoding fixture hex bytes")?;
-    let digest_hex = fs::read_to_string(format!("testvectors/{name}.digest"))
-        .with_context(|| format!("reading {name}.digest"))?;
-    let digest_vec = hex::decode(digest_hex.trim()).context("decoding digest hex")?;
-    let digest: [u8; 32] =
-        digest_vec.try_into().map_err(|_| anyhow::anyhow!("digest must be 32 bytes"))?;
-    Ok((bytes, digest))
-}
-
-fn load_binary_fixture(name: &str) -> Result<(Vec<u8>, [u8; 32])> {
-    let bytes = fs::read(format!("testvectors/{name}.bin"))
-        .with_context(|| format!("reading {name}.bin"))?;
-    let digest_hex = fs::read_to_string(format!("testvectors/{name}.digest"))
-        .with_context(|| format!("reading {name}.digest"))?;
-    let digest_vec = hex::decode(digest_hex.trim()).context("decoding digest hex")?;
-    let digest: [u8; 32] =
-        digest_vec.try_into().map_err(|_| anyhow::anyhow!("digest must be 32 bytes"))?;
-    Ok((bytes, digest))
-}
-
 fn verify_case<M>(name: &str, schema: &str, expected: M) -> Result<()>
 where
     M: Message + Default + Clone,
 {
-    verify_case_with_domain(name, schema, DOMAIN, expected)
+    verify_case_with_domain(name, schema, Domain::Core, expected)
 }
 
-fn verify_case_with_domain<M>(name: &str, schema: &str, domain: &str, expected: M) -> Result<()>
+fn verify_case_with_domain<M>(name: &str, schema: &str, domain: Domain, expected: M) -> Result<()>
 where
     M: Message + Default + Clone,
 {
-    let (fixture_bytes, fixture_digest) = load_fixture(name)?;
+    let (fixture_bytes, fixture_digest) = load_hex_fixture(name)?;
 
     let decoded = M::decode(fixture_bytes.as_slice())?;
     let encoded = canonical_bytes(&decoded);
     assert_eq!(fixture_bytes, encoded, "canonical bytes should be stable");
 
-    let digest = digest32(domain, schema, VERSION, &encoded);
-    assert_eq!(fixture_digest, digest, "digest should match stored fixture");
+    assert!(
+        verify_digest32(domain, schema, VERSION, &encoded, &fixture_digest),
+        "digest should match stored fixture"
+    );
 
     // Regenerate bytes from an explicitly constructed message to ensure parity.
     let constructed_bytes = canonical_bytes(&expected);
@@ -134,6 +103,22 @@ fn reason_codes_basic_case() -> Result<()> {
     verify_case("reason_codes_basic", REASON_CODES_SCHEMA, expected)
 }
 
+fn reason_codes_algo_case(name: &str, algo: DigestAlgo) -> Result<()> {
+    let (fixture_bytes, fixture_digest) = load_hex_fixture(name)?;
+    let (basic_bytes, _) = load_hex_fixture("reason_codes_basic")?;
+    assert_eq!(fixture_bytes, basic_bytes, "{name} encodes the reason_codes_basic message");
+    assert_eq!(
+        digest32_with(algo, Domain::Core, REASON_CODES_SCHEMA, VERSION, &fixture_bytes),
+        fixture_digest,
+        "{name} digest should match stored fixture"
+    );
+    Ok(())
+}
+
+fn reason_codes_sha256_case() -> Result<()> {
+    reason_codes_algo_case("reason_codes_sha256", DigestAlgo::Sha256)
+}
+
 fn ucf_envelope_policy_decision_case() -> Result<()> {
     let expected = UcfEnvelope {
         epoch_id: "epoch-1".to_string(),
@@ -216,7 +201,7 @@ fn asset_digest_morphology_case() -> Result<()> {
     verify_case_with_domain(
         "asset_digest_morphology_v1",
         ASSET_DIGEST_SCHEMA,
-        ASSET_MORPHOLOGY_DOMAIN,
+        Domain::AssetMorphology,
         expected,
     )
 }
@@ -274,7 +259,7 @@ fn asset_manifest_case() -> Result<()> {
     verify_case_with_domain(
         "asset_manifest_v1",
         ASSET_MANIFEST_SCHEMA,
-        ASSET_MANIFEST_DOMAIN,
+        Domain::AssetManifest,
         expected,
     )
 }
@@ -334,7 +319,7 @@ fn biophys_morphology_set_case() -> Result<()> {
     verify_case_with_domain(
         "biophys_morphology_set_v1",
         MORPHOLOGY_SET_SCHEMA,
-        ASSET_MORPHOLOGY_DOMAIN,
+        Domain::AssetMorphology,
         expected,
     )
 }
@@ -368,7 +353,7 @@ fn biophys_channel_params_set_case() -> Result<()> {
     verify_case_with_domain(
         "biophys_channel_params_set_v1",
         CHANNEL_PARAMS_SET_SCHEMA,
-        ASSET_CHANNEL_PARAMS_DOMAIN,
+        Domain::AssetChannelParams,
         expected,
     )
 }
@@ -408,7 +393,7 @@ fn biophys_synapse_params_set_case() -> Result<()> {
     verify_case_with_domain(
         "biophys_synapse_params_set_v1",
         SYNAPSE_PARAMS_SET_SCHEMA,
-        ASSET_SYN_PARAMS_DOMAIN,
+        Domain::AssetSynapseParams,
         expected,
     )
 }
@@ -426,7 +411,7 @@ fn biophys_connectivity_graph_case() -> Result<()> {
     verify_case_with_domain(
         "biophys_connectivity_graph_v1",
         CONNECTIVITY_GRAPH_SCHEMA,
-        ASSET_CONNECTIVITY_DOMAIN,
+        Domain::AssetConnectivity,
         expected,
     )
 }
@@ -612,7 +597,7 @@ fn experience_record_rt_perception_case() -> Result<()> {
         candidate_refs,
         workspace_mode: WorkMode::WmSimulate as i32,
         core_embedding_digest: Some(Digest32 { value: vec![0x01; 32] }),
-        reason_codes: Some(ReasonCodes { codes: sorted_strings(&["ingest", "perception"]) }),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["ingest", "perception"]) }),
     };
 
     let _metabolic_frame = MetabolicFrame {
@@ -632,7 +617,7 @@ fn experience_record_rt_perception_case() -> Result<()> {
             uri: "hpa://baseline/seed".to_string(),
             label: "hpa".to_string(),
         }),
-        reason_codes: Some(ReasonCodes { codes: sorted_strings(&["metabolic-baseline"]) }),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["metabolic-baseline"]) }),
     };
 
     verify_case("experience_rt_perception", EXPERIENCE_SCHEMA, expected)
@@ -711,7 +696,7 @@ fn experience_record_rt_action_exec_case() -> Result<()> {
         }],
         workspace_mode: WorkMode::WmExecPlan as i32,
         core_embedding_digest: Some(Digest32 { value: vec![0x02; 32] }),
-        reason_codes: Some(ReasonCodes { codes: sorted_strings(&["action", "execute"]) }),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["action", "execute"]) }),
     };
 
     let _metabolic_frame = MetabolicFrame {
@@ -728,7 +713,7 @@ fn experience_record_rt_action_exec_case() -> Result<()> {
         noise_class: NoiseClass::Med as i32,
         priority_class: PriorityClass::High as i32,
         hpa_baseline_ref: None,
-        reason_codes: Some(ReasonCodes { codes: sorted_strings(&["metabolic-action"]) }),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["metabolic-action"]) }),
     };
 
     let mut dlp_refs =
@@ -747,7 +732,7 @@ fn experience_record_rt_action_exec_case() -> Result<()> {
             uri: "pvgs://receipt/alpha".to_string(),
             label: "pvgs".to_string(),
         }),
-        reason_codes: Some(ReasonCodes { codes: sorted_strings(&["policy-check", "pvgs-gate"]) }),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["policy-check", "pvgs-gate"]) }),
     };
 
     verify_case("experience_rt_action_exec", EXPERIENCE_SCHEMA, expected)
@@ -817,7 +802,7 @@ fn experience_record_rt_output_case() -> Result<()> {
         }],
         workspace_mode: WorkMode::WmReport as i32,
         core_embedding_digest: Some(Digest32 { value: vec![0x03; 32] }),
-        reason_codes: Some(ReasonCodes { codes: sorted_strings(&["output", "ready"]) }),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["output", "ready"]) }),
     };
 
     let _metabolic_frame = MetabolicFrame {
@@ -834,7 +819,7 @@ fn experience_record_rt_output_case() -> Result<()> {
         noise_class: NoiseClass::Low as i32,
         priority_class: PriorityClass::Med as i32,
         hpa_baseline_ref: None,
-        reason_codes: Some(ReasonCodes { codes: sorted_strings(&["metabolic-output"]) }),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["metabolic-output"]) }),
     };
 
     let mut policy_decision_refs =
@@ -853,9 +838,7 @@ fn experience_record_rt_output_case() -> Result<()> {
             label: "budget".to_string(),
         }),
         pvgs_receipt_ref: None,
-        reason_codes: Some(ReasonCodes {
-            codes: sorted_strings(&["dlp-approved", "output-ready"]),
-        }),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["dlp-approved", "output-ready"]) }),
     };
 
     verify_case("experience_rt_output", EXPERIENCE_SCHEMA, expected)
@@ -919,7 +902,7 @@ fn microcircuit_config_lc_case() -> Result<()> {
     verify_case_with_domain(
         "microcircuit_config_lc_v1",
         MICRO_CIRCUIT_SCHEMA,
-        MICRO_CIRCUIT_DOMAIN,
+        Domain::McConfig,
         expected,
     )
 }
@@ -939,7 +922,7 @@ fn microcircuit_config_sn_case() -> Result<()> {
     verify_case_with_domain(
         "microcircuit_config_sn_v1",
         MICRO_CIRCUIT_SCHEMA,
-        MICRO_CIRCUIT_DOMAIN,
+        Domain::McConfig,
         expected,
     )
 }
@@ -962,12 +945,11 @@ fn microcircuit_config_hpa_case() -> Result<()> {
     verify_case_with_domain(
         "microcircuit_config_hpa_v1",
         MICRO_CIRCUIT_SCHEMA,
-        MICRO_CIRCUIT_DOMAIN,
+        Domain::McConfig,
         expected,
     )
 }
 
-#[test]
 fn microcircuit_config_hpa_bin_case() -> Result<()> {
     let (fixture_bytes, fixture_digest) = load_binary_fixture("mc_cfg_hpa")?;
 
@@ -975,8 +957,10 @@ fn microcircuit_config_hpa_bin_case() -> Result<()> {
     let encoded = canonical_bytes(&decoded);
     assert_eq!(fixture_bytes, encoded, "canonical bytes should be stable");
 
-    let digest = digest32(MICRO_CIRCUIT_DOMAIN, MICRO_CIRCUIT_SCHEMA, VERSION, &encoded);
-    assert_eq!(fixture_digest, digest, "digest should match stored fixture");
+    assert!(
+        verify_digest32(Domain::McConfig, MICRO_CIRCUIT_SCHEMA, VERSION, &encoded, &fixture_digest),
+        "digest should match stored fixture"
+    );
 
     let expected = MicrocircuitConfigEvidence {
         module: MicroModule::Hpa as i32,
@@ -1135,7 +1119,7 @@ fn replay_plan_asset_manifest_ref_case() -> Result<()> {
         vrf_digest_ref: None,
         proof_receipt_ref: None,
         asset_manifest_ref: Some(Ref {
-            uri: "asset_manifest".to_string(),
+            uri: "ucf://assets/manifest".to_string(),
             label: "manifest-digest".to_string(),
         }),
     };
@@ -1353,7 +1337,7 @@ fn tool_onboarding_event_case() -> Result<()> {
         action_id: "read-latest".to_string(),
         stage: OnboardingStage::To6Suspended as i32,
         stage_reason_codes: Some(ReasonCodes {
-            codes: sorted_strings(&["missing-attestation", "risk-review"]),
+            codes: sorted_codes(&["missing-attestation", "risk-review"]),
         }),
         required_artifact_digests: vec![Digest32 { value: vec![0x05; 32] }],
         test_evidence_refs: vec![Ref {
@@ -1424,7 +1408,7 @@ fn approval_artifact_package_case() -> Result<()> {
         risk_level: RiskLevel::Medium as i32,
         requested_data_class: DataClass::Restricted as i32,
         constraints_proposal: Some(ConstraintsDelta {
-            constraints_added: sorted_strings(&["approval-required", "scope-narrowing"]),
+            constraints_added: sorted_codes(&["approval-required", "scope-narrowing"]),
             constraints_removed: vec!["legacy-exception".to_string()],
         }),
         alternatives,
@@ -1446,7 +1430,7 @@ fn approval_decision_case() -> Result<()> {
             constraints_added: vec!["cooldown-required".to_string()],
             constraints_removed: vec![],
         }),
-        reason_codes: Some(ReasonCodes { codes: sorted_strings(&["two-person", "risk-review"]) }),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["two-person", "risk-review"]) }),
         signatures: vec![Signature {
             algorithm: "ed25519".to_string(),
             signer: vec![0xAA],
@@ -1468,89 +1452,88 @@ fn approval_decision_case() -> Result<()> {
     verify_case("approval_decision", APPROVAL_DECISION_SCHEMA, expected)
 }
 
-fn sep_event_chain_case() -> Result<()> {
-    let events = vec![
-        (
-            "sep_event_chain_1",
-            SepEvent {
-                event_id: "evt-1".to_string(),
-                session_id: "session-9000".to_string(),
-                event_type: SepEventType::EvIntent as i32,
-                object_ref: Some(Ref {
-                    uri: "intent://primary/42".to_string(),
-                    label: "intent".to_string(),
-                }),
-                reason_codes: Some(ReasonCodes { codes: vec!["init".to_string()] }),
-                timestamp_ms: 1_700_002_000,
-                prev_event_digest: Some(Digest32 { value: vec![0x00; 32] }),
-                event_digest: Some(Digest32 { value: vec![0x10; 32] }),
-                attestation_sig: Some(Signature {
-                    algorithm: "ed25519".to_string(),
-                    signer: vec![0x01],
-                    signature: vec![0x02],
-                }),
-                epoch_id: 100,
-            },
-        ),
-        (
-            "sep_event_chain_2",
-            SepEvent {
-                event_id: "evt-2".to_string(),
-                session_id: "session-9000".to_string(),
-                event_type: SepEventType::EvDecision as i32,
-                object_ref: Some(Ref {
-                    uri: "decision://approval".to_string(),
-                    label: "decision".to_string(),
-                }),
-                reason_codes: Some(ReasonCodes { codes: vec!["policy".to_string()] }),
-                timestamp_ms: 1_700_002_500,
-                prev_event_digest: Some(Digest32 { value: vec![0x10; 32] }),
-                event_digest: Some(Digest32 { value: vec![0x20; 32] }),
-                attestation_sig: Some(Signature {
-                    algorithm: "ed25519".to_string(),
-                    signer: vec![0x03],
-                    signature: vec![0x04],
-                }),
-                epoch_id: 100,
-            },
-        ),
-        (
-            "sep_event_chain_3",
-            SepEvent {
-                event_id: "evt-3".to_string(),
-                session_id: "session-9000".to_string(),
-                event_type: SepEventType::EvOutcome as i32,
-                object_ref: Some(Ref {
-                    uri: "outcome://result".to_string(),
-                    label: "outcome".to_string(),
-                }),
-                reason_codes: Some(ReasonCodes { codes: vec!["success".to_string()] }),
-                timestamp_ms: 1_700_003_000,
-                prev_event_digest: Some(Digest32 { value: vec![0x20; 32] }),
-                event_digest: Some(Digest32 { value: vec![0x30; 32] }),
-                attestation_sig: Some(Signature {
-                    algorithm: "ed25519".to_string(),
-                    signer: vec![0x05],
-                    signature: vec![0x06],
-                }),
-                epoch_id: 101,
-            },
-        ),
-    ];
+/// The three-event SEP chain behind the `sep_event_chain_*` fixtures.
+fn sep_event_chain() -> [SepEvent; 3] {
+    let evt_1 = with_event_digest(SepEvent {
+        event_id: "evt-1".to_string(),
+        session_id: "session-9000".to_string(),
+        event_type: SepEventType::EvIntent as i32,
+        object_ref: Some(Ref {
+            uri: "intent://primary/42".to_string(),
+            label: "intent".to_string(),
+        }),
+        reason_codes: Some(ReasonCodes { codes: vec!["init".to_string()] }),
+        timestamp_ms: 1_700_002_000,
+        prev_event_digest: Some(Digest32 { value: vec![0x00; 32] }),
+        event_digest: None,
+        attestation_sig: Some(Signature {
+            algorithm: "ed25519".to_string(),
+            signer: vec![0x01],
+            signature: vec![0x02],
+        }),
+        epoch_id: 100,
+    });
+    let evt_2 = with_event_digest(SepEvent {
+        event_id: "evt-2".to_string(),
+        session_id: "session-9000".to_string(),
+        event_type: SepEventType::EvDecision as i32,
+        object_ref: Some(Ref {
+            uri: "decision://approval".to_string(),
+            label: "decision".to_string(),
+        }),
+        reason_codes: Some(ReasonCodes { codes: vec!["policy".to_string()] }),
+        timestamp_ms: 1_700_002_500,
+        prev_event_digest: evt_1.event_digest.clone(),
+        event_digest: None,
+        attestation_sig: Some(Signature {
+            algorithm: "ed25519".to_string(),
+            signer: vec![0x03],
+            signature: vec![0x04],
+        }),
+        epoch_id: 100,
+    });
+    let evt_3 = with_event_digest(SepEvent {
+        event_id: "evt-3".to_string(),
+        session_id: "session-9000".to_string(),
+        event_type: SepEventType::EvOutcome as i32,
+        object_ref: Some(Ref { uri: "outcome://result".to_string(), label: "outcome".to_string() }),
+        reason_codes: Some(ReasonCodes { codes: vec!["success".to_string()] }),
+        timestamp_ms: 1_700_003_000,
+        prev_event_digest: evt_2.event_digest.clone(),
+        event_digest: None,
+        attestation_sig: Some(Signature {
+            algorithm: "ed25519".to_string(),
+            signer: vec![0x05],
+            signature: vec![0x06],
+        }),
+        epoch_id: 101,
+    });
+    [evt_1, evt_2, evt_3]
+}
 
-    for (name, expected) in events {
-        verify_case(name, SEP_EVENT_SCHEMA, expected.clone())?;
-    }
+fn sep_event_chain_case(name: &str) -> Result<()> {
+    let [evt_1, evt_2, evt_3] = sep_event_chain();
+    let expected = match name {
+        "sep_event_chain_1" => evt_1,
+        "sep_event_chain_2" => evt_2,
+        "sep_event_chain_3" => evt_3,
+        _ => anyhow::bail!("unknown sep_event_chain fixture {name}"),
+    };
+    verify_case(name, SEP_EVENT_SCHEMA, expected)
+}
 
-    Ok(())
+fn with_event_digest(mut event: SepEvent) -> SepEvent {
+    event.event_digest = Some(Digest32 { value: compute_sep_event_digest(&event).to_vec() });
+    event
 }
 
 fn session_seal_case() -> Result<()> {
+    let [_, _, evt_3] = sep_event_chain();
     let expected = SessionSeal {
         seal_id: "seal-9000".to_string(),
         seal_digest: Some(Digest32 { value: vec![0xAB; 32] }),
         session_id: "session-9000".to_string(),
-        final_event_digest: Some(Digest32 { value: vec![0x30; 32] }),
+        final_event_digest: evt_3.event_digest,
         final_record_digest: Some(Digest32 { value: vec![0xCD; 32] }),
         proof_receipt_ref: Some(Ref {
             uri: "proof://session/receipt".to_string(),
@@ -1573,7 +1556,7 @@ fn completeness_report_case() -> Result<()> {
             label: "missing".to_string(),
         }],
         missing_edges: vec!["evt-2->evt-4".to_string(), "evt-1->evt-3".to_string()],
-        reason_codes: Some(ReasonCodes { codes: sorted_strings(&["edge-gap", "missing-node"]) }),
+        reason_codes: Some(ReasonCodes { codes: sorted_codes(&["edge-gap", "missing-node"]) }),
         proof_receipt_ref: Some(Ref {
             uri: "proof://completeness/receipt".to_string(),
             label: "proof".to_string(),
@@ -1680,6 +1663,12 @@ const FIXTURE_CASES: &[FixtureCase] = &[
         proto_files: &["proto/ucf/v1/milestones.proto", "proto/ucf/v1/common.proto"],
         verify: macro_milestone_finalized_case,
     },
+    FixtureCase {
+        name: "mc_cfg_hpa",
+        schema: MICRO_CIRCUIT_SCHEMA,
+        proto_files: &["proto/ucf/v1/microcircuit.proto", "proto/ucf/v1/common.proto"],
+        verify: microcircuit_config_hpa_bin_case,
+    },
     FixtureCase {
         name: "meso_milestone_stable",
         schema: MESO_MILESTONE_SCHEMA,
@@ -1728,6 +1717,12 @@ const FIXTURE_CASES: &[FixtureCase] = &[
         proto_files: &["proto/ucf/v1/common.proto"],
         verify: reason_codes_basic_case,
     },
+    FixtureCase {
+        name: "reason_codes_sha256",
+        schema: REASON_CODES_SCHEMA,
+        proto_files: &["proto/ucf/v1/common.proto"],
+        verify: reason_codes_sha256_case,
+    },
     FixtureCase {
         name: "replay_plan_asset_manifest_ref",
         schema: REPLAY_PLAN_SCHEMA,
@@ -1750,7 +1745,19 @@ const FIXTURE_CASES: &[FixtureCase] = &[
         name: "sep_event_chain_1",
         schema: SEP_EVENT_SCHEMA,
         proto_files: &["proto/ucf/v1/sep.proto", "proto/ucf/v1/common.proto"],
-        verify: sep_event_chain_case,
+        verify: || sep_event_chain_case("sep_event_chain_1"),
+    },
+    FixtureCase {
+        name: "sep_event_chain_2",
+        schema: SEP_EVENT_SCHEMA,
+        proto_files: &["proto/ucf/v1/sep.proto", "proto/ucf/v1/common.proto"],
+        verify: || sep_event_chain_case("sep_event_chain_2"),
+    },
+    FixtureCase {
+        name: "sep_event_chain_3",
+        schema: SEP_EVENT_SCHEMA,
+        proto_files: &["proto/ucf/v1/sep.proto", "proto/ucf/v1/common.proto"],
+        verify: || sep_event_chain_case("sep_event_chain_3"),
     },
     FixtureCase {
         name: "session_seal",
@@ -1791,6 +1798,12 @@ fn fixture_registry_is_complete() -> Result<()> {
     sorted.sort();
     assert_eq!(names, sorted, "fixture registry should be sorted by name");
 
+    let published: Vec<(&str, &str)> =
+        FIXTURES.iter().map(|spec| (spec.name, spec.schema)).collect();
+    let registered: Vec<(&str, &str)> =
+        FIXTURE_CASES.iter().map(|case| (case.name, case.schema)).collect();
+    assert_eq!(registered, published, "fixture registry should match testvectors::FIXTURES");
+
     let mut covered_protos: HashSet<&str> = HashSet::new();
     for case in FIXTURE_CASES {
         assert!(!case.schema.is_empty(), "schema identifier must be set for {}", case.name);
@@ -0,0 +1,96 @@
+#![forbid(unsafe_code)]
+#![cfg(feature = "dynamic")]
+
+use std::collections::BTreeSet;
+
+use prost::Message;
+use prost_reflect::{DynamicMessage, ReflectMessage, Value};
+use ucf_protocol::dynamic::{decode_dynamic, DynamicError};
+use ucf_protocol::reason_codes::describe;
+use ucf_protocol::refs::AllowedSchemes;
+use ucf_protocol::testing::FIXTURES;
+use ucf_protocol::testvectors::load_fixture;
+use ucf_protocol::ucf::v1::{PolicyDecision, ReasonCodes, Ref};
+
+#[test]
+fn decodes_policy_decision_by_schema_id() {
+    let (bytes, _) = load_fixture("policy_decision").expect("fixture loads");
+    let expected = PolicyDecision::decode(bytes.as_slice()).expect("fixture decodes");
+
+    let message = decode_dynamic("ucf.v1.PolicyDecision", &bytes).expect("decodes dynamically");
+    assert_eq!(message.descriptor().full_name(), "ucf.v1.PolicyDecision");
+    assert_eq!(
+        message.get_field_by_name("decision").as_deref(),
+        Some(&Value::EnumNumber(expected.decision))
+    );
+    assert_eq!(message.encode_to_vec(), bytes);
+}
+
+#[test]
+fn rejects_unknown_schema_ids() {
+    assert_eq!(
+        decode_dynamic("ucf.v1.NoSuchMessage", &[]).unwrap_err(),
+        DynamicError::UnknownSchema("ucf.v1.NoSuchMessage".to_string())
+    );
+}
+
+/// Every message named `full_name` nested anywhere in `message`, including itself.
+fn nested(message: &DynamicMessage, full_name: &str, found: &mut Vec<DynamicMessage>) {
+    if message.descriptor().full_name() == full_name {
+        found.push(message.clone());
+        return;
+    }
+    for (_, value) in message.fields() {
+        nested_in_value(value, full_name, found);
+    }
+}
+
+fn nested_in_value(value: &Value, full_name: &str, found: &mut Vec<DynamicMessage>) {
+    match value {
+        Value::Message(message) => nested(message, full_name, found),
+        Value::List(values) => {
+            values.iter().for_each(|value| nested_in_value(value, full_name, found))
+        }
+        Value::Map(entries) => {
+            entries.values().for_each(|value| nested_in_value(value, full_name, found))
+        }
+        _ => {}
+    }
+}
+
+/// Every `full_name` message nested in any registered fixture, with the fixture's name.
+fn fixture_messages<M: Message + Default>(full_name: &str) -> Vec<(&'static str, M)> {
+    let mut messages = Vec::new();
+    for spec in FIXTURES {
+        let (bytes, _) = load_fixture(spec.name).expect("fixture loads");
+        let message = decode_dynamic(spec.schema, &bytes).expect("fixture decodes");
+        let mut found = Vec::new();
+        nested(&message, full_name, &mut found);
+        messages.extend(
+            found.iter().map(|message| (spec.name, message.transcode_to().expect("transcodes"))),
+        );
+    }
+    messages
+}
+
+#[test]
+fn every_fixture_ref_is_a_valid_uri() {
+    let refs = fixture_messages::<Ref>("ucf.v1.Ref");
+    assert!(!refs.is_empty());
+    for (name, reference) in refs {
+        assert_eq!(reference.validate(&AllowedSchemes::default()), Ok(()), "{name}: {reference:?}");
+    }
+}
+
+#[test]
+fn every_fixture_reason_code_is_described() {
+    let codes = fixture_messages::<ReasonCodes>("ucf.v1.ReasonCodes");
+    assert!(!codes.is_empty());
+    let undescribed: BTreeSet<&str> = codes
+        .iter()
+        .flat_map(|(_, codes)| &codes.codes)
+        .map(String::as_str)
+        .filter(|code| describe(code).is_none())
+        .collect();
+    assert!(undescribed.is_empty(), "undescribed reason codes: {undescribed:?}");
+}
@@ -0,0 +1,52 @@
+#![forbid(unsafe_code)]
+#![cfg(feature = "grpc")]
+
+use ed25519_dalek::SigningKey;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+use tonic::{Request, Response, Status};
+use ucf_protocol::crypto::AllowedAlgorithms;
+use ucf_protocol::envelope;
+use ucf_protocol::testing::minimal_envelope;
+use ucf_protocol::ucf::v1::ucf_exchange_client::UcfExchangeClient;
+use ucf_protocol::ucf::v1::ucf_exchange_server::{UcfExchange, UcfExchangeServer};
+use ucf_protocol::ucf::v1::{MsgType, ProofReceipt, ReceiptStatus, UcfEnvelope};
+
+/// Accepts envelopes whose payload digest and signature verify.
+struct Validator;
+
+#[tonic::async_trait]
+impl UcfExchange for Validator {
+    async fn submit(
+        &self,
+        request: Request<UcfEnvelope>,
+    ) -> Result<Response<ProofReceipt>, Status> {
+        envelope::verify(request.get_ref(), &AllowedAlgorithms::default())
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        Ok(Response::new(ProofReceipt {
+            status: ReceiptStatus::Accepted as i32,
+            ..Default::default()
+        }))
+    }
+}
+
+#[tokio::test]
+async fn envelopes_round_trip_over_grpc() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("loopback binds");
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        Server::builder()
+            .add_service(UcfExchangeServer::new(Validator))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    let mut client = UcfExchangeClient::connect(format!("http://{addr}")).await.expect("connects");
+
+    let sealed = minimal_envelope(MsgType::PolicyQuery, &SigningKey::from_bytes(&[5u8; 32]));
+    let receipt = client.submit(sealed.clone()).await.expect("submit succeeds").into_inner();
+    assert_eq!(receipt.status, ReceiptStatus::Accepted as i32);
+
+    let tampered = UcfEnvelope { payload: b"tampered".to_vec(), ..sealed };
+    let status = client.submit(tampered).await.expect_err("tampered payload is rejected");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+}
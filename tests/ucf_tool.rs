@@ -0,0 +1,58 @@
+#![forbid(unsafe_code)]
+
+use std::process::{Command, Output};
+
+fn ucf_tool(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ucf-tool"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .expect("ucf-tool runs")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).expect("stdout is utf-8")
+}
+
+#[test]
+fn verify_reports_registered_fixtures() {
+    let output = ucf_tool(&["verify", "control_frame_m1_overlays_on"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "ok        control_frame_m1_overlays_on\n");
+
+    let output = ucf_tool(&["verify", "no_such_fixture"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn digest_matches_the_stored_fixture_digest() {
+    let output = ucf_tool(&[
+        "digest",
+        "--domain",
+        "ucf-core",
+        "--schema",
+        "ucf.v1.ReasonCodes",
+        "testvectors/reason_codes_sha256.hex",
+        "--algo",
+        "sha256",
+    ]);
+    assert!(output.status.success());
+    let stored = std::fs::read_to_string("testvectors/reason_codes_sha256.digest").unwrap();
+    assert_eq!(stdout(&output), stored);
+}
+
+#[test]
+fn decode_renders_the_message() {
+    let output = ucf_tool(&[
+        "decode",
+        "--schema",
+        "ucf.v1.ReasonCodes",
+        "testvectors/reason_codes_basic.hex",
+    ]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("deterministic"));
+
+    let output =
+        ucf_tool(&["decode", "--schema", "ucf.v1.Nope", "testvectors/reason_codes_basic.hex"]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,18 @@
+#![forbid(unsafe_code)]
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+//! Smoke test for the `wasm` exports, run with
+//! `wasm-pack test --node -- --features wasm`.
+
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const REASON_CODES_HEX: &str = include_str!("../testvectors/reason_codes_basic.hex");
+const REASON_CODES_DIGEST: &str = include_str!("../testvectors/reason_codes_basic.digest");
+
+#[wasm_bindgen_test]
+fn digests_reason_codes_fixture() {
+    let bytes = ucf_protocol::codec::normalize_hex_fixture(REASON_CODES_HEX).unwrap();
+    let digest = ucf_protocol::wasm::digest32("ucf-core", "ucf.v1.ReasonCodes", "1", &bytes);
+    assert_eq!(hex::encode(digest), REASON_CODES_DIGEST.trim());
+    assert!(ucf_protocol::wasm::is_canonical("ucf.v1.ReasonCodes", &bytes));
+}